@@ -1,7 +1,7 @@
 #!/usr/bin/env bash
 
 # Скрипт для запуска дашборда фотографа
-# Использование: ./run.sh [город] [широта] [долгота] [api_key]
+# Использование: ./run.sh [город] [широта] [долгота] [api_key] [параметры my_dashboard...]
 
 echo "📸 Запуск дашборда для фотографов"
 echo "=================================="
@@ -31,10 +31,7 @@ if [ -z "$4" ] && [ -n "$OPENWEATHER_API_KEY" ]; then
     echo "ℹ️  Используется API ключ из переменной окружения OPENWEATHER_API_KEY"
 fi
 
-# Установка переменных окружения
-export CITY="$CITY"
-export LATITUDE="$LATITUDE"
-export LONGITUDE="$LONGITUDE"
+# API ключ передается через окружение, чтобы не попадать в список процессов
 export OPENWEATHER_API_KEY="$OPENWEATHER_API_KEY"
 
 # Проверяем, что Cargo установлен
@@ -52,8 +49,8 @@ fi
 echo "🚀 Запуск дашборда..."
 echo ""
 
-# Запуск приложения
-cargo run
+# Запуск приложения (дополнительные параметры передаются как есть, например --format json)
+cargo run -- --city "$CITY" --lat "$LATITUDE" --lon "$LONGITUDE" "${@:5}"
 
 echo ""
 echo "✅ Дашборд завершен" 
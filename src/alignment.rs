@@ -0,0 +1,397 @@
+//! # Alignment Module
+//!
+//! Поиск совпадений Солнца или Луны с ориентиром: моментов, когда светило
+//! видно точно над заданным объектом (башней, вершиной, маяком). Направление
+//! и угол места ориентира рассчитываются по координатам наблюдателя и цели,
+//! положение светил - теми же формулами, что и в [`golden_hour`](crate::golden_hour)
+//! и [`moon`](crate::moon).
+//!
+//! ## Основные компоненты
+//!
+//! - [`AlignmentFinder`] - Поиск совпадений на несколько дней вперед
+//! - [`TargetGeometry`] - Азимут, угол места и расстояние до ориентира
+//! - [`Alignment`] - Найденное совпадение
+//! - [`Body`] - Солнце или Луна
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use chrono::{TimeZone, Utc};
+//! use my_dashboard::alignment::AlignmentFinder;
+//! use my_dashboard::location::Location;
+//!
+//! let observer = Location::new("Воробьевы горы", 55.7105, 37.5532).with_elevation(200.0);
+//! let target = Location::new("Москва-Сити", 55.7496, 37.5377).with_elevation(140.0);
+//!
+//! // Башня высотой 370 м над уровнем земли
+//! let finder = AlignmentFinder::new(&observer, &target, 370.0).unwrap();
+//! println!(
+//!     "Азимут {:.1}°, угол места {:.2}°",
+//!     finder.target().bearing,
+//!     finder.target().elevation_angle
+//! );
+//!
+//! let start = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+//! for alignment in finder.find(start, 30) {
+//!     println!("{} {}", alignment.body.name(), alignment.time.format("%d.%m %H:%M"));
+//! }
+//! ```
+
+use crate::golden_hour::GoldenHourService;
+use crate::location::Location;
+use crate::moon::MoonService;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+/// Средний радиус Земли в километрах
+const EARTH_RADIUS_KM: f64 = 6_371.0;
+
+/// Коэффициент земной рефракции для луча вдоль поверхности
+const TERRESTRIAL_REFRACTION: f64 = 0.13;
+
+/// Допуск по азимуту и углу места по умолчанию, градусы
+///
+/// Видимый диаметр Солнца и Луны - около 0.5°: при отклонении до 1°
+/// светило касается ориентира.
+pub const DEFAULT_TOLERANCE: f64 = 1.0;
+
+/// Шаг перебора времени
+const SEARCH_STEP_MINUTES: i64 = 2;
+
+/// Светило
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Body {
+    /// Солнце
+    Sun,
+    /// Луна
+    Moon,
+}
+
+impl Body {
+    /// Название на русском языке
+    pub fn name(self) -> &'static str {
+        match self {
+            Body::Sun => "Солнце",
+            Body::Moon => "Луна",
+        }
+    }
+}
+
+/// Положение ориентира относительно наблюдателя
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TargetGeometry {
+    /// Азимут на ориентир в градусах (0 - север, 90 - восток)
+    pub bearing: f64,
+    /// Видимый угол места вершины ориентира в градусах с учетом кривизны Земли
+    pub elevation_angle: f64,
+    /// Расстояние до ориентира в километрах
+    pub distance_km: f64,
+}
+
+impl TargetGeometry {
+    /// Рассчитывает направление и угол места вершины ориентира
+    ///
+    /// # Аргументы
+    ///
+    /// * `observer` - Место съемки (высота над уровнем моря из `elevation`)
+    /// * `target` - Ориентир (высота основания над уровнем моря из `elevation`)
+    /// * `target_height` - Высота ориентира над основанием в метрах
+    pub fn between(observer: &Location, target: &Location, target_height: f64) -> Self {
+        let (lat1, lat2) = (observer.latitude.to_radians(), target.latitude.to_radians());
+        let delta_lon = (target.longitude - observer.longitude).to_radians();
+        let delta_lat = lat2 - lat1;
+
+        // Расстояние по большому кругу (формула гаверсинусов)
+        let a = (delta_lat / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+        let distance_km = 2.0 * EARTH_RADIUS_KM * a.sqrt().asin();
+
+        // Начальный азимут
+        let bearing = (delta_lon.sin() * lat2.cos())
+            .atan2(lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * delta_lon.cos())
+            .to_degrees()
+            .rem_euclid(360.0);
+
+        // Превышение вершины с поправкой на кривизну Земли и рефракцию
+        let distance_m = distance_km * 1000.0;
+        let drop =
+            distance_m.powi(2) * (1.0 - TERRESTRIAL_REFRACTION) / (2.0 * EARTH_RADIUS_KM * 1000.0);
+        let rise = target.elevation + target_height - observer.elevation - drop;
+
+        Self {
+            bearing,
+            elevation_angle: rise.atan2(distance_m).to_degrees(),
+            distance_km,
+        }
+    }
+}
+
+/// Момент, когда светило видно над ориентиром
+#[derive(Debug, Clone, Serialize)]
+pub struct Alignment {
+    /// Светило
+    pub body: Body,
+    /// Время наилучшего совпадения в часовом поясе наблюдателя
+    pub time: DateTime<Tz>,
+    /// Азимут светила в градусах
+    pub azimuth: f64,
+    /// Видимая высота центра светила в градусах (с рефракцией)
+    pub altitude: f64,
+    /// Отклонение от азимута ориентира в градусах (+ - правее)
+    pub azimuth_error: f64,
+    /// Отклонение от угла места ориентира в градусах (+ - выше)
+    pub altitude_error: f64,
+    /// Освещенность Луны (0-1), для Солнца `None`
+    pub moon_illumination: Option<f64>,
+}
+
+/// Поиск совпадений Солнца и Луны с ориентиром
+pub struct AlignmentFinder {
+    target: TargetGeometry,
+    sun: GoldenHourService,
+    moon: MoonService,
+    tolerance: f64,
+    timezone: Tz,
+}
+
+impl AlignmentFinder {
+    /// Создает поиск для наблюдателя и ориентира
+    ///
+    /// Возвращает ошибку, если координаты некорректны или ориентир
+    /// совпадает с точкой наблюдения.
+    pub fn new(observer: &Location, target: &Location, target_height: f64) -> Result<Self> {
+        observer.validate()?;
+        target.validate()?;
+        if !target_height.is_finite() || target_height < 0.0 {
+            bail!(
+                "Некорректная высота ориентира '{}': {} м",
+                target.name,
+                target_height
+            );
+        }
+
+        let target = TargetGeometry::between(observer, target, target_height);
+        if target.distance_km < 0.01 {
+            bail!("Ориентир совпадает с точкой наблюдения");
+        }
+
+        Ok(Self {
+            target,
+            sun: GoldenHourService::new(observer.latitude, observer.longitude),
+            moon: MoonService::new(observer.latitude, observer.longitude),
+            tolerance: DEFAULT_TOLERANCE,
+            timezone: observer.timezone(),
+        })
+    }
+
+    /// Задает допуск по азимуту и углу места в градусах
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance.abs();
+        self
+    }
+
+    /// Положение ориентира относительно наблюдателя
+    pub fn target(&self) -> &TargetGeometry {
+        &self.target
+    }
+
+    /// Ищет совпадения Солнца и Луны с ориентиром на `days` суток от `start`
+    ///
+    /// Время перебирается с шагом в 2 минуты; из подряд идущих подходящих
+    /// моментов выбирается один - с наименьшим отклонением. Результат
+    /// отсортирован по времени.
+    pub fn find(&self, start: DateTime<Utc>, days: u32) -> Vec<Alignment> {
+        let end = start + Duration::days(days as i64);
+        let mut alignments = Vec::new();
+
+        for body in [Body::Sun, Body::Moon] {
+            let mut best: Option<Alignment> = None;
+            let mut time = start;
+
+            while time <= end {
+                match self.check(body, time) {
+                    Some(candidate) => {
+                        if best
+                            .as_ref()
+                            .is_none_or(|best| error(&candidate) < error(best))
+                        {
+                            best = Some(candidate);
+                        }
+                    }
+                    None => alignments.extend(best.take()),
+                }
+                time += Duration::minutes(SEARCH_STEP_MINUTES);
+            }
+            alignments.extend(best);
+        }
+
+        alignments.sort_by_key(|alignment| alignment.time);
+        alignments
+    }
+
+    /// Совпадение светила с ориентиром в заданный момент
+    fn check(&self, body: Body, time: DateTime<Utc>) -> Option<Alignment> {
+        let (azimuth, altitude, moon_illumination) = match body {
+            Body::Sun => {
+                let position = self.sun.sun_position(time);
+                (position.azimuth, position.altitude, None)
+            }
+            Body::Moon => {
+                let position = self.moon.position(time);
+                (
+                    position.azimuth,
+                    position.altitude,
+                    Some(position.illumination),
+                )
+            }
+        };
+        // Светило заметно ниже горизонта не может совпасть с ориентиром
+        if altitude < self.target.elevation_angle - 2.0 * self.tolerance - 1.0 {
+            return None;
+        }
+
+        let altitude = altitude + refraction(altitude);
+        let azimuth_error = (azimuth - self.target.bearing + 180.0).rem_euclid(360.0) - 180.0;
+        let altitude_error = altitude - self.target.elevation_angle;
+        if azimuth_error.abs() > self.tolerance || altitude_error.abs() > self.tolerance {
+            return None;
+        }
+
+        Some(Alignment {
+            body,
+            time: time.with_timezone(&self.timezone),
+            azimuth,
+            altitude,
+            azimuth_error,
+            altitude_error,
+            moon_illumination,
+        })
+    }
+}
+
+/// Суммарное отклонение светила от ориентира
+fn error(alignment: &Alignment) -> f64 {
+    alignment.azimuth_error.hypot(alignment.altitude_error)
+}
+
+/// Атмосферная рефракция в градусах для истинной высоты (формула Сэмундссона)
+fn refraction(altitude: f64) -> f64 {
+    let altitude = altitude.max(-1.0);
+    1.02 / (altitude + 10.3 / (altitude + 5.11)).to_radians().tan() / 60.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Timelike};
+    use pretty_assertions::assert_eq;
+
+    /// Точка на заданном азимуте и расстоянии от исходной
+    fn destination(from: &Location, bearing: f64, distance_km: f64) -> Location {
+        let (lat, lon) = (from.latitude.to_radians(), from.longitude.to_radians());
+        let (bearing, angle) = (bearing.to_radians(), distance_km / EARTH_RADIUS_KM);
+        let lat2 = (lat.sin() * angle.cos() + lat.cos() * angle.sin() * bearing.cos()).asin();
+        let lon2 = lon
+            + (bearing.sin() * angle.sin() * lat.cos()).atan2(angle.cos() - lat.sin() * lat2.sin());
+        Location::new("Ориентир", lat2.to_degrees(), lon2.to_degrees())
+    }
+
+    fn observer() -> Location {
+        Location::new("Наблюдатель", 55.7558, 37.6176)
+    }
+
+    #[test]
+    fn test_target_geometry() {
+        let observer = observer();
+
+        // Ориентир в 5 км к востоку на уровне наблюдателя: чуть ниже
+        // горизонта из-за кривизны Земли
+        let east = TargetGeometry::between(&observer, &destination(&observer, 90.0, 5.0), 0.0);
+        assert!((east.bearing - 90.0).abs() < 0.1, "{:?}", east);
+        assert!((east.distance_km - 5.0).abs() < 0.01);
+        assert!(east.elevation_angle < 0.0 && east.elevation_angle > -0.05);
+
+        // Башня 300 м в 3 км: угол места ~5.7°
+        let tower = TargetGeometry::between(&observer, &destination(&observer, 200.0, 3.0), 300.0);
+        assert!((tower.bearing - 200.0).abs() < 0.1);
+        assert!((tower.elevation_angle - 5.7).abs() < 0.1, "{:?}", tower);
+
+        // Наблюдатель выше ориентира - угол отрицательный
+        let hill = observer.clone().with_elevation(500.0);
+        let below = TargetGeometry::between(&hill, &destination(&observer, 0.0, 2.0), 100.0);
+        assert!(below.elevation_angle < -10.0);
+    }
+
+    #[test]
+    fn test_find_sunrise_behind_landmark() {
+        let observer = observer();
+        // 15 января Солнце в Москве восходит на азимуте ~128°
+        let day = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let sunrise_azimuth = GoldenHourService::new(observer.latitude, observer.longitude)
+            .calculate_golden_hours_for_date(day.date_naive())
+            .sunrise_azimuth
+            .unwrap();
+        let target = destination(&observer, sunrise_azimuth, 4.0);
+
+        let finder = AlignmentFinder::new(&observer, &target, 0.0).unwrap();
+        let alignments: Vec<Alignment> = finder
+            .find(day, 1)
+            .into_iter()
+            .filter(|alignment| alignment.body == Body::Sun)
+            .collect();
+
+        assert_eq!(alignments.len(), 1, "{:?}", alignments);
+        let sunrise = &alignments[0];
+        // Утро 15 января (восход около 05:50 UTC)
+        let utc = sunrise.time.with_timezone(&Utc);
+        assert_eq!(utc.date_naive(), day.date_naive());
+        assert!((5..=6).contains(&utc.hour()), "{}", utc);
+        assert!(sunrise.azimuth_error.abs() <= DEFAULT_TOLERANCE);
+        assert!(sunrise.altitude_error.abs() <= DEFAULT_TOLERANCE);
+        assert_eq!(sunrise.moon_illumination, None);
+    }
+
+    #[test]
+    fn test_find_moon_alignments() {
+        let observer = observer();
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        // Ориентир на востоке у горизонта: Луна восходит над ним раз в месяц
+        let target = destination(&observer, 90.0, 10.0);
+        let finder = AlignmentFinder::new(&observer, &target, 50.0)
+            .unwrap()
+            .with_tolerance(2.0);
+
+        let alignments = finder.find(start, 30);
+        let moon: Vec<&Alignment> = alignments
+            .iter()
+            .filter(|alignment| alignment.body == Body::Moon)
+            .collect();
+        assert!(!moon.is_empty());
+        assert!(moon
+            .iter()
+            .all(|alignment| alignment.moon_illumination.is_some()));
+        assert!(alignments
+            .windows(2)
+            .all(|pair| pair[0].time <= pair[1].time));
+    }
+
+    #[test]
+    fn test_invalid_target() {
+        let observer = observer();
+        assert!(AlignmentFinder::new(&observer, &observer, 10.0).is_err());
+        let target = destination(&observer, 45.0, 1.0);
+        assert!(AlignmentFinder::new(&observer, &target, -5.0).is_err());
+        assert!(AlignmentFinder::new(&observer, &Location::new("Ошибка", 95.0, 0.0), 0.0).is_err());
+    }
+
+    #[test]
+    fn test_refraction_near_horizon() {
+        // Для истинной высоты 0° рефракция ~29', на высоте 45° - около минуты
+        assert!((refraction(0.0) - 0.48).abs() < 0.02);
+        assert!(refraction(45.0) < 1.5 / 60.0);
+        assert_eq!(Body::Moon.name(), "Луна");
+    }
+}
@@ -0,0 +1,417 @@
+//! # Cache Module
+//!
+//! Локальный дисковый кэш ответов внешних API (OpenWeatherMap, Open-Meteo, NOAA).
+//! Позволяет запускать дашборд часто (cron, статус-бар) без расхода квоты API.
+//!
+//! ## Основные компоненты
+//!
+//! - [`ResponseCache`] - Кэш ответов в `$XDG_CACHE_HOME/my_dashboard`
+//! - [`CacheSource`] - Источник данных со своим временем жизни (TTL)
+//! - [`CacheMode`] - Режим работы кэша (`--no-cache`, `--refresh`)
+//!
+//! ## Поведение
+//!
+//! 1. Свежая запись (моложе TTL источника) возвращается без запроса к API
+//! 2. Иначе выполняется запрос, успешный ответ сохраняется в кэш
+//! 3. Если запрос завершился ошибкой, используется устаревшая запись
+//!    (stale-while-error), если она не старше допустимого срока
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::cache::{CacheMode, CacheSource, ResponseCache};
+//!
+//! let dir = std::env::temp_dir().join("my_dashboard_doc");
+//! let cache = ResponseCache::new(dir, CacheMode::Disabled);
+//!
+//! // Для асинхронного использования:
+//! // let body = cache
+//! //     .get_or_fetch(CacheSource::SolarWind, "noaa-swepam", || fetch_text(url))
+//! //     .await?;
+//! assert_eq!(CacheSource::Geocoding.ttl().num_days(), 30);
+//! ```
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Режим работы кэша на время запуска
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Свежие записи используются, новые ответы сохраняются
+    #[default]
+    Normal,
+    /// Свежие записи игнорируются, ответы запрашиваются заново и сохраняются (`--refresh`)
+    Refresh,
+    /// Кэш не читается и не записывается (`--no-cache`)
+    Disabled,
+}
+
+static CACHE_MODE: OnceLock<CacheMode> = OnceLock::new();
+
+/// Устанавливает режим кэша для всего процесса
+///
+/// Вызывается один раз при запуске; повторные вызовы игнорируются.
+pub fn set_cache_mode(mode: CacheMode) {
+    if CACHE_MODE.set(mode).is_err() {
+        debug!("Режим кэша уже установлен, игнорируем {:?}", mode);
+    }
+}
+
+/// Текущий режим кэша (по умолчанию [`CacheMode::Normal`])
+pub fn cache_mode() -> CacheMode {
+    CACHE_MODE.get().copied().unwrap_or_default()
+}
+
+/// Источник данных с собственным временем жизни записей
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheSource {
+    /// Прямое и обратное геокодирование
+    Geocoding,
+    /// Текущая погода и почасовой прогноз
+    Weather,
+    /// Солнечный ветер NOAA SWEPAM
+    SolarWind,
+    /// Геомагнитный Kp индекс NOAA
+    Geomagnetic,
+}
+
+impl CacheSource {
+    /// Время, в течение которого запись считается свежей
+    pub fn ttl(&self) -> Duration {
+        match self {
+            // Координаты городов практически не меняются
+            CacheSource::Geocoding => Duration::days(30),
+            CacheSource::Weather => Duration::minutes(10),
+            // SWEPAM обновляется раз в час
+            CacheSource::SolarWind => Duration::minutes(15),
+            CacheSource::Geomagnetic => Duration::minutes(5),
+        }
+    }
+
+    /// Максимальный возраст записи, которую можно показать при ошибке API
+    pub fn max_stale_age(&self) -> Duration {
+        match self {
+            CacheSource::Geocoding => Duration::days(365),
+            CacheSource::Weather => Duration::hours(6),
+            CacheSource::SolarWind | CacheSource::Geomagnetic => Duration::hours(3),
+        }
+    }
+
+    fn prefix(&self) -> &'static str {
+        match self {
+            CacheSource::Geocoding => "geocoding",
+            CacheSource::Weather => "weather",
+            CacheSource::SolarWind => "solar_wind",
+            CacheSource::Geomagnetic => "geomagnetic",
+        }
+    }
+}
+
+/// Запись кэша на диске
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    fetched_at: DateTime<Utc>,
+    body: String,
+}
+
+/// Директория кэша по умолчанию: `$XDG_CACHE_HOME/my_dashboard` или `~/.cache/my_dashboard`
+pub fn default_cache_dir() -> Option<PathBuf> {
+    let base = match std::env::var("XDG_CACHE_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var("HOME").ok()?).join(".cache"),
+    };
+    Some(base.join("my_dashboard"))
+}
+
+/// Дисковый кэш ответов внешних API
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: Option<PathBuf>,
+    mode: CacheMode,
+}
+
+impl ResponseCache {
+    /// Создает кэш в указанной директории
+    pub fn new(dir: PathBuf, mode: CacheMode) -> Self {
+        Self {
+            dir: Some(dir),
+            mode,
+        }
+    }
+
+    /// Создает кэш в директории по умолчанию с режимом текущего процесса
+    ///
+    /// Если директорию определить не удалось, кэш отключается.
+    pub fn from_env() -> Self {
+        let dir = default_cache_dir();
+        if dir.is_none() {
+            debug!("Не удалось определить директорию кэша, кэш отключен");
+        }
+        Self {
+            dir,
+            mode: cache_mode(),
+        }
+    }
+
+    /// Возвращает ответ из кэша или выполняет запрос `fetch`
+    ///
+    /// `key` должен однозначно описывать запрос и не содержать секретов (API ключей).
+    pub async fn get_or_fetch<F, Fut>(
+        &self,
+        source: CacheSource,
+        key: &str,
+        fetch: F,
+    ) -> Result<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let dir = match (&self.dir, self.mode) {
+            (Some(dir), CacheMode::Normal | CacheMode::Refresh) => dir,
+            _ => return fetch().await,
+        };
+
+        let path = entry_path(dir, source, key);
+        let cached = read_entry(&path, key);
+        let now = Utc::now();
+
+        if self.mode == CacheMode::Normal {
+            if let Some(entry) = cached
+                .as_ref()
+                .filter(|e| now - e.fetched_at < source.ttl())
+            {
+                debug!("💾 КЭШ: свежая запись {} ({})", key, source.prefix());
+                return Ok(entry.body.clone());
+            }
+        }
+
+        match fetch().await {
+            Ok(body) => {
+                write_entry(&path, key, &body, now);
+                Ok(body)
+            }
+            Err(e) => {
+                match cached.filter(|entry| now - entry.fetched_at < source.max_stale_age()) {
+                    Some(entry) => {
+                        warn!(
+                            "Ошибка запроса {} ({}), используются данные из кэша от {}",
+                            key,
+                            e,
+                            entry.fetched_at.format("%Y-%m-%d %H:%M UTC")
+                        );
+                        Ok(entry.body)
+                    }
+                    None => Err(e),
+                }
+            }
+        }
+    }
+}
+
+fn entry_path(dir: &Path, source: CacheSource, key: &str) -> PathBuf {
+    let safe_key: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dir.join(format!("{}-{}.json", source.prefix(), safe_key))
+}
+
+fn read_entry(path: &Path, key: &str) -> Option<CacheEntry> {
+    let content = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str::<CacheEntry>(&content) {
+        // Разные ключи могут дать одно имя файла после замены символов
+        Ok(entry) if entry.key == key => Some(entry),
+        Ok(_) => None,
+        Err(e) => {
+            warn!("Поврежденная запись кэша {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn write_entry(path: &Path, key: &str, body: &str, fetched_at: DateTime<Utc>) {
+    let entry = CacheEntry {
+        key: key.to_string(),
+        fetched_at,
+        body: body.to_string(),
+    };
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| {
+            let content = serde_json::to_string(&entry)?;
+            std::fs::write(path, content)
+        });
+
+    if let Err(e) = result {
+        warn!("Не удалось сохранить кэш {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "my_dashboard_cache_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn backdate(dir: &Path, source: CacheSource, key: &str, age: Duration) {
+        let path = entry_path(dir, source, key);
+        let mut entry = read_entry(&path, key).unwrap();
+        entry.fetched_at = Utc::now() - age;
+        std::fs::write(&path, serde_json::to_string(&entry).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_entry_path_is_sanitized() {
+        let path = entry_path(
+            Path::new("/tmp/cache"),
+            CacheSource::Weather,
+            "owm/current?lat=1",
+        );
+        assert_eq!(
+            path,
+            PathBuf::from("/tmp/cache/weather-owm_current_lat_1.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fresh_entry_skips_fetch() {
+        let dir = test_dir("fresh");
+        let cache = ResponseCache::new(dir.clone(), CacheMode::Normal);
+
+        let first = cache
+            .get_or_fetch(CacheSource::Weather, "key", || async {
+                Ok("первый".to_string())
+            })
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_fetch(CacheSource::Weather, "key", || async {
+                Err(anyhow::anyhow!("запрос не ожидался"))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(first, "первый");
+        assert_eq!(second, "первый");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_is_refetched() {
+        let dir = test_dir("expired");
+        let cache = ResponseCache::new(dir.clone(), CacheMode::Normal);
+
+        cache
+            .get_or_fetch(CacheSource::Geomagnetic, "kp", || async {
+                Ok("старый".to_string())
+            })
+            .await
+            .unwrap();
+        backdate(&dir, CacheSource::Geomagnetic, "kp", Duration::minutes(30));
+
+        let body = cache
+            .get_or_fetch(CacheSource::Geomagnetic, "kp", || async {
+                Ok("новый".to_string())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(body, "новый");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_stale_entry_used_on_error() {
+        let dir = test_dir("stale");
+        let cache = ResponseCache::new(dir.clone(), CacheMode::Normal);
+
+        cache
+            .get_or_fetch(CacheSource::Weather, "owm", || async {
+                Ok("кэш".to_string())
+            })
+            .await
+            .unwrap();
+        backdate(&dir, CacheSource::Weather, "owm", Duration::hours(1));
+
+        let stale = cache
+            .get_or_fetch(CacheSource::Weather, "owm", || async {
+                Err(anyhow::anyhow!("HTTP 429"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(stale, "кэш");
+
+        // Слишком старые данные не показываются
+        backdate(&dir, CacheSource::Weather, "owm", Duration::hours(7));
+        let result = cache
+            .get_or_fetch(CacheSource::Weather, "owm", || async {
+                Err(anyhow::anyhow!("HTTP 429"))
+            })
+            .await;
+        assert!(result.is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_and_disabled_modes() {
+        let dir = test_dir("modes");
+        let normal = ResponseCache::new(dir.clone(), CacheMode::Normal);
+        normal
+            .get_or_fetch(CacheSource::SolarWind, "swepam", || async {
+                Ok("1".to_string())
+            })
+            .await
+            .unwrap();
+
+        // --refresh игнорирует свежую запись и перезаписывает ее
+        let refresh = ResponseCache::new(dir.clone(), CacheMode::Refresh);
+        let body = refresh
+            .get_or_fetch(CacheSource::SolarWind, "swepam", || async {
+                Ok("2".to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(body, "2");
+
+        // --no-cache не использует даже устаревшие данные при ошибке
+        let disabled = ResponseCache::new(dir.clone(), CacheMode::Disabled);
+        let result = disabled
+            .get_or_fetch(CacheSource::SolarWind, "swepam", || async {
+                Err(anyhow::anyhow!("нет сети"))
+            })
+            .await;
+        assert!(result.is_err());
+
+        let cached = normal
+            .get_or_fetch(CacheSource::SolarWind, "swepam", || async {
+                Err(anyhow::anyhow!("запрос не ожидался"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(cached, "2");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
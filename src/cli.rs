@@ -0,0 +1,745 @@
+//! # CLI Module
+//!
+//! Аргументы командной строки дашборда.
+//! Каждый параметр места съемки можно задать флагом или переменной окружения
+//! (в том числе из файла `.env`); флаг имеет приоритет.
+//!
+//! ## Основные компоненты
+//!
+//! - [`Cli`] - Разобранные аргументы командной строки
+//! - [`Cli::resolve_location`] - Выбор места съемки из флагов и конфигурации
+//! - [`Cli::resolve_comparison`] - Локации для режима сравнения `--compare`
+//! - [`parse_latitude`], [`parse_longitude`] - Проверка координат
+//! - [`parse_datetime`] - Разбор момента времени для `--at`
+//! - [`RequestedTime`] - Момент из `--at`: абсолютный или по часам места съемки
+//! - [`parse_timezone`] - Проверка часового пояса IANA
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use clap::Parser;
+//! use my_dashboard::cli::Cli;
+//! use my_dashboard::render::OutputFormat;
+//!
+//! let cli = Cli::try_parse_from([
+//!     "my_dashboard", "--city", "Murmansk", "--lat", "68.97", "--lon", "33.07",
+//!     "--format", "json",
+//! ]).unwrap();
+//! assert_eq!(cli.city.as_deref(), Some("Murmansk"));
+//! assert_eq!(cli.format, OutputFormat::Json);
+//!
+//! // Некорректные координаты отклоняются с понятной ошибкой
+//! assert!(Cli::try_parse_from(["my_dashboard", "--lat", "91"]).is_err());
+//! ```
+
+use crate::cache::CacheMode;
+use crate::config::{Config, Units};
+use crate::is_demo_mode;
+use crate::location::Location;
+use crate::render::{DashboardSection, OutputFormat};
+use crate::weather_provider::{create_provider, provider_kind_from_env, WeatherProvider};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use clap::{ArgAction, Parser};
+use log::{debug, warn, LevelFilter};
+use std::path::PathBuf;
+
+/// Место съемки, если не заданы ни координаты, ни локация из конфигурации
+pub const DEFAULT_CITY: &str = "Moscow";
+/// Широта места съемки по умолчанию
+pub const DEFAULT_LATITUDE: f64 = 55.7558;
+/// Долгота места съемки по умолчанию
+pub const DEFAULT_LONGITUDE: f64 = 37.6176;
+/// Часовой пояс места съемки по умолчанию
+pub const DEFAULT_TIMEZONE: Tz = Tz::Europe__Moscow;
+
+/// Аргументы командной строки
+#[derive(Debug, Parser)]
+#[command(
+    name = "my_dashboard",
+    version,
+    about = "Дашборд для фотографов: погода, золотой час и северные сияния"
+)]
+pub struct Cli {
+    /// Сохраненная локация из файла конфигурации
+    #[arg(short, long, value_name = "NAME")]
+    pub location: Option<String>,
+
+    /// Сравнить сохраненные локации через запятую (без списка - все локации из конфигурации)
+    #[arg(
+        long,
+        value_name = "NAMES",
+        value_delimiter = ',',
+        num_args = 0..,
+        conflicts_with = "location"
+    )]
+    pub compare: Option<Vec<String>>,
+
+    /// Путь к файлу конфигурации (по умолчанию ~/.config/my_dashboard/config.toml)
+    #[arg(long, env = "MY_DASHBOARD_CONFIG", value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Название места съемки; без --lat/--lon координаты определяются по названию
+    #[arg(long, env = "CITY")]
+    pub city: Option<String>,
+
+    /// Широта точки съемки в градусах (от -90 до 90)
+    #[arg(
+        long = "lat",
+        env = "LATITUDE",
+        value_parser = parse_latitude,
+        allow_negative_numbers = true
+    )]
+    pub latitude: Option<f64>,
+
+    /// Долгота точки съемки в градусах (от -180 до 180)
+    #[arg(
+        long = "lon",
+        env = "LONGITUDE",
+        value_parser = parse_longitude,
+        allow_negative_numbers = true
+    )]
+    pub longitude: Option<f64>,
+
+    /// Высота точки съемки над уровнем моря в метрах (заменяет значение из конфигурации)
+    #[arg(long, value_name = "METERS", allow_negative_numbers = true)]
+    pub elevation: Option<f64>,
+
+    /// CSV файл с профилем горизонта: строки "азимут,высота" в градусах
+    #[arg(long, value_name = "CSV")]
+    pub horizon: Option<PathBuf>,
+
+    /// Часовой пояс IANA места съемки, например Europe/Oslo (по умолчанию из конфигурации или по координатам)
+    #[arg(long, env = "TIMEZONE", value_name = "TZ", value_parser = parse_timezone)]
+    pub timezone: Option<Tz>,
+
+    /// API ключ OpenWeatherMap (без ключа - демонстрационные данные)
+    #[arg(long, env = "OPENWEATHER_API_KEY", hide_env_values = true)]
+    pub api_key: Option<String>,
+
+    /// Единицы измерения в текстовом выводе (по умолчанию из конфигурации)
+    #[arg(long, value_enum)]
+    pub units: Option<Units>,
+
+    /// Момент времени для расчетов вместо текущего: "2024-06-21 05:30" (по часам места съемки), "2024-06-21" или RFC 3339
+    #[arg(long, value_name = "DATETIME", value_parser = parse_datetime)]
+    pub at: Option<RequestedTime>,
+
+    /// Формат вывода
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Секции для вывода через запятую
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = DashboardSection::DEFAULT
+    )]
+    pub sections: Vec<DashboardSection>,
+
+    /// Подробный лог: -v info, -vv debug, -vvv trace (по умолчанию RUST_LOG)
+    #[arg(short, long, action = ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Отключить лог полностью
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// DEMO режим: демонстрационные данные и фиксированное время
+    #[arg(long)]
+    pub demo: bool,
+
+    /// Запросить данные заново, обновив кэш
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Не читать и не записывать кэш
+    #[arg(long, conflicts_with = "refresh")]
+    pub no_cache: bool,
+}
+
+impl Cli {
+    /// Загружает конфигурацию и применяет к ней флаги командной строки
+    ///
+    /// Файл, указанный явно через `--config`, обязан существовать;
+    /// файл по умолчанию необязателен.
+    pub fn load_config(&self) -> Result<Config> {
+        let mut config = match &self.config {
+            Some(path) => Config::load(path)?,
+            None => Config::load_default()?,
+        };
+        if let Some(api_key) = &self.api_key {
+            config.api_keys.openweathermap = Some(api_key.clone());
+        }
+        if let Some(units) = self.units {
+            config.units = units;
+        }
+        Ok(config)
+    }
+
+    /// Определяет место съемки
+    ///
+    /// Приоритет: `--location`, затем явные координаты (флаги или
+    /// `LATITUDE`/`LONGITUDE`), затем город без координат (`--city` или
+    /// `CITY`, координаты определяет провайдер погоды), затем
+    /// `default_location` из конфигурации, затем Москва. `--elevation`,
+    /// `--horizon` и `--timezone` заменяют высоту, профиль горизонта и
+    /// часовой пояс выбранной локации.
+    pub async fn resolve_location(&self, config: &Config) -> Result<Location> {
+        let geocoder = create_provider(
+            provider_kind_from_env(),
+            config.openweathermap_key(),
+            &config.language,
+        );
+        self.resolve_location_with(config, geocoder.as_ref()).await
+    }
+
+    /// То же, что [`Cli::resolve_location`], с заданным источником геокодирования
+    pub async fn resolve_location_with(
+        &self,
+        config: &Config,
+        geocoder: &dyn WeatherProvider,
+    ) -> Result<Location> {
+        let mut location = self.select_location(config, geocoder).await?;
+        if let Some(elevation) = self.elevation {
+            location.elevation = elevation;
+        }
+        if let Some(horizon) = &self.horizon {
+            location.horizon_profile = Some(horizon.clone());
+        }
+        if let Some(timezone) = self.timezone {
+            location.timezone = Some(timezone);
+        }
+        Ok(location)
+    }
+
+    async fn select_location(
+        &self,
+        config: &Config,
+        geocoder: &dyn WeatherProvider,
+    ) -> Result<Location> {
+        if let Some(name) = &self.location {
+            return Ok(config.location(name)?.clone());
+        }
+
+        match (self.latitude, self.longitude, &self.city) {
+            (Some(latitude), Some(longitude), city) => Ok(Location::new(
+                city.as_deref().unwrap_or(DEFAULT_CITY),
+                latitude,
+                longitude,
+            )),
+            (None, None, Some(city)) => geocode_city(city, geocoder).await,
+            (None, None, None) => match config.default_location()? {
+                Some(location) => Ok(location.clone()),
+                None => Ok(
+                    Location::new(DEFAULT_CITY, DEFAULT_LATITUDE, DEFAULT_LONGITUDE)
+                        .with_timezone(DEFAULT_TIMEZONE),
+                ),
+            },
+            _ => bail!("Укажите обе координаты: широту (--lat) и долготу (--lon)"),
+        }
+    }
+
+    /// Локации для сравнения или `None`, если режим сравнения не запрошен
+    ///
+    /// Пустой `--compare` выбирает все сохраненные локации; повторы
+    /// названий отбрасываются.
+    pub fn resolve_comparison(&self, config: &Config) -> Result<Option<Vec<Location>>> {
+        let Some(names) = &self.compare else {
+            return Ok(None);
+        };
+
+        let mut locations: Vec<Location> = Vec::new();
+        if names.is_empty() {
+            locations = config.locations.clone();
+        } else {
+            for name in names.iter().filter(|name| !name.trim().is_empty()) {
+                let location = config.location(name)?;
+                if !locations.iter().any(|known| known.name == location.name) {
+                    locations.push(location.clone());
+                }
+            }
+        }
+
+        if locations.is_empty() {
+            bail!("Нет локаций для сравнения: добавьте [[locations]] в файл конфигурации");
+        }
+        Ok(Some(locations))
+    }
+
+    /// Уровень логирования по флагам `-v`/`-q`
+    ///
+    /// `None` - флаги не заданы, уровень определяется переменной `RUST_LOG`.
+    pub fn log_level(&self) -> Option<LevelFilter> {
+        if self.quiet {
+            return Some(LevelFilter::Off);
+        }
+        match self.verbose {
+            0 => None,
+            1 => Some(LevelFilter::Info),
+            2 => Some(LevelFilter::Debug),
+            _ => Some(LevelFilter::Trace),
+        }
+    }
+
+    /// Режим дискового кэша по флагам `--refresh`/`--no-cache`
+    pub fn cache_mode(&self) -> CacheMode {
+        if self.no_cache {
+            CacheMode::Disabled
+        } else if self.refresh {
+            CacheMode::Refresh
+        } else {
+            CacheMode::Normal
+        }
+    }
+}
+
+/// Место съемки по названию города: координаты определяет провайдер погоды
+///
+/// В DEMO режиме сеть не используется, и город получает координаты места
+/// по умолчанию.
+async fn geocode_city(city: &str, geocoder: &dyn WeatherProvider) -> Result<Location> {
+    if is_demo_mode() {
+        warn!(
+            "DEMO режим: координаты города {} не определяются, используется место по умолчанию",
+            city
+        );
+        return Ok(Location::new(city, DEFAULT_LATITUDE, DEFAULT_LONGITUDE)
+            .with_timezone(DEFAULT_TIMEZONE));
+    }
+    if !geocoder.has_credentials() {
+        bail!(
+            "Не удалось определить координаты города '{}': провайдеру {} нужен API ключ. Укажите --lat и --lon или WEATHER_PROVIDER=open-meteo",
+            city,
+            geocoder.name()
+        );
+    }
+
+    let coords = geocoder
+        .geocode(city)
+        .await
+        .with_context(|| format!("Не удалось определить координаты города '{}'", city))?;
+    debug!(
+        "Координаты города {}: lat={}, lon={}",
+        city, coords.lat, coords.lon
+    );
+    Ok(Location::new(city, coords.lat, coords.lon))
+}
+
+/// Проверяет широту: число от -90 до 90
+pub fn parse_latitude(value: &str) -> Result<f64, String> {
+    parse_coordinate(value, 90.0, "широта")
+}
+
+/// Проверяет долготу: число от -180 до 180
+pub fn parse_longitude(value: &str) -> Result<f64, String> {
+    parse_coordinate(value, 180.0, "долгота")
+}
+
+fn parse_coordinate(value: &str, limit: f64, name: &str) -> Result<f64, String> {
+    let number: f64 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} должна быть числом в градусах", name))?;
+    if !(-limit..=limit).contains(&number) {
+        return Err(format!(
+            "{} должна быть в диапазоне от {} до {}",
+            name, -limit, limit
+        ));
+    }
+    Ok(number)
+}
+
+/// Момент времени из `--at`
+///
+/// Время без смещения относится к часам места съемки, поэтому переводится
+/// в абсолютный момент только после выбора локации.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestedTime {
+    /// Момент с явным смещением (RFC 3339)
+    Instant(DateTime<Utc>),
+    /// Дата и время по часам места съемки
+    Local(NaiveDateTime),
+}
+
+impl RequestedTime {
+    /// Абсолютный момент для места съемки в часовом поясе `timezone`
+    ///
+    /// Ошибка, если такого времени нет (перевод часов вперед).
+    pub fn resolve(&self, timezone: Tz) -> Result<DateTime<Utc>> {
+        match self {
+            RequestedTime::Instant(time) => Ok(*time),
+            RequestedTime::Local(naive) => naive
+                .and_local_timezone(timezone)
+                .earliest()
+                .map(|time| time.with_timezone(&Utc))
+                .ok_or_else(|| anyhow!("время {} не существует в поясе {}", naive, timezone)),
+        }
+    }
+}
+
+/// Проверяет часовой пояс IANA: `Europe/Oslo`, `UTC`
+pub fn parse_timezone(value: &str) -> Result<Tz, String> {
+    value.trim().parse().map_err(|_| {
+        format!(
+            "неизвестный часовой пояс '{}': ожидается имя IANA, например Europe/Oslo",
+            value
+        )
+    })
+}
+
+/// Разбирает момент времени
+///
+/// Поддерживаются `2024-06-21 05:30`, `2024-06-21T05:30[:00]`, `2024-06-21`
+/// (начало суток) по часам места съемки и RFC 3339 с явным смещением.
+pub fn parse_datetime(value: &str) -> Result<RequestedTime, String> {
+    let value = value.trim();
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(RequestedTime::Instant(time.with_timezone(&Utc)));
+    }
+
+    let naive = [
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })
+    .ok_or_else(|| {
+        "ожидается дата и время вида 2024-06-21 05:30, 2024-06-21 или RFC 3339".to_string()
+    })?;
+
+    Ok(RequestedTime::Local(naive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherForecast;
+    use crate::weather_provider::CityCoordinates;
+    use async_trait::async_trait;
+    use chrono::{Datelike, Timelike};
+    use pretty_assertions::assert_eq;
+
+    /// Геокодер без сети: знает только Казань
+    struct KazanGeocoder;
+
+    #[async_trait]
+    impl WeatherProvider for KazanGeocoder {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        async fn geocode(&self, city: &str) -> Result<CityCoordinates> {
+            match city {
+                "Казань" => Ok(CityCoordinates {
+                    lat: 55.7887,
+                    lon: 49.1221,
+                }),
+                _ => bail!("город не найден"),
+            }
+        }
+
+        async fn fetch_forecast(&self, _: &CityCoordinates, _: i64) -> Result<WeatherForecast> {
+            unreachable!("при выборе места прогноз не запрашивается")
+        }
+    }
+
+    async fn resolve(config: &Config, args: &[&str]) -> Result<Location> {
+        let mut argv = vec!["my_dashboard"];
+        argv.extend_from_slice(args);
+        Cli::try_parse_from(argv)
+            .unwrap()
+            .resolve_location_with(config, &KazanGeocoder)
+            .await
+    }
+
+    #[test]
+    fn test_parse_location_and_format() {
+        let cli = Cli::try_parse_from([
+            "my_dashboard",
+            "--city",
+            "Sydney",
+            "--lat",
+            "-33.87",
+            "--lon",
+            "151.21",
+            "--format",
+            "json",
+            "--sections",
+            "summary,golden-hour",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.city.as_deref(), Some("Sydney"));
+        assert_eq!(cli.latitude, Some(-33.87));
+        assert_eq!(cli.longitude, Some(151.21));
+        assert_eq!(cli.format, OutputFormat::Json);
+        assert_eq!(
+            cli.sections,
+            vec![DashboardSection::Summary, DashboardSection::GoldenHour]
+        );
+    }
+
+    #[test]
+    fn test_invalid_coordinates_rejected() {
+        let error = Cli::try_parse_from(["my_dashboard", "--lat", "91"]).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("широта должна быть в диапазоне от -90 до 90"));
+
+        assert!(Cli::try_parse_from(["my_dashboard", "--lon", "-180.5"]).is_err());
+        assert!(Cli::try_parse_from(["my_dashboard", "--lat", "north"]).is_err());
+    }
+
+    #[test]
+    fn test_log_level_and_cache_mode() {
+        let cli = Cli::try_parse_from(["my_dashboard"]).unwrap();
+        assert_eq!(cli.log_level(), None);
+        assert_eq!(cli.cache_mode(), CacheMode::Normal);
+        assert_eq!(cli.sections, DashboardSection::DEFAULT.to_vec());
+
+        let cli = Cli::try_parse_from(["my_dashboard", "-vv", "--refresh"]).unwrap();
+        assert_eq!(cli.log_level(), Some(LevelFilter::Debug));
+        assert_eq!(cli.cache_mode(), CacheMode::Refresh);
+
+        let cli = Cli::try_parse_from(["my_dashboard", "-q", "--no-cache"]).unwrap();
+        assert_eq!(cli.log_level(), Some(LevelFilter::Off));
+        assert_eq!(cli.cache_mode(), CacheMode::Disabled);
+
+        assert!(Cli::try_parse_from(["my_dashboard", "-v", "-q"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_location() {
+        let config = Config::from_toml(
+            r#"
+            default_location = "Маяк"
+
+            [[locations]]
+            name = "Маяк"
+            latitude = 69.2
+            longitude = 35.1
+            elevation = 40
+
+            [[locations]]
+            name = "Поле"
+            latitude = 55.0
+            longitude = 37.0
+            "#,
+        )
+        .unwrap();
+
+        // Сохраненная локация по названию
+        assert_eq!(
+            resolve(&config, &["-l", "поле"]).await.unwrap().latitude,
+            55.0
+        );
+        // Явные координаты важнее локации по умолчанию
+        let explicit = resolve(
+            &config,
+            &["--city", "Sydney", "--lat", "-33.87", "--lon", "151.21"],
+        )
+        .await
+        .unwrap();
+        assert_eq!(explicit, Location::new("Sydney", -33.87, 151.21));
+        // Без координат - локация по умолчанию из конфигурации
+        assert_eq!(resolve(&config, &[]).await.unwrap().elevation, 40.0);
+        // Высота и профиль горизонта из флагов заменяют значения локации
+        let peak = resolve(
+            &config,
+            &[
+                "-l",
+                "Маяк",
+                "--elevation",
+                "1200",
+                "--horizon",
+                "hills.csv",
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(peak.elevation, 1200.0);
+        assert_eq!(peak.horizon_profile, Some(PathBuf::from("hills.csv")));
+        // Неизвестная локация и неполные координаты - ошибки
+        assert!(resolve(&config, &["--location", "Пляж"]).await.is_err());
+        assert!(resolve(&config, &["--lat", "10"]).await.is_err());
+
+        // Без конфигурации - место по умолчанию
+        assert_eq!(
+            resolve(&Config::default(), &[]).await.unwrap(),
+            Location::new(DEFAULT_CITY, DEFAULT_LATITUDE, DEFAULT_LONGITUDE)
+                .with_timezone(DEFAULT_TIMEZONE)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_city_without_coordinates_is_geocoded() {
+        // Город без координат важнее локации по умолчанию и не получает координаты Москвы
+        let config = Config::from_toml(
+            r#"
+            default_location = "Поле"
+
+            [[locations]]
+            name = "Поле"
+            latitude = 55.0
+            longitude = 37.0
+            "#,
+        )
+        .unwrap();
+        let kazan = resolve(&config, &["--city", "Казань"]).await.unwrap();
+        assert_eq!(kazan, Location::new("Казань", 55.7887, 49.1221));
+
+        // Город, который провайдер не нашел, - ошибка, а не Москва
+        let error = resolve(&config, &["--city", "Нигдебург"])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Нигдебург"));
+    }
+
+    #[test]
+    fn test_resolve_comparison() {
+        let config = Config::from_toml(
+            r#"
+            [[locations]]
+            name = "Маяк"
+            latitude = 69.2
+            longitude = 35.1
+
+            [[locations]]
+            name = "Поле"
+            latitude = 55.0
+            longitude = 37.0
+            "#,
+        )
+        .unwrap();
+        let resolve = |args: &[&str]| {
+            let mut argv = vec!["my_dashboard"];
+            argv.extend_from_slice(args);
+            Cli::try_parse_from(argv)
+                .unwrap()
+                .resolve_comparison(&config)
+        };
+        let names = |locations: Option<Vec<Location>>| -> Vec<String> {
+            locations
+                .unwrap()
+                .into_iter()
+                .map(|location| location.name)
+                .collect()
+        };
+
+        assert!(resolve(&[]).unwrap().is_none());
+        assert_eq!(
+            names(resolve(&["--compare"]).unwrap()),
+            vec!["Маяк", "Поле"]
+        );
+        assert_eq!(
+            names(resolve(&["--compare", "поле,маяк,Поле"]).unwrap()),
+            vec!["Поле", "Маяк"]
+        );
+        assert!(resolve(&["--compare", "Пляж"]).is_err());
+        assert!(Cli::try_parse_from(["my_dashboard", "--compare", "-l", "Поле"]).is_err());
+
+        let cli = Cli::try_parse_from(["my_dashboard", "--compare"]).unwrap();
+        assert!(cli.resolve_comparison(&Config::default()).is_err());
+    }
+
+    #[test]
+    fn test_load_config_applies_flags() {
+        let path = std::env::temp_dir().join("my_dashboard_cli_config.toml");
+        std::fs::write(
+            &path,
+            "units = \"imperial\"\n[api_keys]\nopenweathermap = \"from_file\"\n",
+        )
+        .unwrap();
+        let path_arg = path.to_str().unwrap();
+
+        let config = Cli::try_parse_from(["my_dashboard", "--config", path_arg])
+            .unwrap()
+            .load_config()
+            .unwrap();
+        assert_eq!(config.openweathermap_key(), "from_file");
+        assert_eq!(config.units, Units::Imperial);
+
+        let config = Cli::try_parse_from([
+            "my_dashboard",
+            "--config",
+            path_arg,
+            "--api-key",
+            "from_flag",
+            "--units",
+            "metric",
+        ])
+        .unwrap()
+        .load_config()
+        .unwrap();
+        assert_eq!(config.openweathermap_key(), "from_flag");
+        assert_eq!(config.units, Units::Metric);
+
+        let _ = std::fs::remove_file(&path);
+        assert!(Cli::try_parse_from(["my_dashboard", "--config", path_arg])
+            .unwrap()
+            .load_config()
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_datetime() {
+        let oslo = chrono_tz::Europe::Oslo;
+
+        // Время без смещения - по часам места съемки
+        let time = parse_datetime("2024-06-21 05:30").unwrap();
+        let RequestedTime::Local(naive) = time else {
+            panic!("ожидается местное время: {:?}", time);
+        };
+        assert_eq!((naive.month(), naive.day()), (6, 21));
+        assert_eq!((naive.hour(), naive.minute()), (5, 30));
+        assert_eq!(time.resolve(oslo).unwrap().hour(), 3);
+        assert_eq!(time.resolve(DEFAULT_TIMEZONE).unwrap().hour(), 2);
+
+        let midnight = parse_datetime("2024-06-21").unwrap();
+        assert_eq!(
+            midnight,
+            RequestedTime::Local(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap().into())
+        );
+
+        // RFC 3339 - абсолютный момент в любом поясе
+        let rfc = parse_datetime("2024-06-21T05:30:00+00:00").unwrap();
+        assert_eq!(rfc.resolve(oslo).unwrap().timestamp(), 1718947800);
+
+        // Перевод часов вперед: 02:30 в Осло 31 марта не существует
+        assert!(parse_datetime("2024-03-31 02:30")
+            .unwrap()
+            .resolve(oslo)
+            .is_err());
+        assert!(parse_datetime("завтра").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_timezone_flag() {
+        let cli = Cli::try_parse_from(["my_dashboard", "--timezone", "Europe/Oslo"]).unwrap();
+        assert_eq!(cli.timezone, Some(chrono_tz::Europe::Oslo));
+        let location = cli.resolve_location(&Config::default()).await.unwrap();
+        assert_eq!(location.timezone(), chrono_tz::Europe::Oslo);
+
+        // Без флага и координат - Москва по московскому времени
+        let cli = Cli::try_parse_from(["my_dashboard"]).unwrap();
+        assert_eq!(
+            cli.resolve_location(&Config::default())
+                .await
+                .unwrap()
+                .timezone(),
+            DEFAULT_TIMEZONE
+        );
+        assert!(Cli::try_parse_from(["my_dashboard", "--timezone", "Moscow"]).is_err());
+    }
+}
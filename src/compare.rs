@@ -0,0 +1,228 @@
+//! # Compare Module
+//!
+//! Сравнение нескольких мест съемки: полный дашборд строится для каждой
+//! локации параллельно, результаты ранжируются от лучшей к худшей.
+//! Помогает выбрать, куда ехать, когда погода на грани.
+//!
+//! ## Основные компоненты
+//!
+//! - [`compare_locations`] - Построение дашбордов и рейтинга локаций
+//! - [`LocationComparison`] - Рейтинг локаций
+//! - [`ComparisonEntry`] - Итоги по одной локации
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::compare::ComparisonEntry;
+//! use my_dashboard::location::Location;
+//!
+//! let failed = ComparisonEntry::failed(Location::new("Поле", 55.0, 37.0), "нет сети");
+//! assert_eq!(failed.error.as_deref(), Some("нет сети"));
+//!
+//! // Для асинхронного использования:
+//! // let comparison = my_dashboard::compare::compare_locations(&config, &locations).await;
+//! // println!("Лучшая локация: {}", comparison.entries[0].location.name);
+//! ```
+
+use crate::config::Config;
+use crate::golden_hour::GoldenHourInfo;
+use crate::location::Location;
+use crate::{generate_dashboard_output, get_current_time, DashboardOutput};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use futures::future::join_all;
+use log::{debug, warn};
+use serde::Serialize;
+use std::cmp::Ordering;
+
+/// Итоги дашборда по одной локации
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonEntry {
+    /// Место в рейтинге (1 - лучшая локация)
+    pub rank: usize,
+    /// Локация
+    pub location: Location,
+    /// Оценка погодных условий (0-10), `None` если данные о погоде недоступны
+    pub weather_score: Option<f64>,
+    /// Подходят ли условия для астрофотографии, `None` если данные о погоде недоступны
+    pub astro_suitable: Option<bool>,
+    /// Вероятность северных сияний (0-1), `None` если данные NOAA недоступны
+    pub aurora_probability: Option<f64>,
+    /// Восход, закат, золотой и синий час (по часам локации)
+    pub golden_hour: Option<GoldenHourInfo>,
+    /// Начало лучших часов для съемки по часам локации
+    pub best_hours: Vec<DateTime<Tz>>,
+    /// Причина, по которой дашборд для локации не построен
+    pub error: Option<String>,
+}
+
+impl ComparisonEntry {
+    /// Собирает итоги из построенного дашборда
+    pub fn from_output(output: &DashboardOutput) -> Self {
+        Self {
+            rank: 0,
+            location: output.location.clone(),
+            weather_score: output.summary.weather_score,
+            astro_suitable: output
+                .astrophotography_output
+                .as_option()
+                .map(|astro| astro.is_suitable),
+            aurora_probability: output.summary.aurora_probability,
+            golden_hour: Some(output.golden_hour_output.info.clone()),
+            best_hours: output.summary.best_shooting_hours.clone(),
+            error: None,
+        }
+    }
+
+    /// Локация, для которой дашборд построить не удалось
+    pub fn failed(location: Location, error: impl Into<String>) -> Self {
+        Self {
+            rank: 0,
+            location,
+            weather_score: None,
+            astro_suitable: None,
+            aurora_probability: None,
+            golden_hour: None,
+            best_hours: Vec::new(),
+            error: Some(error.into()),
+        }
+    }
+
+    /// Сравнивает итоги: лучшая локация идет первой
+    ///
+    /// Порядок: оценка погоды, затем пригодность для астрофото, затем
+    /// вероятность сияний. Локации без данных - в конце.
+    fn compare(&self, other: &Self) -> Ordering {
+        let key = |entry: &Self| {
+            (
+                entry.error.is_none(),
+                entry.weather_score.unwrap_or(-1.0),
+                entry.astro_suitable.unwrap_or(false),
+                entry.aurora_probability.unwrap_or(0.0),
+            )
+        };
+        let (a, b) = (key(self), key(other));
+        b.0.cmp(&a.0)
+            .then(b.1.total_cmp(&a.1))
+            .then(b.2.cmp(&a.2))
+            .then(b.3.total_cmp(&a.3))
+    }
+}
+
+/// Рейтинг локаций
+#[derive(Debug, Clone, Serialize)]
+pub struct LocationComparison {
+    /// Время построения сравнения (локации могут быть в разных часовых поясах)
+    pub generated_at: DateTime<Utc>,
+    /// Локации от лучшей к худшей
+    pub entries: Vec<ComparisonEntry>,
+}
+
+impl LocationComparison {
+    /// Ранжирует итоги по локациям
+    ///
+    /// При равных показателях сохраняется исходный порядок локаций.
+    pub fn new(mut entries: Vec<ComparisonEntry>) -> Self {
+        entries.sort_by(ComparisonEntry::compare);
+        for (index, entry) in entries.iter_mut().enumerate() {
+            entry.rank = index + 1;
+        }
+        Self {
+            generated_at: get_current_time(),
+            entries,
+        }
+    }
+}
+
+/// Строит дашборды для всех локаций параллельно и ранжирует их
+///
+/// Ошибка одной локации не прерывает сравнение: она попадает в рейтинг
+/// с причиной сбоя.
+pub async fn compare_locations(config: &Config, locations: &[Location]) -> LocationComparison {
+    debug!("Сравнение {} локаций", locations.len());
+
+    let outputs = join_all(
+        locations
+            .iter()
+            .map(|location| generate_dashboard_output(config, location)),
+    )
+    .await;
+
+    let entries = locations
+        .iter()
+        .zip(outputs)
+        .map(|(location, output)| match output {
+            Ok(output) => ComparisonEntry::from_output(&output),
+            Err(error) => {
+                warn!(
+                    "Дашборд для локации {} не построен: {}",
+                    location.name, error
+                );
+                ComparisonEntry::failed(location.clone(), format!("{:#}", error))
+            }
+        })
+        .collect();
+
+    LocationComparison::new(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(name: &str, weather_score: Option<f64>, aurora_probability: f64) -> ComparisonEntry {
+        ComparisonEntry {
+            rank: 0,
+            location: Location::new(name, 55.0, 37.0),
+            weather_score,
+            astro_suitable: weather_score.map(|score| score > 7.0),
+            aurora_probability: Some(aurora_probability),
+            golden_hour: None,
+            best_hours: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn names(comparison: &LocationComparison) -> Vec<&str> {
+        comparison
+            .entries
+            .iter()
+            .map(|entry| entry.location.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_ranking() {
+        let comparison = LocationComparison::new(vec![
+            entry("Облачно", Some(3.5), 0.1),
+            ComparisonEntry::failed(Location::new("Сбой", 60.0, 30.0), "timeout"),
+            entry("Ясно", Some(8.2), 0.1),
+            entry("Без погоды", None, 0.9),
+            entry("Ясно и сияния", Some(8.2), 0.6),
+        ]);
+
+        assert_eq!(
+            names(&comparison),
+            vec!["Ясно и сияния", "Ясно", "Облачно", "Без погоды", "Сбой"]
+        );
+        let ranks: Vec<usize> = comparison.entries.iter().map(|entry| entry.rank).collect();
+        assert_eq!(ranks, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_compare_locations() {
+        let locations = vec![
+            Location::new("Москва", 55.7558, 37.6176),
+            Location::new("Ошибка", 95.0, 37.6176),
+            Location::new("Санкт-Петербург", 59.9311, 30.3609),
+        ];
+        let comparison = compare_locations(&Config::default(), &locations).await;
+
+        assert_eq!(comparison.entries.len(), 3);
+        let failed = comparison.entries.last().unwrap();
+        assert_eq!(failed.location.name, "Ошибка");
+        assert!(failed.error.is_some());
+        assert!(comparison.entries[0].golden_hour.is_some());
+    }
+}
@@ -0,0 +1,455 @@
+//! # Config Module
+//!
+//! Файл конфигурации `~/.config/my_dashboard/config.toml` (или
+//! `$XDG_CONFIG_HOME/my_dashboard/config.toml`): API ключи, единицы измерения,
+//! язык названий мест, границы золотого и синего часа, профили оценки
+//! для жанров съемки и сохраненные локации.
+//!
+//! ## Основные компоненты
+//!
+//! - [`Config`] - Содержимое файла конфигурации
+//! - [`ApiKeys`] - Ключи внешних API
+//! - [`Units`] - Единицы измерения в текстовом выводе
+//!
+//! ## Пример файла
+//!
+//! ```toml
+//! language = "ru"
+//! units = "metric"
+//! default_location = "Воробьевы горы"
+//!
+//! [api_keys]
+//! openweathermap = "your_api_key"
+//!
+//! [golden_hour]
+//! golden_upper = 6.0
+//! golden_lower = -4.0
+//! blue_lower = -6.0
+//!
+//! [profiles.macro]
+//! wind = { max = 1.5, weight = 5, tolerance = 2 }
+//!
+//! [[locations]]
+//! name = "Воробьевы горы"
+//! latitude = 55.7105
+//! longitude = 37.5532
+//! elevation = 200
+//! horizon = "NE"
+//! ```
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::config::Config;
+//!
+//! let config = Config::from_toml(r#"
+//!     [[locations]]
+//!     name = "Териберка"
+//!     latitude = 69.16
+//!     longitude = 35.14
+//!     horizon = "N"
+//! "#).unwrap();
+//!
+//! let location = config.location("териберка").unwrap();
+//! assert_eq!(location.horizon_direction, Some(0.0));
+//! ```
+
+use crate::golden_hour::LightThresholds;
+use crate::location::Location;
+use crate::scoring::{self, ScoringProfile};
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Язык названий мест по умолчанию
+pub const DEFAULT_LANGUAGE: &str = "ru";
+
+/// Ключ OpenWeatherMap, при котором используются демонстрационные данные
+pub const DEMO_API_KEY: &str = "demo_key";
+
+/// Единицы измерения в текстовом выводе
+///
+/// Внутренние расчеты и JSON вывод всегда используют метрическую систему.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    /// °C, м/с
+    #[default]
+    Metric,
+    /// °F, миль/ч
+    Imperial,
+}
+
+impl Units {
+    /// Температура из °C в единицах вывода
+    pub fn temperature(self, celsius: f64) -> f64 {
+        match self {
+            Units::Metric => celsius,
+            Units::Imperial => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    /// Обозначение температуры
+    pub fn temperature_unit(self) -> &'static str {
+        match self {
+            Units::Metric => "°C",
+            Units::Imperial => "°F",
+        }
+    }
+
+    /// Скорость из м/с в единицах вывода
+    pub fn speed(self, meters_per_second: f64) -> f64 {
+        match self {
+            Units::Metric => meters_per_second,
+            Units::Imperial => meters_per_second * 2.236_936,
+        }
+    }
+
+    /// Обозначение скорости
+    pub fn speed_unit(self) -> &'static str {
+        match self {
+            Units::Metric => "м/с",
+            Units::Imperial => "миль/ч",
+        }
+    }
+}
+
+/// Ключи внешних API
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiKeys {
+    /// Ключ OpenWeatherMap
+    pub openweathermap: Option<String>,
+}
+
+/// Содержимое файла конфигурации
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Ключи внешних API
+    pub api_keys: ApiKeys,
+    /// Единицы измерения в текстовом выводе
+    pub units: Units,
+    /// Язык названий мест при геокодировании (код ISO 639-1)
+    pub language: String,
+    /// Локация, используемая без `--location` и без явных координат
+    pub default_location: Option<String>,
+    /// Высоты Солнца, ограничивающие золотой и синий час
+    pub golden_hour: LightThresholds,
+    /// Профили оценки по жанрам: дополняют и переопределяют встроенные
+    pub profiles: BTreeMap<String, ScoringProfile>,
+    /// Сохраненные локации съемки
+    pub locations: Vec<Location>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            api_keys: ApiKeys::default(),
+            units: Units::default(),
+            language: DEFAULT_LANGUAGE.to_string(),
+            default_location: None,
+            golden_hour: LightThresholds::default(),
+            profiles: BTreeMap::new(),
+            locations: Vec::new(),
+        }
+    }
+}
+
+/// Путь к конфигурации по умолчанию: `$XDG_CONFIG_HOME/my_dashboard/config.toml`
+/// или `~/.config/my_dashboard/config.toml`
+pub fn default_config_path() -> Option<PathBuf> {
+    let base = match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var("HOME").ok()?).join(".config"),
+    };
+    Some(base.join("my_dashboard").join("config.toml"))
+}
+
+impl Config {
+    /// Разбирает и проверяет конфигурацию в формате TOML
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let config: Config = toml::from_str(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Загружает конфигурацию из файла
+    ///
+    /// Относительные пути к профилям горизонта отсчитываются от каталога
+    /// файла конфигурации.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Не удалось прочитать конфигурацию {}", path.display()))?;
+        let mut config = Self::from_toml(&content)
+            .with_context(|| format!("Ошибка в конфигурации {}", path.display()))?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        for location in &mut config.locations {
+            if let Some(profile) = location.horizon_profile.as_mut() {
+                if profile.is_relative() {
+                    *profile = base.join(&*profile);
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// Загружает конфигурацию из [`default_config_path`]
+    ///
+    /// Если файла нет, возвращает конфигурацию по умолчанию.
+    pub fn load_default() -> Result<Self> {
+        match default_config_path() {
+            Some(path) if path.exists() => Self::load(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Проверяет локации, их уникальность и локацию по умолчанию
+    pub fn validate(&self) -> Result<()> {
+        if self.language.len() != 2 || !self.language.chars().all(|c| c.is_ascii_lowercase()) {
+            bail!(
+                "Некорректный язык '{}': ожидается код ISO 639-1 (например, ru или en)",
+                self.language
+            );
+        }
+
+        self.golden_hour.validate()?;
+        scoring::validate_profiles(&self.profiles)?;
+
+        for (index, location) in self.locations.iter().enumerate() {
+            location.validate()?;
+            if self.locations[..index]
+                .iter()
+                .any(|other| other.name.to_lowercase() == location.name.to_lowercase())
+            {
+                bail!("Локация '{}' указана несколько раз", location.name);
+            }
+        }
+
+        if let Some(name) = &self.default_location {
+            self.location(name)?;
+        }
+        Ok(())
+    }
+
+    /// Ищет сохраненную локацию по названию (без учета регистра)
+    pub fn location(&self, name: &str) -> Result<&Location> {
+        let wanted = name.trim().to_lowercase();
+        self.locations
+            .iter()
+            .find(|location| location.name.to_lowercase() == wanted)
+            .ok_or_else(|| {
+                let available: Vec<&str> = self
+                    .locations
+                    .iter()
+                    .map(|location| location.name.as_str())
+                    .collect();
+                if available.is_empty() {
+                    anyhow::anyhow!("Локация '{}' не найдена: в конфигурации нет локаций", name)
+                } else {
+                    anyhow::anyhow!(
+                        "Локация '{}' не найдена. Доступны: {}",
+                        name,
+                        available.join(", ")
+                    )
+                }
+            })
+    }
+
+    /// Локация по умолчанию, если она задана
+    pub fn default_location(&self) -> Result<Option<&Location>> {
+        self.default_location
+            .as_deref()
+            .map(|name| self.location(name))
+            .transpose()
+    }
+
+    /// Встроенные профили оценки вместе с профилями из конфигурации
+    pub fn scoring_profiles(&self) -> Vec<(String, ScoringProfile)> {
+        scoring::merge_profiles(&self.profiles)
+    }
+
+    /// Ключ OpenWeatherMap или [`DEMO_API_KEY`], если ключ не задан
+    pub fn openweathermap_key(&self) -> String {
+        self.api_keys
+            .openweathermap
+            .clone()
+            .unwrap_or_else(|| DEMO_API_KEY.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const SAMPLE: &str = r#"
+        language = "en"
+        units = "imperial"
+        default_location = "Lighthouse"
+
+        [api_keys]
+        openweathermap = "secret"
+
+        [golden_hour]
+        golden_upper = 8.0
+
+        [profiles.fog]
+        visibility = { max = 0.5, weight = 5, tolerance = 2 }
+
+        [[locations]]
+        name = "Lighthouse"
+        latitude = 69.2
+        longitude = 35.1
+        elevation = 40
+        horizon = "NE"
+
+        [[locations]]
+        name = "Field"
+        latitude = 55.0
+        longitude = 37.0
+        horizon = 270
+    "#;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::from_toml(SAMPLE).unwrap();
+
+        assert_eq!(config.language, "en");
+        assert_eq!(config.units, Units::Imperial);
+        assert_eq!(config.openweathermap_key(), "secret");
+        assert_eq!(config.locations.len(), 2);
+        assert_eq!(config.golden_hour.golden_upper, 8.0);
+        assert_eq!(
+            config.golden_hour.golden_lower,
+            LightThresholds::default().golden_lower
+        );
+        let profiles = config.scoring_profiles();
+        assert_eq!(profiles.len(), 6);
+        let (_, fog) = profiles.iter().find(|(name, _)| name == "fog").unwrap();
+        assert_eq!(fog.visibility.unwrap().max, Some(0.5));
+        assert_eq!(fog.humidity, None);
+
+        let default = config.default_location().unwrap().unwrap();
+        assert_eq!(default.name, "Lighthouse");
+        assert_eq!(default.elevation, 40.0);
+        assert_eq!(default.horizon_direction, Some(45.0));
+        assert_eq!(
+            config.location("field").unwrap().horizon_direction,
+            Some(270.0)
+        );
+    }
+
+    #[test]
+    fn test_empty_config_uses_defaults() {
+        let config = Config::from_toml("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.language, DEFAULT_LANGUAGE);
+        assert_eq!(config.openweathermap_key(), DEMO_API_KEY);
+        assert_eq!(config.default_location().unwrap(), None);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        // Неизвестная локация по умолчанию
+        assert!(Config::from_toml(r#"default_location = "Nowhere""#).is_err());
+        // Координаты вне диапазона
+        assert!(Config::from_toml(
+            r#"
+            [[locations]]
+            name = "Bad"
+            latitude = 120.0
+            longitude = 0.0
+            "#
+        )
+        .is_err());
+        // Повторяющиеся названия
+        assert!(Config::from_toml(
+            r#"
+            [[locations]]
+            name = "Home"
+            latitude = 1.0
+            longitude = 1.0
+
+            [[locations]]
+            name = "home"
+            latitude = 2.0
+            longitude = 2.0
+            "#
+        )
+        .is_err());
+        // Опечатка в названии поля
+        assert!(Config::from_toml(r#"unit = "metric""#).is_err());
+        // Граница синего часа выше границы золотого
+        assert!(Config::from_toml(
+            r#"
+            [golden_hour]
+            blue_lower = 0.0
+            "#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_unknown_location_lists_available() {
+        let config = Config::from_toml(SAMPLE).unwrap();
+        let error = config.location("Beach").unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "Локация 'Beach' не найдена. Доступны: Lighthouse, Field"
+        );
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let path = std::env::temp_dir().join("my_dashboard_missing_config.toml");
+        let _ = std::fs::remove_file(&path);
+        assert!(Config::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_load_resolves_horizon_profile() {
+        let dir = std::env::temp_dir().join("my_dashboard_config_horizon");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[locations]]
+            name = "Долина"
+            latitude = 43.0
+            longitude = 42.0
+            horizon_profile = "valley.csv"
+
+            [[locations]]
+            name = "Вершина"
+            latitude = 43.1
+            longitude = 42.1
+            horizon_profile = "/data/peak.csv"
+            "#,
+        )
+        .unwrap();
+
+        let config = Config::load(&path).unwrap();
+        assert_eq!(
+            config.location("Долина").unwrap().horizon_profile,
+            Some(dir.join("valley.csv"))
+        );
+        assert_eq!(
+            config.location("Вершина").unwrap().horizon_profile,
+            Some(PathBuf::from("/data/peak.csv"))
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_units_conversion() {
+        assert_eq!(Units::Imperial.temperature(100.0), 212.0);
+        assert_eq!(Units::Metric.temperature(20.0), 20.0);
+        assert!((Units::Imperial.speed(10.0) - 22.37).abs() < 0.01);
+        assert_eq!(Units::Imperial.speed_unit(), "миль/ч");
+    }
+}
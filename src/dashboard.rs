@@ -8,18 +8,16 @@
 //!
 //! - [`PhotographyDashboard`] - Основной класс дашборда
 //! - [`DashboardSummary`] - Структура сводки условий
+//! - [`DayPlan`] - План съемки на один день горизонта прогноза
 //!
 //! ## Пример использования
 //!
 //! ```rust
 //! use my_dashboard::dashboard::{PhotographyDashboard, DashboardSummary};
+//! use my_dashboard::location::Location;
 //!
 //! // Создаем дашборд
-//! let dashboard = PhotographyDashboard::new(
-//!     "Moscow".to_string(),
-//!     55.7558,
-//!     37.6176,
-//! );
+//! let dashboard = PhotographyDashboard::new(&Location::new("Moscow", 55.7558, 37.6176));
 //!
 //! // Для асинхронного использования:
 //! // #[tokio::main]
@@ -30,28 +28,62 @@
 //! // }
 //! ```
 
-use crate::golden_hour::{GoldenHourInfo, GoldenHourService};
-use crate::weather::{analyze_weather_for_photography, WeatherAnalysis};
-use chrono::{DateTime, Local};
+use crate::fog::{self, FogMorning};
+use crate::get_current_time;
+use crate::golden_hour::{Daylight, GoldenHourInfo, GoldenHourService, LightThresholds};
+use crate::horizon::HorizonProfile;
+use crate::location::Location;
+use crate::weather::{
+    analyze_daily_weather, analyze_weather_for_photography, WeatherAnalysis, WeatherForecast,
+    FORECAST_HOURS,
+};
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
 use log::debug;
+use serde::Serialize;
+
+/// План съемки на один день горизонта прогноза
+#[derive(Debug, Clone, Serialize)]
+pub struct DayPlan {
+    /// Дата по часам места съемки
+    pub date: NaiveDate,
+    /// Средняя оценка погодных условий за день (0-10)
+    pub weather_score: f64,
+    /// Начало лучших часов дня для съемки по часам места съемки
+    pub best_hours: Vec<DateTime<Tz>>,
+    /// Минимальная температура за день
+    pub min_temperature: f64,
+    /// Максимальная температура за день
+    pub max_temperature: f64,
+    /// Средняя облачность за день
+    pub avg_cloud_cover: f64,
+    /// Золотой и синий час этого дня
+    pub golden_hour: GoldenHourInfo,
+}
 
 /// Сводка условий для фотографии
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DashboardSummary {
     /// Общая рекомендация для съемки
     pub overall_recommendation: String,
-    /// Оценка погодных условий (0-10)
-    pub weather_score: f64,
-    /// Вероятность северных сияний (0-1)
-    pub aurora_probability: f64,
+    /// Оценка погодных условий (0-10), `None` если данные о погоде недоступны
+    pub weather_score: Option<f64>,
+    /// Вероятность северных сияний (0-1), `None` если данные NOAA недоступны
+    pub aurora_probability: Option<f64>,
     /// Есть ли золотой час сегодня
     pub is_golden_hour_today: bool,
-    /// Лучшие часы для съемки
-    pub best_shooting_hours: Vec<usize>,
+    /// Начало лучших часов для съемки по часам места съемки
+    pub best_shooting_hours: Vec<DateTime<Tz>>,
     /// Ключевые моменты для съемки
     pub key_highlights: Vec<String>,
     /// Предупреждения о неблагоприятных условиях
     pub warnings: Vec<String>,
+    /// Планы по дням (заполняются, если прогноз длиннее суток)
+    pub daily_plans: Vec<DayPlan>,
+    /// Даты горизонта, отсортированные от лучшей к худшей
+    pub best_days: Vec<NaiveDate>,
+    /// Туманное утро в ближайшие сутки, если туман вероятен
+    pub fog_morning: Option<FogMorning>,
 }
 
 /// Основной дашборд для фотографов
@@ -60,6 +92,7 @@ pub struct DashboardSummary {
 /// для создания персонализированной сводки условий съемки.
 pub struct PhotographyDashboard {
     golden_hour_service: GoldenHourService,
+    timezone: Tz,
 }
 
 impl PhotographyDashboard {
@@ -67,34 +100,49 @@ impl PhotographyDashboard {
     ///
     /// # Аргументы
     ///
-    /// * `city` - Название города
-    /// * `latitude` - Широта в градусах
-    /// * `longitude` - Долгота в градусах
+    /// * `location` - Место съемки (координаты используются для расчета золотого
+    ///   часа, часовой пояс - для времени и часов прогноза)
     ///
     /// # Пример
     ///
     /// ```rust
     /// use my_dashboard::dashboard::PhotographyDashboard;
+    /// use my_dashboard::location::Location;
     ///
-    /// let dashboard = PhotographyDashboard::new(
-    ///     "Moscow".to_string(),
-    ///     55.7558,
-    ///     37.6176,
-    /// );
+    /// let dashboard = PhotographyDashboard::new(&Location::new("Moscow", 55.7558, 37.6176));
     /// ```
-    pub fn new(city: String, latitude: f64, longitude: f64) -> Self {
-        debug!("Создание дашборда для города: {}", city);
+    pub fn new(location: &Location) -> Self {
+        debug!("Создание дашборда для локации: {}", location.name);
 
+        let timezone = location.timezone();
         Self {
-            golden_hour_service: GoldenHourService::new(latitude, longitude),
+            golden_hour_service: GoldenHourService::new(location.latitude, location.longitude)
+                .with_elevation(location.elevation)
+                .with_timezone(timezone),
+            timezone,
         }
     }
 
+    /// Задает профиль горизонта места съемки
+    pub fn with_horizon(mut self, horizon: HorizonProfile) -> Self {
+        self.golden_hour_service = self.golden_hour_service.with_horizon(horizon);
+        self
+    }
+
+    /// Задает высоты Солнца для границ золотого и синего часа
+    pub fn with_light_thresholds(mut self, thresholds: LightThresholds) -> Self {
+        self.golden_hour_service = self.golden_hour_service.with_thresholds(thresholds);
+        self
+    }
+
     /// Генерирует полную сводку условий для съемки
     ///
     /// Собирает данные о погоде, золотом часе и северных сияниях,
     /// анализирует их и создает персонализированную сводку.
     ///
+    /// Недоступные источники передаются как `None`: сводка строится по
+    /// оставшимся данным, а причину недоступности сообщает вызывающий код.
+    ///
     /// # Возвращает
     ///
     /// `Result<DashboardSummary, Box<dyn std::error::Error>>` - Сводка условий или ошибка
@@ -103,13 +151,10 @@ impl PhotographyDashboard {
     ///
     /// ```rust
     /// use my_dashboard::dashboard::PhotographyDashboard;
+    /// use my_dashboard::location::Location;
     ///
     /// // Создаем дашборд
-    /// let dashboard = PhotographyDashboard::new(
-    ///     "Moscow".to_string(),
-    ///     55.7558,
-    ///     37.6176,
-    /// );
+    /// let dashboard = PhotographyDashboard::new(&Location::new("Moscow", 55.7558, 37.6176));
     ///
     /// // Для асинхронного использования:
     /// // #[tokio::main]
@@ -121,13 +166,27 @@ impl PhotographyDashboard {
     /// ```
     pub async fn generate_dashboard(
         &self,
-        weather_forecast: &crate::weather::WeatherForecast,
-        aurora_probability: f64,
+        weather_forecast: Option<&WeatherForecast>,
+        aurora_probability: Option<f64>,
     ) -> Result<DashboardSummary, anyhow::Error> {
-        let current_time = Local::now();
-
-        // Анализируем погоду
-        let weather_analysis = analyze_weather_for_photography(weather_forecast);
+        let current_time = get_current_time().with_timezone(&self.timezone);
+
+        // Анализируем погоду и туман на ближайшие сутки
+        let today = weather_forecast.map(|forecast| forecast.first_day());
+        let golden_hours = today
+            .as_ref()
+            .map(|today| self.golden_hours_for(today))
+            .unwrap_or_default();
+        let weather_analysis = today
+            .as_ref()
+            .map(|today| analyze_weather_for_photography(today, &golden_hours, self.timezone));
+        let fog_morning = today.as_ref().and_then(|today| {
+            fog::fog_morning(&fog::predict_fog(today, &golden_hours), &golden_hours)
+        });
+        // Планируем съемку по дням, если прогноз длиннее суток
+        let daily_plans = weather_forecast
+            .map(|forecast| self.plan_days(forecast))
+            .unwrap_or_default();
         // Получаем информацию о золотом часе
         let golden_hour_info = self
             .golden_hour_service
@@ -137,47 +196,89 @@ impl PhotographyDashboard {
         let is_golden_hour_today = self.is_golden_hour_today(&golden_hour_info, current_time);
 
         // Создаем общую сводку
-        let summary = self.create_summary(
-            &weather_analysis,
+        let mut summary = self.create_summary(
+            weather_analysis.as_ref(),
             &golden_hour_info,
             is_golden_hour_today,
             current_time,
             aurora_probability,
+            daily_plans,
         );
+        // Туманное утро выделяется в сводке отдельной строкой
+        summary.fog_morning = fog_morning;
 
         Ok(summary)
     }
 
+    /// Строит планы съемки по дням горизонта прогноза
+    ///
+    /// Для прогноза не длиннее суток возвращает пустой список: сводка
+    /// в этом случае описывает только ближайшие 24 часа.
+    pub fn plan_days(&self, weather_forecast: &WeatherForecast) -> Vec<DayPlan> {
+        if weather_forecast.hourly.len() <= FORECAST_HOURS as usize {
+            return Vec::new();
+        }
+
+        let golden_hours = self.golden_hours_for(weather_forecast);
+        analyze_daily_weather(weather_forecast, &golden_hours, self.timezone)
+            .into_iter()
+            .map(|day| DayPlan {
+                date: day.date,
+                weather_score: day.score,
+                best_hours: day.best_hours,
+                min_temperature: day.min_temperature,
+                max_temperature: day.max_temperature,
+                avg_cloud_cover: day.avg_cloud_cover,
+                golden_hour: self
+                    .golden_hour_service
+                    .calculate_golden_hours_for_date(day.date),
+            })
+            .collect()
+    }
+
+    /// Золотой час для всех дней, которые охватывает прогноз
+    fn golden_hours_for(&self, weather_forecast: &WeatherForecast) -> Vec<GoldenHourInfo> {
+        match (
+            weather_forecast.hourly.first(),
+            weather_forecast.hourly.last(),
+        ) {
+            (Some(first), Some(last)) => self
+                .golden_hour_service
+                .golden_hours_between(first.timestamp, last.timestamp),
+            _ => Vec::new(),
+        }
+    }
+
     fn is_golden_hour_today(
         &self,
         golden_hour_info: &GoldenHourInfo,
-        current_time: DateTime<Local>,
+        current_time: DateTime<Tz>,
     ) -> bool {
         // Проверяем, попадает ли текущее время в золотой час
-        (current_time >= golden_hour_info.golden_hour_morning_start
-            && current_time <= golden_hour_info.golden_hour_morning_end)
-            || (current_time >= golden_hour_info.golden_hour_evening_start
-                && current_time <= golden_hour_info.golden_hour_evening_end)
+        golden_hour_info.is_golden_hour_at(current_time)
     }
 
     fn create_summary(
         &self,
-        weather_analysis: &WeatherAnalysis,
+        weather_analysis: Option<&WeatherAnalysis>,
         golden_hour_info: &GoldenHourInfo,
         is_golden_hour_today: bool,
-        current_time: DateTime<Local>,
-        aurora_probability: f64,
+        current_time: DateTime<Tz>,
+        aurora_probability: Option<f64>,
+        daily_plans: Vec<DayPlan>,
     ) -> DashboardSummary {
         let mut key_highlights = Vec::new();
         let mut warnings = Vec::new();
 
         // Анализируем погоду
-        if weather_analysis.overall_score >= 8.0 {
-            key_highlights.push("Отличные погодные условия для съемки!".to_string());
-        } else if weather_analysis.overall_score >= 6.0 {
-            key_highlights.push("Хорошие погодные условия".to_string());
-        } else {
-            warnings.push("Погодные условия не идеальны для съемки".to_string());
+        if let Some(analysis) = weather_analysis {
+            if analysis.overall_score >= 8.0 {
+                key_highlights.push("Отличные погодные условия для съемки!".to_string());
+            } else if analysis.overall_score >= 6.0 {
+                key_highlights.push("Хорошие погодные условия".to_string());
+            } else {
+                warnings.push("Погодные условия не идеальны для съемки".to_string());
+            }
         }
 
         // Анализируем золотой час
@@ -196,21 +297,60 @@ impl PhotographyDashboard {
             }
         }
 
+        // Полярный день и полярная ночь меняют план съемки
+        match golden_hour_info.daylight {
+            Daylight::PolarNight => key_highlights.push(
+                "Полярная ночь: Солнце не восходит - снимайте сумерки и северные сияния"
+                    .to_string(),
+            ),
+            Daylight::PolarDay => warnings
+                .push("Полярный день: Солнце не заходит - звездного неба не будет".to_string()),
+            Daylight::Normal => {}
+        }
+        if !golden_hour_info.has_golden_hour {
+            warnings.push(
+                "Сегодня нет золотого часа: Солнце не проходит высоты мягкого света".to_string(),
+            );
+        }
+
+        // Выделяем лучший день горизонта
+        let best_days = rank_days(&daily_plans);
+        if let Some(best_plan) = best_days
+            .first()
+            .and_then(|date| daily_plans.iter().find(|plan| plan.date == *date))
+        {
+            key_highlights.push(format!(
+                "Лучший день для съемки: {} (оценка {:.1}/10)",
+                best_plan.date.format("%d.%m"),
+                best_plan.weather_score
+            ));
+        }
+
         // Определяем общую рекомендацию
-        let overall_recommendation = self
-            .determine_overall_recommendation(weather_analysis.overall_score, is_golden_hour_today);
+        let overall_recommendation = match weather_analysis {
+            Some(analysis) => {
+                self.determine_overall_recommendation(analysis.overall_score, is_golden_hour_today)
+            }
+            None => "Нет данных о погоде. Ориентируйтесь на время золотого часа и местный прогноз."
+                .to_string(),
+        };
 
         // Используем лучшие часы для съемки из погодного анализа
-        let best_shooting_hours = weather_analysis.best_hours.clone();
+        let best_shooting_hours = weather_analysis
+            .map(|analysis| analysis.best_hours.clone())
+            .unwrap_or_default();
 
         DashboardSummary {
             overall_recommendation,
-            weather_score: weather_analysis.overall_score,
+            weather_score: weather_analysis.map(|analysis| analysis.overall_score),
             aurora_probability,
             is_golden_hour_today,
             best_shooting_hours,
             key_highlights,
             warnings,
+            daily_plans,
+            best_days,
+            fog_morning: None,
         }
     }
 
@@ -231,19 +371,41 @@ impl PhotographyDashboard {
     }
 }
 
+/// Ранжирует дни горизонта по оценке погодных условий
+///
+/// При равной оценке более ранний день идет первым.
+pub fn rank_days(plans: &[DayPlan]) -> Vec<NaiveDate> {
+    let mut ranked: Vec<&DayPlan> = plans.iter().collect();
+    ranked.sort_by(|a, b| {
+        b.weather_score
+            .total_cmp(&a.weather_score)
+            .then(a.date.cmp(&b.date))
+    });
+    ranked.into_iter().map(|plan| plan.date).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::weather::WeatherAnalysis;
-    use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+    use chrono::{NaiveDate, NaiveDateTime, TimeZone};
+    use chrono_tz::Europe::Moscow;
     use pretty_assertions::assert_eq;
 
     // Вспомогательные функции для создания тестовых данных
+    fn moscow_hours(day: u32, hours: &[u32]) -> Vec<DateTime<Tz>> {
+        hours
+            .iter()
+            .map(|&hour| Moscow.with_ymd_and_hms(2024, 6, day, hour, 0, 0).unwrap())
+            .collect()
+    }
+
     fn create_test_weather_analysis() -> WeatherAnalysis {
         WeatherAnalysis {
             overall_score: 7.5,
             recommendations: vec!["Отличные условия для фотографии!".to_string()],
-            best_hours: vec![6, 7, 8, 18, 19, 20],
+            best_hours: moscow_hours(15, &[6, 7, 8, 18, 19, 20]),
+            hours: vec![],
             concerns: vec![],
         }
     }
@@ -254,19 +416,19 @@ mod tests {
         service.calculate_golden_hours(test_date)
     }
 
-    fn create_test_date() -> DateTime<Local> {
+    fn create_test_date() -> DateTime<Tz> {
         // Используем фиксированную дату для тестов
         let naive_date = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
         let naive_datetime = NaiveDateTime::new(
             naive_date,
             chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
         );
-        Local.from_local_datetime(&naive_datetime).unwrap()
+        Moscow.from_local_datetime(&naive_datetime).unwrap()
     }
 
     #[test]
     fn test_is_golden_hour_today() {
-        let _dashboard = PhotographyDashboard::new("TestCity".to_string(), 55.7558, 37.6176);
+        let _dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
 
         let golden_hour_info = create_test_golden_hour_info();
         let test_date = create_test_date();
@@ -280,7 +442,7 @@ mod tests {
 
     #[test]
     fn test_determine_overall_recommendation() {
-        let dashboard = PhotographyDashboard::new("TestCity".to_string(), 55.7558, 37.6176);
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
 
         // Тестируем разные сценарии
         let excellent = dashboard.determine_overall_recommendation(9.0, true);
@@ -297,31 +459,35 @@ mod tests {
 
     #[test]
     fn test_create_summary() {
-        let dashboard = PhotographyDashboard::new("TestCity".to_string(), 55.7558, 37.6176);
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
 
         let weather_analysis = create_test_weather_analysis();
         let golden_hour_info = create_test_golden_hour_info();
         let test_date = create_test_date();
 
         let summary = dashboard.create_summary(
-            &weather_analysis,
+            Some(&weather_analysis),
             &golden_hour_info,
             false, // не золотой час
             test_date,
-            0.3, // 30% вероятность сияний
+            Some(0.3), // 30% вероятность сияний
+            Vec::new(),
         );
 
         // Проверяем структуру сводки
-        assert_eq!(summary.weather_score, 7.5);
-        assert_eq!(summary.aurora_probability, 0.3);
+        assert_eq!(summary.weather_score, Some(7.5));
+        assert_eq!(summary.aurora_probability, Some(0.3));
         assert!(!summary.is_golden_hour_today);
-        assert_eq!(summary.best_shooting_hours, vec![6, 7, 8, 18, 19, 20]);
+        assert_eq!(
+            summary.best_shooting_hours,
+            moscow_hours(15, &[6, 7, 8, 18, 19, 20])
+        );
         assert!(!summary.overall_recommendation.is_empty());
     }
 
     #[test]
     fn test_create_summary_excellent_conditions() {
-        let dashboard = PhotographyDashboard::new("TestCity".to_string(), 55.7558, 37.6176);
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
 
         let mut excellent_weather = create_test_weather_analysis();
         excellent_weather.overall_score = 9.0;
@@ -330,11 +496,12 @@ mod tests {
         let test_date = create_test_date();
 
         let summary = dashboard.create_summary(
-            &excellent_weather,
+            Some(&excellent_weather),
             &golden_hour_info,
             true, // золотой час
             test_date,
-            0.8, // высокая вероятность сияний
+            Some(0.8), // высокая вероятность сияний
+            Vec::new(),
         );
 
         // При отличных условиях должны быть highlights
@@ -350,7 +517,7 @@ mod tests {
 
     #[test]
     fn test_create_summary_poor_conditions() {
-        let dashboard = PhotographyDashboard::new("TestCity".to_string(), 55.7558, 37.6176);
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
 
         let mut poor_weather = create_test_weather_analysis();
         poor_weather.overall_score = 3.0;
@@ -358,8 +525,14 @@ mod tests {
         let golden_hour_info = create_test_golden_hour_info();
         let test_date = create_test_date();
 
-        let summary =
-            dashboard.create_summary(&poor_weather, &golden_hour_info, false, test_date, 0.1);
+        let summary = dashboard.create_summary(
+            Some(&poor_weather),
+            &golden_hour_info,
+            false,
+            test_date,
+            Some(0.1),
+            Vec::new(),
+        );
 
         // При плохих условиях должны быть предупреждения
         assert!(!summary.warnings.is_empty());
@@ -376,29 +549,37 @@ mod tests {
     fn test_dashboard_summary_structure() {
         let summary = DashboardSummary {
             overall_recommendation: "Тестовая рекомендация".to_string(),
-            weather_score: 7.0,
-            aurora_probability: 0.5,
+            weather_score: Some(7.0),
+            aurora_probability: Some(0.5),
             is_golden_hour_today: true,
-            best_shooting_hours: vec![6, 7, 8, 18, 19, 20],
+            best_shooting_hours: moscow_hours(15, &[6, 7, 8, 18, 19, 20]),
             key_highlights: vec!["Отличные условия".to_string()],
             warnings: vec![],
+            daily_plans: vec![],
+            best_days: vec![],
+            fog_morning: None,
         };
 
         // Проверяем разумные пределы
-        assert!(summary.weather_score >= 0.0 && summary.weather_score <= 10.0);
-        assert!(summary.aurora_probability >= 0.0 && summary.aurora_probability <= 1.0);
+        assert!(summary
+            .weather_score
+            .is_some_and(|score| (0.0..=10.0).contains(&score)));
+        assert!(summary
+            .aurora_probability
+            .is_some_and(|probability| (0.0..=1.0).contains(&probability)));
         assert!(!summary.overall_recommendation.is_empty());
         assert!(!summary.best_shooting_hours.is_empty());
 
-        // Проверяем, что лучшие часы в разумных пределах
-        for &hour in &summary.best_shooting_hours {
-            assert!((0..=23).contains(&hour));
-        }
+        // Лучшие часы идут по порядку
+        assert!(summary
+            .best_shooting_hours
+            .windows(2)
+            .all(|pair| pair[0] < pair[1]));
     }
 
     #[test]
     fn test_edge_cases() {
-        let dashboard = PhotographyDashboard::new("TestCity".to_string(), 55.7558, 37.6176);
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
 
         // Тестируем граничные значения
         let min_recommendation = dashboard.determine_overall_recommendation(0.0, false);
@@ -418,26 +599,38 @@ mod tests {
 
     #[test]
     fn test_aurora_probability_validation() {
-        let dashboard = PhotographyDashboard::new("TestCity".to_string(), 55.7558, 37.6176);
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
 
         let weather_analysis = create_test_weather_analysis();
         let golden_hour_info = create_test_golden_hour_info();
         let test_date = create_test_date();
 
         // Тестируем разные значения вероятности сияний
-        let summary_low =
-            dashboard.create_summary(&weather_analysis, &golden_hour_info, false, test_date, 0.0);
+        let summary_low = dashboard.create_summary(
+            Some(&weather_analysis),
+            &golden_hour_info,
+            false,
+            test_date,
+            Some(0.0),
+            Vec::new(),
+        );
 
-        let summary_high =
-            dashboard.create_summary(&weather_analysis, &golden_hour_info, false, test_date, 1.0);
+        let summary_high = dashboard.create_summary(
+            Some(&weather_analysis),
+            &golden_hour_info,
+            false,
+            test_date,
+            Some(1.0),
+            Vec::new(),
+        );
 
-        assert_eq!(summary_low.aurora_probability, 0.0);
-        assert_eq!(summary_high.aurora_probability, 1.0);
+        assert_eq!(summary_low.aurora_probability, Some(0.0));
+        assert_eq!(summary_high.aurora_probability, Some(1.0));
     }
 
     #[test]
     fn test_golden_hour_precise_time_detection() {
-        let dashboard = PhotographyDashboard::new("TestCity".to_string(), 55.7558, 37.6176);
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
 
         let golden_hour_info = create_test_golden_hour_info();
         let test_date = create_test_date();
@@ -446,4 +639,151 @@ mod tests {
         let _is_golden = dashboard.is_golden_hour_today(&golden_hour_info, test_date);
         // Этот тест может быть нестабильным из-за реального времени, поэтому проверяем только логику
     }
+
+    fn create_test_day_plan(day: u32, weather_score: f64) -> DayPlan {
+        let date = NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+        DayPlan {
+            date,
+            weather_score,
+            best_hours: moscow_hours(day, &[5, 6, 20]),
+            min_temperature: 12.0,
+            max_temperature: 24.0,
+            avg_cloud_cover: 30.0,
+            golden_hour: GoldenHourService::new(55.7558, 37.6176)
+                .calculate_golden_hours_for_date(date),
+        }
+    }
+
+    #[test]
+    fn test_rank_days_orders_by_score_then_date() {
+        let plans = vec![
+            create_test_day_plan(15, 6.0),
+            create_test_day_plan(16, 8.5),
+            create_test_day_plan(17, 6.0),
+            create_test_day_plan(18, 9.1),
+        ];
+
+        let ranked = rank_days(&plans);
+
+        let expected: Vec<NaiveDate> = [18, 16, 15, 17]
+            .iter()
+            .map(|&day| NaiveDate::from_ymd_opt(2024, 6, day).unwrap())
+            .collect();
+        assert_eq!(ranked, expected);
+    }
+
+    #[test]
+    fn test_create_summary_highlights_best_day() {
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
+
+        let summary = dashboard.create_summary(
+            Some(&create_test_weather_analysis()),
+            &create_test_golden_hour_info(),
+            false,
+            create_test_date(),
+            Some(0.2),
+            vec![create_test_day_plan(15, 5.0), create_test_day_plan(16, 8.0)],
+        );
+
+        assert_eq!(summary.daily_plans.len(), 2);
+        assert_eq!(
+            summary.best_days[0],
+            NaiveDate::from_ymd_opt(2024, 6, 16).unwrap()
+        );
+        assert!(summary
+            .key_highlights
+            .contains(&"Лучший день для съемки: 16.06 (оценка 8.0/10)".to_string()));
+    }
+
+    #[test]
+    fn test_plan_days_multi_day_forecast() {
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
+        let start = chrono::Utc.with_ymd_and_hms(2024, 6, 15, 0, 0, 0).unwrap();
+        let hourly = (0..72)
+            .map(|hour| crate::weather::WeatherData {
+                temperature: 18.0,
+                humidity: 60.0,
+                wind_speed: 3.0,
+                cloud_cover: if hour < 24 { 80.0 } else { 10.0 },
+                visibility: 10.0,
+                precipitation_probability: 5.0,
+                description: "Тест".to_string(),
+                timestamp: start + chrono::Duration::hours(hour),
+                source: crate::weather::WeatherDataSource::Forecast,
+                cloud_layers: None,
+                dew_point: None,
+            })
+            .collect();
+        let forecast = WeatherForecast { hourly };
+
+        let plans = dashboard.plan_days(&forecast);
+
+        // В зависимости от часового пояса 72 часа покрывают 3 или 4 календарных дня
+        assert!(plans.len() >= 3 && plans.len() <= 4);
+        for plan in &plans {
+            assert!(plan.weather_score >= 0.0 && plan.weather_score <= 10.0);
+            assert_eq!(plan.golden_hour.daylight, Daylight::Normal);
+            let (Some(sunrise), Some(sunset)) = (plan.golden_hour.sunrise, plan.golden_hour.sunset)
+            else {
+                panic!("В Москве должны быть и восход, и закат");
+            };
+            assert!(sunrise < sunset);
+            // Лучшие часы плана относятся к его дню
+            assert!(plan
+                .best_hours
+                .iter()
+                .all(|hour| hour.date_naive() == plan.date));
+        }
+
+        // Суточный прогноз не разбивается на дни
+        assert!(dashboard.plan_days(&forecast.first_day()).is_empty());
+    }
+
+    #[test]
+    fn test_create_summary_polar_night() {
+        let dashboard = PhotographyDashboard::new(&Location::new("Лонгйир", 78.2232, 15.6267));
+        let date = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+        let golden_hour_info =
+            GoldenHourService::new(78.2232, 15.6267).calculate_golden_hours_for_date(date);
+
+        let summary = dashboard.create_summary(
+            None,
+            &golden_hour_info,
+            false,
+            create_test_date(),
+            Some(0.8),
+            Vec::new(),
+        );
+
+        assert!(summary
+            .key_highlights
+            .iter()
+            .any(|highlight| highlight.starts_with("Полярная ночь")));
+        assert!(summary
+            .warnings
+            .iter()
+            .any(|warning| warning.starts_with("Сегодня нет золотого часа")));
+    }
+
+    #[test]
+    fn test_create_summary_without_data_sources() {
+        let dashboard = PhotographyDashboard::new(&Location::new("TestCity", 55.7558, 37.6176));
+
+        let summary = dashboard.create_summary(
+            None,
+            &create_test_golden_hour_info(),
+            false,
+            create_test_date(),
+            None,
+            Vec::new(),
+        );
+
+        // Сводка строится даже без погоды и данных о сияниях
+        assert_eq!(summary.weather_score, None);
+        assert_eq!(summary.aurora_probability, None);
+        assert!(summary.best_shooting_hours.is_empty());
+        assert!(summary
+            .overall_recommendation
+            .contains("Нет данных о погоде"));
+    }
 }
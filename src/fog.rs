@@ -0,0 +1,259 @@
+//! # Fog Module
+//!
+//! Прогноз тумана и дымки по часам прогноза погоды.
+//!
+//! Радиационный туман образуется, когда воздух у земли остывает до точки
+//! росы: ясной безветренной ночью и ранним утром. Поэтому вероятность
+//! строится из разницы температуры и точки росы, влажности, скорости ветра
+//! и времени относительно восхода - туман чаще всего стоит за несколько
+//! часов до восхода и рассеивается в первые часы после него.
+//!
+//! ## Основные компоненты
+//!
+//! - [`FogHour`] - Вероятность тумана в час прогноза
+//! - [`FogMorning`] - Туманное утро, которое стоит выделить в сводке
+//! - [`predict_fog`] - Вероятность тумана для всех часов прогноза
+//! - [`dew_point`] - Оценка точки росы по температуре и влажности
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::fog::dew_point;
+//!
+//! // При влажности 100% точка росы равна температуре
+//! assert!((dew_point(5.0, 100.0) - 5.0).abs() < 1e-9);
+//! assert!(dew_point(5.0, 80.0) < 2.0);
+//! ```
+
+use crate::golden_hour::GoldenHourInfo;
+use crate::weather::{WeatherData, WeatherForecast};
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+/// Вероятность тумана, начиная с которой утро считается туманным, %
+pub const FOG_MORNING_PROBABILITY: f64 = 60.0;
+
+/// Часов до восхода, с которых туман наиболее вероятен
+const HOURS_BEFORE_SUNRISE: i64 = 3;
+
+/// Часов после восхода, за которые туман обычно рассеивается
+const HOURS_AFTER_SUNRISE: i64 = 2;
+
+/// Вероятность тумана в час прогноза
+#[derive(Debug, Clone, Serialize)]
+pub struct FogHour {
+    pub timestamp: DateTime<Utc>,
+    /// Вероятность тумана (0-100%)
+    pub probability: f64,
+    /// Разница температуры и точки росы, °C
+    pub dew_point_spread: f64,
+}
+
+/// Туманное утро: часы вокруг восхода с высокой вероятностью тумана
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FogMorning {
+    /// Восход, вокруг которого ожидается туман
+    pub sunrise: DateTime<Tz>,
+    /// Начало первого туманного часа
+    pub start: DateTime<Tz>,
+    /// Конец последнего туманного часа
+    pub end: DateTime<Tz>,
+    /// Наибольшая вероятность тумана за утро, %
+    pub probability: f64,
+}
+
+/// Точка росы по формуле Магнуса, °C
+///
+/// `humidity` - относительная влажность, %.
+pub fn dew_point(temperature: f64, humidity: f64) -> f64 {
+    const A: f64 = 17.62;
+    const B: f64 = 243.12;
+    let gamma = (humidity.clamp(1.0, 100.0) / 100.0).ln() + A * temperature / (B + temperature);
+    B * gamma / (A - gamma)
+}
+
+/// Вероятность тумана для всех часов прогноза
+///
+/// `golden_hours` - золотой час для дней прогноза, из него берутся восход
+/// и закат. Если источник не дает точку росы, она оценивается по
+/// температуре и влажности.
+pub fn predict_fog(forecast: &WeatherForecast, golden_hours: &[GoldenHourInfo]) -> Vec<FogHour> {
+    forecast
+        .hourly
+        .iter()
+        .map(|weather| {
+            let spread = weather.temperature
+                - weather
+                    .dew_point
+                    .unwrap_or_else(|| dew_point(weather.temperature, weather.humidity));
+            FogHour {
+                timestamp: weather.timestamp,
+                probability: fog_probability(
+                    weather,
+                    spread,
+                    time_factor(weather.timestamp, golden_hours),
+                ),
+                dew_point_spread: spread,
+            }
+        })
+        .collect()
+}
+
+/// Первое туманное утро прогноза
+///
+/// Утро туманное, если хотя бы в один час вокруг восхода вероятность
+/// тумана не ниже [`FOG_MORNING_PROBABILITY`].
+pub fn fog_morning(fog_hours: &[FogHour], golden_hours: &[GoldenHourInfo]) -> Option<FogMorning> {
+    golden_hours
+        .iter()
+        .filter_map(|info| info.sunrise)
+        .find_map(|sunrise| {
+            let foggy: Vec<&FogHour> = fog_hours
+                .iter()
+                .filter(|hour| {
+                    is_near_sunrise(hour.timestamp, sunrise)
+                        && hour.probability >= FOG_MORNING_PROBABILITY
+                })
+                .collect();
+            let (first, last) = (foggy.first()?, foggy.last()?);
+            let timezone = sunrise.timezone();
+            Some(FogMorning {
+                sunrise,
+                start: first.timestamp.with_timezone(&timezone),
+                end: (last.timestamp + Duration::hours(1)).with_timezone(&timezone),
+                probability: foggy
+                    .iter()
+                    .map(|hour| hour.probability)
+                    .fold(0.0, f64::max),
+            })
+        })
+}
+
+/// Вероятность тумана (0-100%) по погоде часа
+fn fog_probability(weather: &WeatherData, dew_point_spread: f64, time_factor: f64) -> f64 {
+    // Туман возможен, когда воздух почти насыщен: разница меньше 4°C
+    let spread_factor = ((4.0 - dew_point_spread) / 3.5).clamp(0.0, 1.0);
+    let humidity_factor = ((weather.humidity - 70.0) / 25.0).clamp(0.0, 1.0);
+    // Ветер перемешивает воздух и не дает туману лечь
+    let wind_factor = ((7.0 - weather.wind_speed) / 5.0).clamp(0.0, 1.0);
+
+    let probability =
+        100.0 * spread_factor * (0.5 + 0.5 * humidity_factor) * wind_factor * time_factor;
+    // Видимость меньше километра - туман уже стоит
+    if weather.visibility < 1.0 {
+        probability.max(90.0)
+    } else {
+        probability
+    }
+}
+
+/// Множитель времени суток: утро у восхода - 1, ночь - 0.7, день - 0.3
+fn time_factor(timestamp: DateTime<Utc>, golden_hours: &[GoldenHourInfo]) -> f64 {
+    let near_sunrise = golden_hours
+        .iter()
+        .filter_map(|info| info.sunrise)
+        .any(|sunrise| is_near_sunrise(timestamp, sunrise));
+    let is_day = golden_hours.iter().any(|info| {
+        info.sunrise
+            .zip(info.sunset)
+            .is_some_and(|(sunrise, sunset)| timestamp >= sunrise && timestamp < sunset)
+    });
+
+    if near_sunrise {
+        1.0
+    } else if is_day {
+        0.3
+    } else {
+        0.7
+    }
+}
+
+fn is_near_sunrise(timestamp: DateTime<Utc>, sunrise: DateTime<Tz>) -> bool {
+    timestamp >= sunrise - Duration::hours(HOURS_BEFORE_SUNRISE)
+        && timestamp <= sunrise + Duration::hours(HOURS_AFTER_SUNRISE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::golden_hour::GoldenHourService;
+    use crate::weather::WeatherDataSource;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::Moscow;
+    use pretty_assertions::assert_eq;
+
+    fn weather(hour: i64, temperature: f64, dew_point: f64, wind_speed: f64) -> WeatherData {
+        WeatherData {
+            temperature,
+            humidity: 96.0,
+            wind_speed,
+            cloud_cover: 10.0,
+            visibility: 10.0,
+            precipitation_probability: 0.0,
+            description: "тест".to_string(),
+            timestamp: Utc.with_ymd_and_hms(2024, 9, 15, 0, 0, 0).unwrap() + Duration::hours(hour),
+            source: WeatherDataSource::Forecast,
+            cloud_layers: None,
+            dew_point: Some(dew_point),
+        }
+    }
+
+    fn golden_hours(forecast: &WeatherForecast) -> Vec<GoldenHourInfo> {
+        GoldenHourService::new(55.7558, 37.6176)
+            .with_timezone(Moscow)
+            .golden_hours_between(
+                forecast.hourly.first().unwrap().timestamp,
+                forecast.hourly.last().unwrap().timestamp,
+            )
+    }
+
+    #[test]
+    fn test_dew_point_magnus() {
+        assert!((dew_point(20.0, 100.0) - 20.0).abs() < 1e-9);
+        // 20°C и 50% - точка росы около 9.3°C
+        assert!((dew_point(20.0, 50.0) - 9.3).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_calm_saturated_morning_is_foggy() {
+        // Восход в Москве 15 сентября около 06:20 по Москве (03:20 UTC)
+        let forecast = WeatherForecast {
+            hourly: (0..24)
+                .map(|hour| match hour {
+                    1..=4 => weather(hour, 8.0, 7.8, 1.0),
+                    _ => weather(hour, 15.0, 8.0, 1.0),
+                })
+                .collect(),
+        };
+        let golden_hours = golden_hours(&forecast);
+        let fog = predict_fog(&forecast, &golden_hours);
+
+        assert!(fog[2].probability > 90.0);
+        assert_eq!(fog[12].probability, 0.0);
+
+        let morning = fog_morning(&fog, &golden_hours).unwrap();
+        assert_eq!(morning.start, forecast.hourly[1].timestamp);
+        assert_eq!(morning.end, forecast.hourly[5].timestamp);
+    }
+
+    #[test]
+    fn test_wind_disperses_fog() {
+        let forecast = WeatherForecast {
+            hourly: (0..6).map(|hour| weather(hour, 8.0, 7.8, 8.0)).collect(),
+        };
+        let golden_hours = golden_hours(&forecast);
+        let fog = predict_fog(&forecast, &golden_hours);
+
+        assert!(fog.iter().all(|hour| hour.probability == 0.0));
+        assert!(fog_morning(&fog, &golden_hours).is_none());
+    }
+
+    #[test]
+    fn test_low_visibility_means_fog() {
+        let mut foggy = weather(12, 15.0, 8.0, 5.0);
+        foggy.visibility = 0.4;
+
+        assert_eq!(fog_probability(&foggy, 7.0, 0.3), 90.0);
+    }
+}
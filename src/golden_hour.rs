@@ -8,22 +8,39 @@
 //!
 //! - [`GoldenHourService`] - Сервис для расчета времени золотого часа
 //! - [`GoldenHourInfo`] - Структура с информацией о времени восхода, заката и золотого часа
+//! - [`TwilightInfo`] - Гражданские, навигационные и астрономические сумерки
+//! - [`DarknessWindow`] - Окно полной темноты для астрофотографии
+//! - [`LightThresholds`] - Высоты Солнца, ограничивающие золотой и синий час
+//! - [`SolarPosition`] - Высота и азимут Солнца в заданный момент
+//! - [`LightingPhase`] - Условия освещения (день, золотой час, синий час, ночь)
+//!
+//! Золотой час - время, когда Солнце находится между -4° и +6° над
+//! горизонтом, синий час - между -6° и -4°. Границы определяются по
+//! моменту, когда Солнце проходит заданную высоту, поэтому на высоких
+//! широтах золотой час может длиться несколько часов.
+//!
+//! Все моменты возвращаются в часовом поясе места съемки (см.
+//! [`GoldenHourService::with_timezone`]), а не компьютера.
 //!
 //! ## Пример использования
 //!
 //! ```rust,no_run
 //! use my_dashboard::golden_hour::GoldenHourService;
-//! use chrono::Local;
+//! use chrono::Utc;
 //!
 //! // Создаем сервис для Москвы
-//! let service = GoldenHourService::new(55.7558, 37.6176);
+//! let service = GoldenHourService::new(55.7558, 37.6176).with_timezone(chrono_tz::Europe::Moscow);
 //!
 //! // Получаем информацию о золотом часе на сегодня
-//! let current_time = Local::now();
+//! let current_time = Utc::now();
 //! let info = service.calculate_golden_hours(current_time);
 //!
-//! println!("Восход: {}", info.sunrise.format("%H:%M"));
-//! println!("Закат: {}", info.sunset.format("%H:%M"));
+//! // За полярным кругом восхода или заката может не быть
+//! if let (Some(sunrise), Some(sunset)) = (info.sunrise, info.sunset) {
+//!     println!("Восход: {} | Закат: {}", sunrise.format("%H:%M"), sunset.format("%H:%M"));
+//! } else {
+//!     println!("{}", info.daylight.name());
+//! }
 //! println!("Золотой час утром: {}-{}",
 //!     info.golden_hour_morning_start.format("%H:%M"),
 //!     info.golden_hour_morning_end.format("%H:%M"));
@@ -34,42 +51,255 @@
 //! }
 //! ```
 
+use crate::horizon::HorizonProfile;
+use crate::location::timezone_at;
+use crate::moon::{julian_centuries, local_sidereal_time, sun_longitude};
 use crate::{get_current_time, is_demo_mode};
-use chrono::{DateTime, Datelike, Local, NaiveDate};
-use sunrise::{Coordinates, SolarDay, SolarEvent};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Высоты Солнца в градусах, ограничивающие золотой и синий час
+///
+/// Золотой час - Солнце между `golden_lower` и `golden_upper`, синий час -
+/// между `blue_lower` и `golden_lower`. Задаются в секции `[golden_hour]`
+/// файла конфигурации.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LightThresholds {
+    /// Верхняя граница золотого часа
+    pub golden_upper: f64,
+    /// Нижняя граница золотого часа и верхняя граница синего часа
+    pub golden_lower: f64,
+    /// Нижняя граница синего часа
+    pub blue_lower: f64,
+}
+
+impl Default for LightThresholds {
+    fn default() -> Self {
+        Self {
+            golden_upper: 6.0,
+            golden_lower: -4.0,
+            blue_lower: -6.0,
+        }
+    }
+}
+
+impl LightThresholds {
+    /// Проверяет, что границы упорядочены и лежат в разумных пределах
+    pub fn validate(&self) -> Result<()> {
+        let values = [self.blue_lower, self.golden_lower, self.golden_upper];
+        if values.iter().any(|value| !(-18.0..=30.0).contains(value)) {
+            bail!(
+                "Высоты Солнца для золотого и синего часа должны быть от -18° до 30°: {:?}",
+                values
+            );
+        }
+        if !(self.blue_lower < self.golden_lower && self.golden_lower < self.golden_upper) {
+            bail!(
+                "Ожидается blue_lower < golden_lower < golden_upper, получено {} / {} / {}",
+                self.blue_lower,
+                self.golden_lower,
+                self.golden_upper
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Начало и конец сумерек одного типа
+///
+/// `None` - Солнце в эти сутки не опускается до соответствующего угла
+/// под горизонтом (например, белые ночи).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TwilightTimes {
+    /// Утро: Солнце поднимается выше угла погружения, сумерки начинаются
+    pub dawn: Option<DateTime<Tz>>,
+    /// Вечер: Солнце опускается ниже угла погружения, сумерки заканчиваются
+    pub dusk: Option<DateTime<Tz>>,
+}
+
+/// Окно полной темноты: Солнце ниже 18° под горизонтом
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DarknessWindow {
+    /// Конец вечерних астрономических сумерек
+    pub start: DateTime<Tz>,
+    /// Начало утренних астрономических сумерек следующего дня
+    pub end: DateTime<Tz>,
+}
+
+impl DarknessWindow {
+    /// Попадает ли момент в окно темноты
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        time >= self.start && time < self.end
+    }
+
+    /// Продолжительность темноты
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// Сумерки по углу погружения Солнца под горизонт
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TwilightInfo {
+    /// Гражданские сумерки: Солнце до 6° под горизонтом
+    pub civil: TwilightTimes,
+    /// Навигационные сумерки: Солнце до 12° под горизонтом
+    pub nautical: TwilightTimes,
+    /// Астрономические сумерки: Солнце до 18° под горизонтом
+    pub astronomical: TwilightTimes,
+    /// Полная темнота с вечера этого дня до утра следующего
+    pub darkness: Option<DarknessWindow>,
+}
+
+/// Положение Солнца на небе
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SolarPosition {
+    /// Высота центра Солнца над горизонтом в градусах (без рефракции)
+    pub altitude: f64,
+    /// Азимут в градусах (0 - север, 90 - восток)
+    pub azimuth: f64,
+}
+
+/// Высота Солнца в момент восхода и заката: рефракция 34' и радиус диска 16'
+const SUNRISE_ALTITUDE: f64 = -0.8333;
+
+/// Высота Солнца в конце гражданских сумерек
+const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.0;
+
+/// Высота Солнца в конце навигационных сумерек
+const NAUTICAL_TWILIGHT_ALTITUDE: f64 = -12.0;
+
+/// Высота Солнца в конце астрономических сумерек
+const ASTRONOMICAL_TWILIGHT_ALTITUDE: f64 = -18.0;
+
+/// Смена дня и ночи в заданные сутки
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Daylight {
+    /// Солнце восходит и заходит (или делает что-то одно в переходные дни)
+    Normal,
+    /// Солнце не заходит
+    PolarDay,
+    /// Солнце не восходит
+    PolarNight,
+}
+
+impl Daylight {
+    /// Название на русском языке
+    pub fn name(self) -> &'static str {
+        match self {
+            Daylight::Normal => "Обычный день",
+            Daylight::PolarDay => "Полярный день",
+            Daylight::PolarNight => "Полярная ночь",
+        }
+    }
+}
+
+/// Условия освещения, определяемые высотой Солнца над горизонтом
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LightingPhase {
+    /// Солнце выше золотого часа
+    Day,
+    /// Золотой час
+    GoldenHour,
+    /// Синий час
+    BlueHour,
+    /// Солнце ниже синего часа
+    Night,
+}
+
+impl LightingPhase {
+    /// Название на русском языке
+    pub fn name(self) -> &'static str {
+        match self {
+            LightingPhase::Day => "День",
+            LightingPhase::GoldenHour => "Золотой час",
+            LightingPhase::BlueHour => "Синий час",
+            LightingPhase::Night => "Ночь",
+        }
+    }
+}
 
 /// Информация о времени восхода, заката, золотого и синего часа
-#[derive(Debug, Clone)]
+///
+/// Если золотого или синего часа в эти сутки нет, начало и конец
+/// соответствующих окон совпадают, а `has_golden_hour` / `has_blue_hour`
+/// равны `false`.
+#[derive(Debug, Clone, Serialize)]
 pub struct GoldenHourInfo {
-    /// Время восхода солнца
-    pub sunrise: DateTime<Local>,
-    /// Время заката солнца
-    pub sunset: DateTime<Local>,
+    /// Полярный день, полярная ночь или обычная смена дня и ночи
+    pub daylight: Daylight,
+    /// Время восхода солнца, `None` если Солнце в эти сутки не восходит
+    pub sunrise: Option<DateTime<Tz>>,
+    /// Время заката солнца, `None` если Солнце в эти сутки не заходит
+    pub sunset: Option<DateTime<Tz>>,
+    /// Азимут точки восхода в градусах (0 - север, 90 - восток)
+    pub sunrise_azimuth: Option<f64>,
+    /// Азимут точки заката в градусах
+    pub sunset_azimuth: Option<f64>,
+    /// Есть ли в эти сутки золотой час
+    pub has_golden_hour: bool,
+    /// Есть ли в эти сутки синий час
+    pub has_blue_hour: bool,
     /// Начало утреннего золотого часа
-    pub golden_hour_morning_start: DateTime<Local>,
+    pub golden_hour_morning_start: DateTime<Tz>,
     /// Конец утреннего золотого часа
-    pub golden_hour_morning_end: DateTime<Local>,
+    pub golden_hour_morning_end: DateTime<Tz>,
     /// Начало вечернего золотого часа
-    pub golden_hour_evening_start: DateTime<Local>,
+    pub golden_hour_evening_start: DateTime<Tz>,
     /// Конец вечернего золотого часа
-    pub golden_hour_evening_end: DateTime<Local>,
+    pub golden_hour_evening_end: DateTime<Tz>,
     /// Начало утреннего синего часа
-    pub blue_hour_morning_start: DateTime<Local>,
+    pub blue_hour_morning_start: DateTime<Tz>,
     /// Конец утреннего синего часа
-    pub blue_hour_morning_end: DateTime<Local>,
+    pub blue_hour_morning_end: DateTime<Tz>,
     /// Начало вечернего синего часа
-    pub blue_hour_evening_start: DateTime<Local>,
+    pub blue_hour_evening_start: DateTime<Tz>,
     /// Конец вечернего синего часа
-    pub blue_hour_evening_end: DateTime<Local>,
+    pub blue_hour_evening_end: DateTime<Tz>,
+    /// Гражданские, навигационные и астрономические сумерки
+    pub twilight: TwilightInfo,
+}
+
+impl GoldenHourInfo {
+    /// Попадает ли момент в утренний или вечерний золотой час
+    pub fn is_golden_hour_at<Z: TimeZone>(&self, time: DateTime<Z>) -> bool {
+        // Пустое окно (начало совпадает с концом) - золотого часа нет
+        let within =
+            |start: DateTime<Tz>, end: DateTime<Tz>| start < end && time >= start && time <= end;
+        within(self.golden_hour_morning_start, self.golden_hour_morning_end)
+            || within(self.golden_hour_evening_start, self.golden_hour_evening_end)
+    }
+
+    /// Пересекается ли интервал `[start, end)` с утренним или вечерним золотым часом
+    ///
+    /// Используется для часов прогноза: золотой час короче часа и может
+    /// начинаться в середине часа.
+    pub fn overlaps_golden_hour<Z: TimeZone>(&self, start: DateTime<Z>, end: DateTime<Z>) -> bool {
+        let overlaps = |window_start: DateTime<Tz>, window_end: DateTime<Tz>| {
+            window_start < window_end && start < window_end && end > window_start
+        };
+        overlaps(self.golden_hour_morning_start, self.golden_hour_morning_end)
+            || overlaps(self.golden_hour_evening_start, self.golden_hour_evening_end)
+    }
 }
 
 /// Сервис для расчета золотого часа и синего часа
 ///
-/// Использует библиотеку `sunrise` для точных астрономических расчетов
-/// времени восхода, заката и оптимальных условий освещения для фотографии.
+/// Считает высоту Солнца по собственной модели положения Солнца
+/// ([`sun_position`](Self::sun_position)) и ищет моменты, когда она проходит
+/// заданные углы: восход и заход, границы золотого и синего часа, сумерки.
 pub struct GoldenHourService {
     latitude: f64,
     longitude: f64,
+    elevation: f64,
+    horizon: Option<HorizonProfile>,
+    thresholds: LightThresholds,
+    timezone: Tz,
 }
 
 impl GoldenHourService {
@@ -80,6 +310,9 @@ impl GoldenHourService {
     /// * `latitude` - Широта в градусах (от -90 до 90)
     /// * `longitude` - Долгота в градусах (от -180 до 180)
     ///
+    /// Часовой пояс по умолчанию определяется по координатам (см.
+    /// [`timezone_at`]).
+    ///
     /// # Пример
     ///
     /// ```rust
@@ -91,14 +324,49 @@ impl GoldenHourService {
         Self {
             latitude,
             longitude,
+            elevation: 0.0,
+            horizon: None,
+            thresholds: LightThresholds::default(),
+            timezone: timezone_at(latitude, longitude),
         }
     }
 
+    /// Задает часовой пояс места съемки: в нем считаются сутки и выводится время
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Часовой пояс, в котором возвращаются моменты
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
+    /// Задает высоту наблюдателя над уровнем моря в метрах
+    ///
+    /// С горы Солнце видно раньше восхода и дольше после заката.
+    pub fn with_elevation(mut self, elevation: f64) -> Self {
+        self.elevation = elevation;
+        self
+    }
+
+    /// Задает профиль горизонта: восход и закат считаются над линией рельефа
+    pub fn with_horizon(mut self, horizon: HorizonProfile) -> Self {
+        self.horizon = Some(horizon);
+        self
+    }
+
+    /// Задает высоты Солнца для границ золотого и синего часа
+    pub fn with_thresholds(mut self, thresholds: LightThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
     /// Рассчитывает время золотого и синего часа для указанной даты
     ///
     /// # Аргументы
     ///
-    /// * `date` - Дата для расчета
+    /// * `date` - Момент, сутки которого (по часам места съемки) рассчитываются
     ///
     /// # Возвращает
     ///
@@ -108,16 +376,17 @@ impl GoldenHourService {
     ///
     /// ```rust
     /// use my_dashboard::golden_hour::GoldenHourService;
-    /// use chrono::Local;
+    /// use chrono::Utc;
     ///
     /// let service = GoldenHourService::new(55.7558, 37.6176);
-    /// let current_time = Local::now();
+    /// let current_time = Utc::now();
     /// let info = service.calculate_golden_hours(current_time);
     ///
-    /// println!("Восход: {}", info.sunrise.format("%H:%M"));
-    /// println!("Закат: {}", info.sunset.format("%H:%M"));
+    /// if let Some(sunrise) = info.sunrise {
+    ///     println!("Восход: {}", sunrise.format("%H:%M"));
+    /// }
     /// ```
-    pub fn calculate_golden_hours(&self, date: DateTime<Local>) -> GoldenHourInfo {
+    pub fn calculate_golden_hours<Z: TimeZone>(&self, date: DateTime<Z>) -> GoldenHourInfo {
         // В DEMO режиме используем фиксированную дату для стабильности тестов
         let demo_mode = is_demo_mode();
 
@@ -125,53 +394,72 @@ impl GoldenHourService {
             // Используем фиксированную дату для стабильности (ночное время)
             get_current_time()
         } else {
-            date
+            date.with_timezone(&Utc)
         };
 
-        // Создаем координаты
-        let coords = Coordinates::new(self.latitude, self.longitude).expect("Invalid coordinates");
-
-        // Создаем дату
-        let naive_date = NaiveDate::from_ymd_opt(
-            calculation_date.year(),
-            calculation_date.month(),
-            calculation_date.day(),
+        self.calculate_golden_hours_for_date(
+            calculation_date.with_timezone(&self.timezone).date_naive(),
         )
-        .expect("Invalid date");
-
-        // Создаем солнечный день
-        let solar_day = SolarDay::new(coords, naive_date);
-
-        // Получаем время восхода и заката
-        let sunrise_timestamp = solar_day.event_time(SolarEvent::Sunrise).timestamp();
-        let sunset_timestamp = solar_day.event_time(SolarEvent::Sunset).timestamp();
-
-        let sunrise = DateTime::from_timestamp(sunrise_timestamp, 0)
-            .unwrap()
-            .with_timezone(&Local);
-        let sunset = DateTime::from_timestamp(sunset_timestamp, 0)
-            .unwrap()
-            .with_timezone(&Local);
-
-        // Золотой час утром: за 1 час до восхода и 1 час после
-        let golden_hour_morning_start = sunrise - chrono::Duration::hours(1);
-        let golden_hour_morning_end = sunrise + chrono::Duration::hours(1);
-
-        // Золотой час вечером: за 1 час до заката и 1 час после
-        let golden_hour_evening_start = sunset - chrono::Duration::hours(1);
-        let golden_hour_evening_end = sunset + chrono::Duration::hours(1);
-
-        // Синий час утром: за 30 минут до восхода
-        let blue_hour_morning_start = sunrise - chrono::Duration::minutes(30);
-        let blue_hour_morning_end = sunrise;
+    }
 
-        // Синий час вечером: за 30 минут после заката
-        let blue_hour_evening_start = sunset;
-        let blue_hour_evening_end = sunset + chrono::Duration::minutes(30);
+    /// Рассчитывает золотой и синий час для конкретной календарной даты
+    ///
+    /// В отличие от [`calculate_golden_hours`](Self::calculate_golden_hours) не
+    /// подменяет дату в DEMO режиме, поэтому используется для планирования
+    /// съемки на несколько дней вперед.
+    pub fn calculate_golden_hours_for_date(&self, naive_date: NaiveDate) -> GoldenHourInfo {
+        // Утром Солнце поднимается от нижней кульминации до полудня,
+        // вечером опускается от полудня до следующей нижней кульминации
+        let noon = self.solar_noon(naive_date);
+        let (previous_midnight, next_midnight) =
+            (noon - Duration::hours(12), noon + Duration::hours(12));
+        let morning = |altitude| self.altitude_crossing(previous_midnight, noon, altitude);
+        let evening = |altitude| self.altitude_crossing(noon, next_midnight, altitude);
+
+        // Восход и заход: на высоких широтах их может не быть
+        let sunrise = self.exact_crossing(previous_midnight, noon, SUNRISE_ALTITUDE);
+        let sunset = self.exact_crossing(noon, next_midnight, SUNRISE_ALTITUDE);
+        let azimuth =
+            |time: Option<DateTime<Utc>>| time.map(|time| self.sun_position(time).azimuth);
+        let local =
+            |time: Option<DateTime<Utc>>| time.map(|time| time.with_timezone(&self.timezone));
+        // Смена дня и ночи определяется по тому же видимому горизонту, что
+        // восход и заход: за горами Солнце может не показаться весь день
+        let daylight = if sunrise.is_some() || sunset.is_some() {
+            Daylight::Normal
+        } else if self.height_above_horizon(noon) < SUNRISE_ALTITUDE {
+            Daylight::PolarNight
+        } else {
+            Daylight::PolarDay
+        };
+        let LightThresholds {
+            golden_upper,
+            golden_lower,
+            blue_lower,
+        } = self.thresholds;
+
+        // Золотой час: Солнце между golden_lower и golden_upper
+        let golden_hour_morning_start = morning(golden_lower);
+        let golden_hour_morning_end = morning(golden_upper);
+        let golden_hour_evening_start = evening(golden_upper);
+        let golden_hour_evening_end = evening(golden_lower);
+
+        // Синий час: Солнце между blue_lower и golden_lower
+        let blue_hour_morning_start = morning(blue_lower);
+        let blue_hour_morning_end = golden_hour_morning_start;
+        let blue_hour_evening_start = golden_hour_evening_end;
+        let blue_hour_evening_end = evening(blue_lower);
 
         GoldenHourInfo {
-            sunrise,
-            sunset,
+            daylight,
+            sunrise: local(sunrise),
+            sunset: local(sunset),
+            sunrise_azimuth: azimuth(sunrise),
+            sunset_azimuth: azimuth(sunset),
+            has_golden_hour: golden_hour_morning_start < golden_hour_morning_end
+                || golden_hour_evening_start < golden_hour_evening_end,
+            has_blue_hour: blue_hour_morning_start < blue_hour_morning_end
+                || blue_hour_evening_start < blue_hour_evening_end,
             golden_hour_morning_start,
             golden_hour_morning_end,
             golden_hour_evening_start,
@@ -180,11 +468,228 @@ impl GoldenHourService {
             blue_hour_morning_end,
             blue_hour_evening_start,
             blue_hour_evening_end,
+            twilight: self.calculate_twilight(naive_date),
+        }
+    }
+
+    /// Рассчитывает сумерки всех трех типов и окно полной темноты
+    ///
+    /// Окно темноты начинается вечером `naive_date` и заканчивается утром
+    /// следующего дня; если Солнце не опускается ниже 18°, окна нет.
+    /// Сумерки считаются по геометрической высоте Солнца тем же поиском
+    /// пересечения, что и синий час, поэтому без высоты и профиля горизонта
+    /// конец синего часа совпадает с концом гражданских сумерек.
+    pub fn calculate_twilight(&self, naive_date: NaiveDate) -> TwilightInfo {
+        let noon = self.solar_noon(naive_date);
+        let twilight = |altitude| TwilightTimes {
+            dawn: self.geometric_crossing(noon - Duration::hours(12), noon, altitude),
+            dusk: self.geometric_crossing(noon, noon + Duration::hours(12), altitude),
+        };
+        let astronomical = twilight(ASTRONOMICAL_TWILIGHT_ALTITUDE);
+
+        let next_dawn = naive_date.succ_opt().and_then(|next_date| {
+            let next_noon = self.solar_noon(next_date);
+            self.geometric_crossing(
+                next_noon - Duration::hours(12),
+                next_noon,
+                ASTRONOMICAL_TWILIGHT_ALTITUDE,
+            )
+        });
+        let darkness = match (astronomical.dusk, next_dawn) {
+            (Some(start), Some(end)) if start < end => Some(DarknessWindow { start, end }),
+            _ => None,
+        };
+
+        TwilightInfo {
+            civil: twilight(CIVIL_TWILIGHT_ALTITUDE),
+            nautical: twilight(NAUTICAL_TWILIGHT_ALTITUDE),
+            astronomical,
+            darkness,
+        }
+    }
+
+    /// Окна полной темноты, пересекающиеся с интервалом `[start, end]`
+    ///
+    /// Используется для выбора часов астрофотографии в прогнозе.
+    pub fn darkness_windows(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<DarknessWindow> {
+        // Ночь начинается накануне: окно предыдущего вечера может
+        // захватывать начало интервала
+        let mut date = start.with_timezone(&self.timezone).date_naive().pred_opt();
+        let last_date = end.with_timezone(&self.timezone).date_naive();
+        let mut windows = Vec::new();
+
+        while let Some(current) = date.filter(|current| *current <= last_date) {
+            if let Some(window) = self.calculate_twilight(current).darkness {
+                if window.end > start && window.start < end {
+                    windows.push(window);
+                }
+            }
+            date = current.succ_opt();
+        }
+        windows
+    }
+
+    /// Золотой и синий час для каждого дня интервала `[start, end]`
+    ///
+    /// Используется для оценки часов прогноза. Даты берутся по часам места
+    /// съемки, начиная с предыдущего дня: на высоких широтах вечерний золотой
+    /// час может закончиться после полуночи.
+    pub fn golden_hours_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<GoldenHourInfo> {
+        let mut date = start.with_timezone(&self.timezone).date_naive().pred_opt();
+        let last_date = end.with_timezone(&self.timezone).date_naive();
+        let mut days = Vec::new();
+
+        while let Some(current) = date.filter(|current| *current <= last_date) {
+            days.push(self.calculate_golden_hours_for_date(current));
+            date = current.succ_opt();
         }
+        days
+    }
+
+    /// Положение Солнца на небе в заданный момент
+    ///
+    /// # Пример
+    ///
+    /// ```rust
+    /// use chrono::{TimeZone, Utc};
+    /// use my_dashboard::golden_hour::GoldenHourService;
+    ///
+    /// let service = GoldenHourService::new(55.7558, 37.6176);
+    /// let position = service.sun_position(Utc.with_ymd_and_hms(2024, 6, 21, 9, 30, 0).unwrap());
+    ///
+    /// // Летний полдень в Москве: Солнце высоко на юге
+    /// assert!(position.altitude > 55.0);
+    /// assert!((position.azimuth - 180.0).abs() < 5.0);
+    /// ```
+    pub fn sun_position(&self, time: DateTime<Utc>) -> SolarPosition {
+        let (right_ascension, declination) = sun_equatorial(time);
+        let hour_angle = (local_sidereal_time(time, self.longitude) - right_ascension).to_radians();
+        let declination = declination.to_radians();
+        let latitude = self.latitude.to_radians();
+
+        let altitude = (latitude.sin() * declination.sin()
+            + latitude.cos() * declination.cos() * hour_angle.cos())
+        .clamp(-1.0, 1.0)
+        .asin()
+        .to_degrees();
+        let azimuth = hour_angle
+            .sin()
+            .atan2(hour_angle.cos() * latitude.sin() - declination.tan() * latitude.cos())
+            .to_degrees()
+            + 180.0;
+
+        SolarPosition {
+            altitude,
+            azimuth: azimuth.rem_euclid(360.0),
+        }
+    }
+
+    /// Высота Солнца над видимым горизонтом места съемки
+    ///
+    /// С высоты горизонт опускается (понижение горизонта), а горы и лес
+    /// из профиля горизонта поднимают его.
+    fn height_above_horizon(&self, time: DateTime<Utc>) -> f64 {
+        let position = self.sun_position(time);
+        let terrain = self
+            .horizon
+            .as_ref()
+            .map_or(0.0, |horizon| horizon.altitude_at(position.azimuth));
+        position.altitude - terrain + horizon_dip(self.elevation)
+    }
+
+    /// Местный солнечный полдень (верхняя кульминация Солнца)
+    fn solar_noon(&self, naive_date: NaiveDate) -> DateTime<Utc> {
+        let mut noon = naive_date
+            .and_hms_opt(12, 0, 0)
+            .unwrap_or_default()
+            .and_utc()
+            - Duration::seconds((self.longitude / 15.0 * 3600.0) as i64);
+
+        // Поправка на уравнение времени: смещаемся на часовой угол Солнца
+        for _ in 0..2 {
+            let (right_ascension, _) = sun_equatorial(noon);
+            let hour_angle = (local_sidereal_time(noon, self.longitude) - right_ascension + 180.0)
+                .rem_euclid(360.0)
+                - 180.0;
+            noon -= Duration::seconds((hour_angle / 15.0 * 3600.0) as i64);
+        }
+        noon
+    }
+
+    /// Дата по местному солнечному времени: ее полдень ближе всего к `time`
+    ///
+    /// В отличие от даты по часовому поясу не зависит от границ поясов и
+    /// летнего времени, поэтому до полудня этой даты Солнце всегда
+    /// поднимается, а после - опускается.
+    fn solar_date(&self, time: DateTime<Utc>) -> NaiveDate {
+        (time + Duration::seconds((self.longitude / 15.0 * 3600.0) as i64)).date_naive()
+    }
+
+    /// Момент, когда Солнце проходит высоту `altitude` на отрезке `[from, to]`
+    ///
+    /// Высота отсчитывается от видимого горизонта места съемки (см.
+    /// [`height_above_horizon`](Self::height_above_horizon)).
+    ///
+    /// На отрезке от нижней кульминации до верхней (или наоборот) высота
+    /// меняется монотонно. Если Солнце уже выше (для утра) заданной высоты
+    /// в начале отрезка, возвращается `from`; если не достигает ее до
+    /// конца отрезка - `to`. Так на высоких широтах золотой час может
+    /// растянуться на всю ночь или на весь день.
+    fn altitude_crossing(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        altitude: f64,
+    ) -> DateTime<Tz> {
+        let time = self.exact_crossing(from, to, altitude).unwrap_or_else(|| {
+            let rising = self.height_above_horizon(from) < self.height_above_horizon(to);
+            if (self.height_above_horizon(from) >= altitude) == rising {
+                from
+            } else {
+                to
+            }
+        });
+        time.with_timezone(&self.timezone)
+    }
+
+    /// Момент прохождения высоты `altitude` внутри отрезка `[from, to]`
+    ///
+    /// `None`, если Солнце проходит эту высоту не на отрезке: уже прошло
+    /// ее к началу или не достигает до конца.
+    fn exact_crossing(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        altitude: f64,
+    ) -> Option<DateTime<Utc>> {
+        find_crossing(|time| self.height_above_horizon(time), from, to, altitude)
+    }
+
+    /// Как [`exact_crossing`](Self::exact_crossing), но по геометрической
+    /// высоте Солнца, без высоты наблюдателя и профиля горизонта
+    fn geometric_crossing(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        altitude: f64,
+    ) -> Option<DateTime<Tz>> {
+        find_crossing(|time| self.sun_position(time).altitude, from, to, altitude)
+            .map(|time| time.with_timezone(&self.timezone))
     }
 
     /// Проверяет, является ли текущее время золотым часом
     ///
+    /// Текущее время берется из [`get_current_time`], поэтому учитывает
+    /// `--at` и DEMO режим.
+    ///
     /// # Возвращает
     ///
     /// `bool` - `true` если сейчас золотой час, `false` в противном случае
@@ -200,13 +705,9 @@ impl GoldenHourService {
     /// }
     /// ```
     pub fn is_golden_hour(&self) -> bool {
-        let current_time = chrono::Local::now();
-        let golden_hours = self.calculate_golden_hours(current_time);
-
-        (current_time >= golden_hours.golden_hour_morning_start
-            && current_time <= golden_hours.golden_hour_morning_end)
-            || (current_time >= golden_hours.golden_hour_evening_start
-                && current_time <= golden_hours.golden_hour_evening_end)
+        let current_time = get_current_time();
+        self.calculate_golden_hours(current_time)
+            .is_golden_hour_at(current_time)
     }
 
     /// Определяет текущие условия освещения для указанного времени
@@ -223,82 +724,165 @@ impl GoldenHourService {
     ///
     /// ```rust
     /// use my_dashboard::golden_hour::GoldenHourService;
-    /// use chrono::Local;
+    /// use chrono::Utc;
     ///
     /// let service = GoldenHourService::new(55.7558, 37.6176);
-    /// let current_time = Local::now();
+    /// let current_time = Utc::now();
     /// let condition = service.get_current_lighting_condition(current_time);
     /// println!("Текущие условия: {}", condition);
     /// ```
-    pub fn get_current_lighting_condition(&self, current_time: DateTime<Local>) -> String {
+    pub fn get_current_lighting_condition<Z: TimeZone>(&self, current_time: DateTime<Z>) -> String {
         // В DEMO режиме используем фиксированное время для стабильности тестов
         let demo_mode = is_demo_mode();
 
-        let calculation_time = if demo_mode {
+        let time = if demo_mode {
             // Используем фиксированное время для стабильности (ночное время)
             get_current_time()
         } else {
-            current_time
+            current_time.with_timezone(&Utc)
         };
 
-        let golden_hours = self.calculate_golden_hours(calculation_time);
-
-        // Сначала проверяем синий час
-        if calculation_time >= golden_hours.blue_hour_morning_start
-            && calculation_time <= golden_hours.blue_hour_morning_end
-        {
-            "Синий час (утро)".to_string()
-        } else if calculation_time >= golden_hours.blue_hour_evening_start
-            && calculation_time <= golden_hours.blue_hour_evening_end
-        {
-            "Синий час (вечер)".to_string()
-        } else if calculation_time >= golden_hours.golden_hour_morning_start
-            && calculation_time <= golden_hours.golden_hour_morning_end
-        {
-            "Золотой час (утро)".to_string()
-        } else if calculation_time >= golden_hours.golden_hour_evening_start
-            && calculation_time <= golden_hours.golden_hour_evening_end
-        {
-            "Золотой час (вечер)".to_string()
-        } else if calculation_time >= golden_hours.sunrise
-            && calculation_time <= golden_hours.sunset
-        {
-            "Дневное время".to_string()
+        let morning = time < self.solar_noon(self.solar_date(time));
+        let part_of_day = if morning { "утро" } else { "вечер" };
+
+        match self.lighting_phase(time) {
+            LightingPhase::Day => "Дневное время".to_string(),
+            LightingPhase::GoldenHour => format!("Золотой час ({})", part_of_day),
+            LightingPhase::BlueHour => format!("Синий час ({})", part_of_day),
+            LightingPhase::Night => "Ночное время".to_string(),
+        }
+    }
+
+    /// Условия освещения в заданный момент
+    ///
+    /// Определяются высотой Солнца над видимым горизонтом, поэтому верны и
+    /// в полярный день, и в полярную ночь. В отличие от
+    /// [`get_current_lighting_condition`](Self::get_current_lighting_condition)
+    /// не подменяет время в DEMO режиме.
+    pub fn lighting_phase(&self, time: DateTime<Utc>) -> LightingPhase {
+        let altitude = self.height_above_horizon(time);
+        let LightThresholds {
+            golden_upper,
+            golden_lower,
+            blue_lower,
+        } = self.thresholds;
+
+        if altitude > golden_upper {
+            LightingPhase::Day
+        } else if altitude >= golden_lower {
+            LightingPhase::GoldenHour
+        } else if altitude >= blue_lower {
+            LightingPhase::BlueHour
         } else {
-            "Ночное время".to_string()
+            LightingPhase::Night
         }
     }
+
+    /// Лучшие для съемки условия освещения в интервале `[start, end)`
+    ///
+    /// Золотой час важнее синего, синий - важнее дня и ночи: час прогноза,
+    /// захватывающий 15 минут синего часа, помечается синим часом. Высота
+    /// Солнца проверяется каждые 5 минут.
+    pub fn lighting_phase_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> LightingPhase {
+        let priority = |phase: &LightingPhase| match phase {
+            LightingPhase::GoldenHour => 3,
+            LightingPhase::BlueHour => 2,
+            LightingPhase::Day => 1,
+            LightingPhase::Night => 0,
+        };
+        let step = Duration::minutes(5);
+        let mut time = start;
+        let mut best = self.lighting_phase(start);
+
+        while time < end {
+            let phase = self.lighting_phase(time);
+            if priority(&phase) > priority(&best) {
+                best = phase;
+            }
+            time += step;
+        }
+        best
+    }
+}
+
+/// Момент, когда `height` проходит `altitude` на отрезке `[from, to]`
+///
+/// Высота на отрезке должна меняться монотонно; поиск делением пополам
+/// с точностью до 30 секунд.
+fn find_crossing(
+    height: impl Fn(DateTime<Utc>) -> f64,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    altitude: f64,
+) -> Option<DateTime<Utc>> {
+    let rising = height(from) < height(to);
+    let reached = |time| (height(time) >= altitude) == rising;
+
+    let (mut start, mut end) = (from, to);
+    if reached(start) || !reached(end) {
+        return None;
+    }
+    while end - start > Duration::seconds(30) {
+        let middle = start + (end - start) / 2;
+        if reached(middle) {
+            end = middle;
+        } else {
+            start = middle;
+        }
+    }
+    Some(start + (end - start) / 2)
+}
+
+/// Понижение видимого горизонта в градусах для наблюдателя на высоте `elevation` метров
+///
+/// Приближение с учетом земной рефракции: 1.76' × √h.
+fn horizon_dip(elevation: f64) -> f64 {
+    1.76 / 60.0 * elevation.max(0.0).sqrt()
+}
+
+/// Прямое восхождение и склонение Солнца в градусах
+fn sun_equatorial(time: DateTime<Utc>) -> (f64, f64) {
+    let longitude = sun_longitude(time).to_radians();
+    let obliquity = (23.439_291 - 0.013_004_2 * julian_centuries(time)).to_radians();
+    let right_ascension = (obliquity.cos() * longitude.sin()).atan2(longitude.cos());
+    let declination = (obliquity.sin() * longitude.sin()).asin();
+    (right_ascension.to_degrees(), declination.to_degrees())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+    use chrono::{NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+    use chrono_tz::Europe::Moscow;
     use pretty_assertions::assert_eq;
 
     // Вспомогательные функции для создания тестовых данных
     fn create_test_service() -> GoldenHourService {
-        GoldenHourService::new(55.7558, 37.6176) // Москва
+        GoldenHourService::new(55.7558, 37.6176).with_timezone(Moscow) // Москва
     }
 
-    fn create_test_date() -> DateTime<Local> {
+    fn create_test_date() -> DateTime<Tz> {
         // Используем фиксированную дату для тестов (летний день)
         let naive_date = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
         let naive_datetime = NaiveDateTime::new(
             naive_date,
             chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
         );
-        Local.from_local_datetime(&naive_datetime).unwrap()
+        Moscow.from_local_datetime(&naive_datetime).unwrap()
     }
 
-    fn create_winter_date() -> DateTime<Local> {
+    fn create_winter_date() -> DateTime<Tz> {
         // Зимний день для тестирования коротких дней
         let naive_date = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
         let naive_datetime = NaiveDateTime::new(
             naive_date,
             chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
         );
-        Local.from_local_datetime(&naive_datetime).unwrap()
+        Moscow.from_local_datetime(&naive_datetime).unwrap()
     }
 
     #[test]
@@ -308,30 +892,21 @@ mod tests {
         let info = service.calculate_golden_hours(test_date);
 
         // Проверяем, что все поля заполнены
-        assert!(info.sunrise > info.golden_hour_morning_start);
-        assert!(info.sunrise < info.golden_hour_morning_end);
-        assert!(info.sunset > info.golden_hour_evening_start);
-        assert!(info.sunset < info.golden_hour_evening_end);
-
-        // Проверяем золотые часы
-        assert_eq!(
-            info.golden_hour_morning_end - info.golden_hour_morning_start,
-            chrono::Duration::hours(2)
-        );
-        assert_eq!(
-            info.golden_hour_evening_end - info.golden_hour_evening_start,
-            chrono::Duration::hours(2)
-        );
+        assert!(info.sunrise.unwrap() > info.golden_hour_morning_start);
+        assert!(info.sunrise.unwrap() < info.golden_hour_morning_end);
+        assert!(info.sunset.unwrap() > info.golden_hour_evening_start);
+        assert!(info.sunset.unwrap() < info.golden_hour_evening_end);
+
+        // Синий час сразу переходит в золотой и обратно
+        assert_eq!(info.blue_hour_morning_end, info.golden_hour_morning_start);
+        assert_eq!(info.blue_hour_evening_start, info.golden_hour_evening_end);
+        assert!(info.blue_hour_morning_start < info.blue_hour_morning_end);
+        assert!(info.blue_hour_evening_start < info.blue_hour_evening_end);
+    }
 
-        // Проверяем синие часы
-        assert_eq!(
-            info.blue_hour_morning_end - info.blue_hour_morning_start,
-            chrono::Duration::minutes(30)
-        );
-        assert_eq!(
-            info.blue_hour_evening_end - info.blue_hour_evening_start,
-            chrono::Duration::minutes(30)
-        );
+    /// Высота Солнца в момент, заданный в местном времени
+    fn altitude_at(service: &GoldenHourService, time: DateTime<Tz>) -> f64 {
+        service.sun_position(time.with_timezone(&Utc)).altitude
     }
 
     #[test]
@@ -340,19 +915,27 @@ mod tests {
         let test_date = create_test_date();
         let info = service.calculate_golden_hours(test_date);
 
-        // Золотой час утром должен быть за 1 час до восхода и 1 час после
-        let expected_morning_start = info.sunrise - chrono::Duration::hours(1);
-        let expected_morning_end = info.sunrise + chrono::Duration::hours(1);
-
-        assert_eq!(info.golden_hour_morning_start, expected_morning_start);
-        assert_eq!(info.golden_hour_morning_end, expected_morning_end);
-
-        // Золотой час вечером должен быть за 1 час до заката и 1 час после
-        let expected_evening_start = info.sunset - chrono::Duration::hours(1);
-        let expected_evening_end = info.sunset + chrono::Duration::hours(1);
+        // Золотой час - Солнце между -4° и +6°
+        for (time, expected) in [
+            (info.golden_hour_morning_start, -4.0),
+            (info.golden_hour_morning_end, 6.0),
+            (info.golden_hour_evening_start, 6.0),
+            (info.golden_hour_evening_end, -4.0),
+        ] {
+            let altitude = altitude_at(&service, time);
+            assert!(
+                (altitude - expected).abs() < 0.1,
+                "{} -> {:.2}°",
+                time,
+                altitude
+            );
+        }
 
-        assert_eq!(info.golden_hour_evening_start, expected_evening_start);
-        assert_eq!(info.golden_hour_evening_end, expected_evening_end);
+        // Летом в Москве Солнце поднимается полого: золотой час дольше часа
+        assert!(
+            info.golden_hour_evening_end - info.golden_hour_evening_start
+                > chrono::Duration::hours(1)
+        );
     }
 
     #[test]
@@ -361,19 +944,82 @@ mod tests {
         let test_date = create_test_date();
         let info = service.calculate_golden_hours(test_date);
 
-        // Синий час утром должен быть за 30 минут до восхода
-        let expected_morning_start = info.sunrise - chrono::Duration::minutes(30);
-        let expected_morning_end = info.sunrise;
+        // Синий час - Солнце между -6° и -4°
+        for (time, expected) in [
+            (info.blue_hour_morning_start, -6.0),
+            (info.blue_hour_morning_end, -4.0),
+            (info.blue_hour_evening_start, -4.0),
+            (info.blue_hour_evening_end, -6.0),
+        ] {
+            let altitude = altitude_at(&service, time);
+            assert!(
+                (altitude - expected).abs() < 0.1,
+                "{} -> {:.2}°",
+                time,
+                altitude
+            );
+        }
+
+        // Синий час заканчивается до восхода и начинается после заката
+        assert!(info.blue_hour_morning_end < info.sunrise.unwrap());
+        assert!(info.blue_hour_evening_start > info.sunset.unwrap());
+    }
 
-        assert_eq!(info.blue_hour_morning_start, expected_morning_start);
-        assert_eq!(info.blue_hour_morning_end, expected_morning_end);
+    #[test]
+    fn test_custom_thresholds() {
+        let thresholds = LightThresholds {
+            golden_upper: 10.0,
+            golden_lower: -2.0,
+            blue_lower: -8.0,
+        };
+        let service = create_test_service().with_thresholds(thresholds);
+        let default_info = create_test_service().calculate_golden_hours(create_test_date());
+        let info = service.calculate_golden_hours(create_test_date());
+
+        // Более широкие границы - более длинные золотой и синий час
+        assert!(info.golden_hour_morning_end > default_info.golden_hour_morning_end);
+        assert!(info.golden_hour_evening_start < default_info.golden_hour_evening_start);
+        assert!(info.blue_hour_evening_end > default_info.blue_hour_evening_end);
+        assert!((altitude_at(&service, info.golden_hour_evening_start) - 10.0).abs() < 0.1);
+
+        assert!(thresholds.validate().is_ok());
+        assert!(LightThresholds::default().validate().is_ok());
+        let unordered = LightThresholds {
+            golden_lower: -7.0,
+            ..LightThresholds::default()
+        };
+        assert!(unordered.validate().is_err());
+        let too_high = LightThresholds {
+            golden_upper: 45.0,
+            ..LightThresholds::default()
+        };
+        assert!(too_high.validate().is_err());
+    }
 
-        // Синий час вечером должен быть за 30 минут после заката
-        let expected_evening_start = info.sunset;
-        let expected_evening_end = info.sunset + chrono::Duration::minutes(30);
+    #[test]
+    fn test_high_latitude_golden_hour() {
+        // Шпицберген, конец декабря: Солнце не поднимается выше -4°,
+        // золотого часа нет
+        let date = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+        let svalbard =
+            GoldenHourService::new(78.2232, 15.6267).calculate_golden_hours_for_date(date);
+        assert_eq!(
+            svalbard.golden_hour_morning_start,
+            svalbard.golden_hour_morning_end
+        );
 
-        assert_eq!(info.blue_hour_evening_start, expected_evening_start);
-        assert_eq!(info.blue_hour_evening_end, expected_evening_end);
+        // Мурманск в те же дни: Солнце весь день чуть ниже горизонта,
+        // золотой час длится от утра до вечера без перерыва
+        let murmansk =
+            GoldenHourService::new(68.9792, 33.0925).calculate_golden_hours_for_date(date);
+        assert_eq!(
+            murmansk.golden_hour_morning_end,
+            murmansk.golden_hour_evening_start
+        );
+        assert!(
+            murmansk.golden_hour_evening_end - murmansk.golden_hour_morning_start
+                > chrono::Duration::hours(2)
+        );
     }
 
     #[test]
@@ -383,7 +1029,7 @@ mod tests {
         let info = service.calculate_golden_hours(test_date);
 
         // Восход должен быть раньше заката
-        assert!(info.sunrise < info.sunset);
+        assert!(info.sunrise.unwrap() < info.sunset.unwrap());
 
         // Золотой час утром должен быть раньше золотого часа вечером
         assert!(info.golden_hour_morning_start < info.golden_hour_evening_start);
@@ -423,8 +1069,8 @@ mod tests {
         assert_eq!(evening_blue, "Синий час (вечер)");
 
         // Проверяем дневное и ночное время
-        let daytime =
-            service.get_current_lighting_condition(info.sunrise + chrono::Duration::hours(6));
+        let daytime = service
+            .get_current_lighting_condition(info.sunrise.unwrap() + chrono::Duration::hours(6));
         assert_eq!(daytime, "Дневное время");
 
         // Для ночного времени используем время до начала синего часа утром
@@ -432,6 +1078,26 @@ mod tests {
             info.blue_hour_morning_start - chrono::Duration::hours(1),
         );
         assert_eq!(nighttime, "Ночное время");
+
+        // Фаза освещения для произвольного момента
+        let phase = |time: DateTime<Tz>| service.lighting_phase(time.with_timezone(&Utc));
+        assert_eq!(
+            phase(info.golden_hour_evening_start + chrono::Duration::minutes(30)),
+            LightingPhase::GoldenHour
+        );
+        assert_eq!(
+            phase(info.blue_hour_evening_start + chrono::Duration::minutes(5)),
+            LightingPhase::BlueHour
+        );
+        assert_eq!(phase(test_date), LightingPhase::Day);
+
+        // Час, который захватывает начало синего часа, помечается синим часом
+        let hour_start =
+            (info.blue_hour_evening_end - chrono::Duration::minutes(10)).with_timezone(&Utc);
+        assert_eq!(
+            service.lighting_phase_between(hour_start, hour_start + chrono::Duration::hours(1)),
+            LightingPhase::BlueHour
+        );
     }
 
     #[test]
@@ -447,8 +1113,8 @@ mod tests {
         let winter_info = service.calculate_golden_hours(winter_date);
 
         // Летом день должен быть длиннее
-        let summer_day_length = summer_info.sunset - summer_info.sunrise;
-        let winter_day_length = winter_info.sunset - winter_info.sunrise;
+        let summer_day_length = summer_info.sunset.unwrap() - summer_info.sunrise.unwrap();
+        let winter_day_length = winter_info.sunset.unwrap() - winter_info.sunrise.unwrap();
 
         assert!(summer_day_length > winter_day_length);
     }
@@ -479,7 +1145,7 @@ mod tests {
         // Создаем время в золотой час вечером (не вложен в синий)
         let golden_evening_time = info.golden_hour_evening_start + chrono::Duration::minutes(30);
         // Создаем время вне золотого и синего часа
-        let non_golden_time = info.sunrise + chrono::Duration::hours(6);
+        let non_golden_time = info.sunrise.unwrap() + chrono::Duration::hours(6);
 
         // Проверяем определение синего и золотого часа
         let morning_condition = service.get_current_lighting_condition(blue_morning_time);
@@ -491,6 +1157,252 @@ mod tests {
         assert_eq!(non_golden_condition, "Дневное время");
     }
 
+    #[test]
+    fn test_sun_position_and_azimuths() {
+        let service = create_test_service();
+
+        // Солнце в полдень на юге, в полночь - на севере под горизонтом
+        let noon = service.sun_position(Utc.with_ymd_and_hms(2024, 6, 21, 9, 30, 0).unwrap());
+        assert!((noon.altitude - 57.7).abs() < 0.5, "{:?}", noon);
+        assert!((noon.azimuth - 180.0).abs() < 5.0, "{:?}", noon);
+        let midnight = service.sun_position(Utc.with_ymd_and_hms(2024, 6, 21, 21, 30, 0).unwrap());
+        assert!(midnight.altitude < 0.0);
+        assert!(
+            midnight.azimuth < 15.0 || midnight.azimuth > 345.0,
+            "{:?}",
+            midnight
+        );
+
+        // Летом Солнце восходит на северо-востоке и заходит на северо-западе,
+        // зимой - на юго-востоке и юго-западе
+        let summer =
+            service.calculate_golden_hours_for_date(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap());
+        let (sunrise, sunset) = (
+            summer.sunrise_azimuth.unwrap(),
+            summer.sunset_azimuth.unwrap(),
+        );
+        assert!((40.0..50.0).contains(&sunrise), "{}", sunrise);
+        assert!((310.0..320.0).contains(&sunset), "{}", sunset);
+
+        let winter =
+            service.calculate_golden_hours_for_date(NaiveDate::from_ymd_opt(2024, 12, 21).unwrap());
+        assert!((125.0..135.0).contains(&winter.sunrise_azimuth.unwrap()));
+        assert!((225.0..235.0).contains(&winter.sunset_azimuth.unwrap()));
+        // Точки восхода и заката симметричны относительно меридиана
+        assert!(
+            (winter.sunrise_azimuth.unwrap() + winter.sunset_azimuth.unwrap() - 360.0).abs() < 1.0
+        );
+
+        // В полярную ночь азимутов нет
+        let svalbard = GoldenHourService::new(78.2232, 15.6267)
+            .calculate_golden_hours_for_date(NaiveDate::from_ymd_opt(2024, 12, 21).unwrap());
+        assert_eq!(svalbard.sunrise_azimuth, None);
+    }
+
+    #[test]
+    fn test_polar_day_and_night() {
+        let svalbard = GoldenHourService::new(78.2232, 15.6267);
+
+        // Полярная ночь: ни восхода, ни заката, ни золотого часа
+        let night = svalbard
+            .calculate_golden_hours_for_date(NaiveDate::from_ymd_opt(2024, 12, 21).unwrap());
+        assert_eq!(night.daylight, Daylight::PolarNight);
+        assert_eq!(night.sunrise, None);
+        assert_eq!(night.sunset, None);
+        assert!(!night.has_golden_hour);
+        assert!(!night.is_golden_hour_at(night.golden_hour_morning_start));
+
+        // Полярный день: Солнце весь день выше +6°, золотого и синего часа нет
+        let day =
+            svalbard.calculate_golden_hours_for_date(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap());
+        assert_eq!(day.daylight, Daylight::PolarDay);
+        assert_eq!((day.sunrise, day.sunset), (None, None));
+        assert!(!day.has_golden_hour);
+        assert!(!day.has_blue_hour);
+
+        // Мурманск в начале полярного дня: Солнце не заходит, но ночью
+        // опускается к горизонту - золотой час есть
+        let murmansk = GoldenHourService::new(68.9792, 33.0925);
+        let june =
+            murmansk.calculate_golden_hours_for_date(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap());
+        assert_eq!(june.daylight, Daylight::PolarDay);
+        assert!(june.has_golden_hour);
+        assert!(!june.has_blue_hour);
+
+        // Москва: обычная смена дня и ночи
+        let moscow = create_test_service().calculate_golden_hours(create_test_date());
+        assert_eq!(moscow.daylight, Daylight::Normal);
+        assert!(moscow.has_golden_hour && moscow.has_blue_hour);
+    }
+
+    #[test]
+    fn test_polar_lighting_conditions() {
+        let svalbard = GoldenHourService::new(78.2232, 15.6267);
+        let noon = Utc.with_ymd_and_hms(2024, 12, 21, 11, 0, 0).unwrap();
+        assert_eq!(
+            svalbard.get_current_lighting_condition(noon),
+            "Ночное время"
+        );
+        let midnight = Utc.with_ymd_and_hms(2024, 6, 21, 23, 0, 0).unwrap();
+        assert_eq!(
+            svalbard.get_current_lighting_condition(midnight),
+            "Дневное время"
+        );
+    }
+
+    #[test]
+    fn test_twilight_phases_winter() {
+        let service = create_test_service();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let info = service.calculate_golden_hours_for_date(date);
+        let twilight = &info.twilight;
+
+        // Утром сумерки начинаются от самых темных к самым светлым
+        let astronomical_dawn = twilight.astronomical.dawn.unwrap();
+        let nautical_dawn = twilight.nautical.dawn.unwrap();
+        let civil_dawn = twilight.civil.dawn.unwrap();
+        assert!(astronomical_dawn < nautical_dawn);
+        assert!(nautical_dawn < civil_dawn);
+        assert!(civil_dawn < info.sunrise.unwrap());
+
+        // Вечером - в обратном порядке
+        let civil_dusk = twilight.civil.dusk.unwrap();
+        let nautical_dusk = twilight.nautical.dusk.unwrap();
+        let astronomical_dusk = twilight.astronomical.dusk.unwrap();
+        assert!(info.sunset.unwrap() < civil_dusk);
+        assert!(civil_dusk < nautical_dusk);
+        assert!(nautical_dusk < astronomical_dusk);
+
+        // Зимой в Москве каждая фаза сумерек длится 35-50 минут
+        let civil = (civil_dusk - info.sunset.unwrap()).num_minutes();
+        assert!((35..=50).contains(&civil), "civil twilight {} min", civil);
+
+        // Синий час и гражданские сумерки заканчиваются на одной высоте -6°
+        assert_eq!(info.blue_hour_evening_end, civil_dusk);
+        assert_eq!(info.blue_hour_morning_start, civil_dawn);
+
+        // Полная темнота - до астрономического рассвета следующего дня
+        let darkness = twilight.darkness.unwrap();
+        assert_eq!(darkness.start, astronomical_dusk);
+        assert_eq!(darkness.end.date_naive(), date.succ_opt().unwrap());
+        assert!(darkness.duration() > chrono::Duration::hours(11));
+        assert!(
+            darkness.contains((astronomical_dusk + chrono::Duration::hours(1)).with_timezone(&Utc))
+        );
+        assert!(!darkness.contains(civil_dusk.with_timezone(&Utc)));
+    }
+
+    #[test]
+    fn test_white_nights_have_no_darkness() {
+        // В июне в Москве Солнце опускается лишь на ~11° под горизонт
+        let service = create_test_service();
+        let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+        let twilight = service.calculate_twilight(date);
+
+        assert!(twilight.civil.dawn.is_some());
+        assert!(twilight.civil.dusk.is_some());
+        assert_eq!(twilight.nautical.dusk, None);
+        assert_eq!(twilight.astronomical.dawn, None);
+        assert_eq!(twilight.darkness, None);
+    }
+
+    #[test]
+    fn test_darkness_windows() {
+        let service = create_test_service();
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let windows = service.darkness_windows(start, start + chrono::Duration::hours(48));
+
+        // Ночь с 14 на 15, с 15 на 16 и с 16 на 17 января
+        assert_eq!(windows.len(), 3);
+        assert!(windows[0].contains(start));
+        assert!(windows.windows(2).all(|pair| pair[0].end < pair[1].start));
+
+        // Летом в Москве темноты нет
+        let summer = Utc.with_ymd_and_hms(2024, 6, 20, 0, 0, 0).unwrap();
+        assert!(service
+            .darkness_windows(summer, summer + chrono::Duration::hours(48))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_golden_hours_between() {
+        let service = create_test_service();
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let days = service.golden_hours_between(start, start + chrono::Duration::hours(36));
+
+        // 14, 15 и 16 января по московскому времени
+        assert_eq!(days.len(), 3);
+
+        // Час, в который начинается вечерний золотой час, пересекается с ним,
+        // полдень - нет
+        let evening = days[1].golden_hour_evening_start;
+        let hour_start = evening - chrono::Duration::minutes(30);
+        assert!(days[1].overlaps_golden_hour(hour_start, hour_start + chrono::Duration::hours(1)));
+        let noon = Moscow.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        assert!(!days[1].overlaps_golden_hour(noon, noon + chrono::Duration::hours(1)));
+    }
+
+    #[test]
+    fn test_elevation_and_horizon() {
+        let date = create_winter_date();
+        let flat = create_test_service().calculate_golden_hours(date);
+
+        // С высоты 2000 м горизонт опускается почти на 1.4°: восход
+        // раньше, закат позже
+        let peak = create_test_service()
+            .with_elevation(2000.0)
+            .calculate_golden_hours(date);
+        assert!(peak.sunrise.unwrap() < flat.sunrise.unwrap());
+        assert!(peak.sunset.unwrap() > flat.sunset.unwrap());
+
+        // Горы на юго-востоке закрывают восход, запад открыт
+        let valley = HorizonProfile::new(vec![(90.0, 0.0), (130.0, 5.0), (180.0, 0.0)]).unwrap();
+        let info = create_test_service()
+            .with_horizon(valley)
+            .calculate_golden_hours(date);
+        let sunrise_delay = info.sunrise.unwrap() - flat.sunrise.unwrap();
+        assert!(
+            sunrise_delay > chrono::Duration::minutes(30),
+            "{}",
+            sunrise_delay
+        );
+        assert!(
+            (info.sunset.unwrap() - flat.sunset.unwrap())
+                .num_minutes()
+                .abs()
+                <= 1
+        );
+        assert!(info.golden_hour_morning_start > flat.golden_hour_morning_start);
+    }
+
+    #[test]
+    fn test_daylight_follows_visible_horizon() {
+        let date = create_winter_date();
+
+        // Зимой в Москве Солнце поднимается на 11°: за стеной гор в 20°
+        // оно не показывается весь день
+        let walls = HorizonProfile::new(vec![(0.0, 20.0), (180.0, 20.0)]).unwrap();
+        let valley = create_test_service()
+            .with_horizon(walls)
+            .calculate_golden_hours(date);
+        assert_eq!(valley.daylight, Daylight::PolarNight);
+        assert!(valley.sunrise.is_none() && valley.sunset.is_none());
+
+        // В Тромсё 1 декабря Солнце в полдень на 1.5° ниже горизонта, но с
+        // высоты 2000 м горизонт опускается, и Солнце восходит
+        let tromso = GoldenHourService::new(69.6492, 18.9553);
+        let date = NaiveDate::from_ymd_opt(2024, 12, 1).unwrap();
+        assert_eq!(
+            tromso.calculate_golden_hours_for_date(date).daylight,
+            Daylight::PolarNight
+        );
+        let summit = tromso
+            .with_elevation(2000.0)
+            .calculate_golden_hours_for_date(date);
+        assert_eq!(summit.daylight, Daylight::Normal);
+        assert!(summit.sunrise.is_some() && summit.sunset.is_some());
+    }
+
     #[test]
     fn test_edge_cases() {
         let service = create_test_service();
@@ -500,8 +1412,8 @@ mod tests {
         let info = service.calculate_golden_hours(test_date);
 
         // Проверяем, что все времена находятся в разумных пределах
-        assert!((0..=23).contains(&info.sunrise.hour()));
-        assert!((0..=23).contains(&info.sunset.hour()));
+        assert!((0..=23).contains(&info.sunrise.unwrap().hour()));
+        assert!((0..=23).contains(&info.sunset.unwrap().hour()));
         assert!((0..=23).contains(&info.golden_hour_morning_start.hour()));
         assert!((0..=23).contains(&info.golden_hour_evening_start.hour()));
 
@@ -0,0 +1,198 @@
+//! # Horizon Module
+//!
+//! Профиль горизонта места съемки: высота линии гор, леса или застройки
+//! для каждого азимута. Солнце восходит не над математическим горизонтом,
+//! а над этой линией, поэтому в долине восход наступает позже, а закат
+//! раньше расчетного.
+//!
+//! Профиль загружается из CSV файла с парами `азимут,высота` в градусах
+//! (его можно снять, например, в PeakFinder или с помощью теодолита в
+//! смартфоне). Между точками высота интерполируется линейно.
+//!
+//! ## Основные компоненты
+//!
+//! - [`HorizonProfile`] - Высота горизонта по азимуту
+//!
+//! ## Пример файла
+//!
+//! ```text
+//! azimuth,altitude
+//! # Горы на востоке, открытый горизонт на западе
+//! 0,2.0
+//! 90,8.5
+//! 180,4.0
+//! 270,0.0
+//! ```
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::horizon::HorizonProfile;
+//!
+//! let profile = HorizonProfile::from_csv("0,2\n90,8\n180,4\n270,0").unwrap();
+//! assert_eq!(profile.altitude_at(45.0), 5.0);
+//! assert_eq!(profile.altitude_at(315.0), 1.0);
+//! ```
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// Высота горизонта по азимуту
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HorizonProfile {
+    /// Точки (азимут, высота) в градусах, отсортированные по азимуту
+    points: Vec<(f64, f64)>,
+}
+
+impl HorizonProfile {
+    /// Создает профиль из точек (азимут, высота) в градусах
+    ///
+    /// Азимуты приводятся к диапазону 0-360°, точки сортируются. Высота
+    /// должна быть от -10° до 90°.
+    pub fn new(points: Vec<(f64, f64)>) -> Result<Self> {
+        if points.is_empty() {
+            bail!("Профиль горизонта пуст");
+        }
+
+        let mut points: Vec<(f64, f64)> = points
+            .into_iter()
+            .map(|(azimuth, altitude)| {
+                if !azimuth.is_finite() || !(-10.0..=90.0).contains(&altitude) {
+                    bail!(
+                        "Некорректная точка профиля горизонта: азимут {}, высота {} (ожидается высота от -10° до 90°)",
+                        azimuth,
+                        altitude
+                    );
+                }
+                Ok((azimuth.rem_euclid(360.0), altitude))
+            })
+            .collect::<Result<_>>()?;
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points.dedup_by(|a, b| a.0 == b.0);
+
+        Ok(Self { points })
+    }
+
+    /// Разбирает CSV: строки `азимут,высота`
+    ///
+    /// Допускаются строка заголовка, пустые строки, комментарии после `#`
+    /// и разделитель `;`.
+    pub fn from_csv(content: &str) -> Result<Self> {
+        let mut points = Vec::new();
+
+        for (index, raw) in content.lines().enumerate() {
+            let line = raw.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split([',', ';']).map(str::trim).collect();
+            let parsed = match fields.as_slice() {
+                [azimuth, altitude] => azimuth
+                    .parse::<f64>()
+                    .ok()
+                    .zip(altitude.parse::<f64>().ok()),
+                _ => None,
+            };
+            match parsed {
+                Some(point) => points.push(point),
+                // Первая непустая строка может быть заголовком
+                None if points.is_empty() && fields.iter().all(|f| f.parse::<f64>().is_err()) => {}
+                None => bail!(
+                    "Строка {} профиля горизонта: ожидается 'азимут,высота', получено '{}'",
+                    index + 1,
+                    raw.trim()
+                ),
+            }
+        }
+
+        Self::new(points)
+    }
+
+    /// Загружает профиль из CSV файла
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).with_context(|| {
+            format!("Не удалось прочитать профиль горизонта {}", path.display())
+        })?;
+        Self::from_csv(&content)
+            .with_context(|| format!("Ошибка в профиле горизонта {}", path.display()))
+    }
+
+    /// Высота горизонта в градусах для азимута
+    ///
+    /// Между соседними точками (в том числе через север) высота
+    /// интерполируется линейно.
+    pub fn altitude_at(&self, azimuth: f64) -> f64 {
+        let azimuth = azimuth.rem_euclid(360.0);
+        let next = self.points.partition_point(|(point, _)| *point < azimuth);
+
+        // Соседние точки слева и справа с переходом через 0°/360°
+        let (left, right) = match next {
+            0 => (self.points[self.points.len() - 1], self.points[0]),
+            n if n == self.points.len() => (self.points[n - 1], self.points[0]),
+            n => (self.points[n - 1], self.points[n]),
+        };
+        if right.0 == azimuth {
+            return right.1;
+        }
+
+        let span = (right.0 - left.0).rem_euclid(360.0);
+        if span == 0.0 {
+            return left.1;
+        }
+        let offset = (azimuth - left.0).rem_euclid(360.0);
+        left.1 + (right.1 - left.1) * offset / span
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_csv() {
+        let profile = HorizonProfile::from_csv(
+            "azimuth;altitude\n# восток закрыт горой\n\n90; 10\n270, 0 # море\n-10,1\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            profile.points,
+            vec![(90.0, 10.0), (270.0, 0.0), (350.0, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let profile = HorizonProfile::new(vec![(90.0, 10.0), (270.0, 0.0)]).unwrap();
+
+        assert_eq!(profile.altitude_at(90.0), 10.0);
+        assert_eq!(profile.altitude_at(180.0), 5.0);
+        assert_eq!(profile.altitude_at(270.0), 0.0);
+        // Через север: от 270° к 90° (+360°)
+        assert_eq!(profile.altitude_at(0.0), 5.0);
+        assert_eq!(profile.altitude_at(-45.0), 2.5);
+
+        // Одна точка - горизонт одинаковой высоты
+        let flat = HorizonProfile::new(vec![(0.0, 3.0)]).unwrap();
+        assert_eq!(flat.altitude_at(123.0), 3.0);
+    }
+
+    #[test]
+    fn test_invalid_profile() {
+        assert!(HorizonProfile::from_csv("").is_err());
+        assert!(HorizonProfile::from_csv("azimuth,altitude\n").is_err());
+        assert!(HorizonProfile::from_csv("0,1\n90,высоко\n").is_err());
+        assert!(HorizonProfile::from_csv("0,95\n").is_err());
+        assert!(HorizonProfile::from_csv("0,1,2\n").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let path = std::env::temp_dir().join("my_dashboard_missing_horizon.csv");
+        let _ = std::fs::remove_file(&path);
+        assert!(HorizonProfile::load(&path).is_err());
+    }
+}
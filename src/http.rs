@@ -0,0 +1,382 @@
+//! # HTTP Module
+//!
+//! Общий HTTP клиент для всех внешних API (OpenWeatherMap, Open-Meteo, NOAA).
+//! Переиспользует соединения, ограничивает время запросов и повторяет
+//! запросы при временных ошибках.
+//!
+//! ## Основные компоненты
+//!
+//! - [`shared_client`] - Общий `reqwest::Client` с таймаутами
+//! - [`get_text`] - GET запрос с повторами и экспоненциальной задержкой
+//! - [`RetryPolicy`] - Настройки повторов
+//! - [`HttpError`] - Ошибка запроса (HTTP статус или сетевая ошибка)
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::http::RetryPolicy;
+//!
+//! let policy = RetryPolicy::default();
+//! assert_eq!(policy.max_attempts, 3);
+//!
+//! // Для асинхронного использования:
+//! // let body = my_dashboard::http::get_text("https://example.com").await?;
+//! ```
+
+use log::{debug, warn};
+use reqwest::StatusCode;
+use std::fmt;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Таймаут установки соединения
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Таймаут всего запроса (включая чтение ответа)
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Наибольшая пауза из `Retry-After`, которую стоит подождать перед повтором
+///
+/// Если сервер просит ждать дольше, запрос не повторяется: вызывающий код
+/// берет данные из кэша.
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+/// Общий HTTP клиент процесса
+///
+/// Создается при первом обращении; все запросы используют один пул соединений.
+pub fn shared_client() -> &'static reqwest::Client {
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("my_dashboard/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_else(|e| {
+                warn!(
+                    "Не удалось настроить HTTP клиент ({}), используем настройки по умолчанию",
+                    e
+                );
+                reqwest::Client::new()
+            })
+    })
+}
+
+/// Настройки повторов запроса
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Максимальное число попыток (включая первую)
+    pub max_attempts: u32,
+    /// Задержка перед первым повтором; каждая следующая вдвое больше
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Задержка перед повтором номер `retry` (начиная с 1)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff * 2u32.saturating_pow(retry.saturating_sub(1))
+    }
+}
+
+/// Ошибка HTTP запроса
+#[derive(Debug)]
+pub enum HttpError {
+    /// Сервер ответил неуспешным статусом
+    Status {
+        status: StatusCode,
+        body: String,
+        /// Пауза из заголовка `Retry-After`, если сервер ее указал
+        retry_after: Option<Duration>,
+    },
+    /// Сетевая ошибка или таймаут (без URL: в нем может быть API ключ)
+    Network(reqwest::Error),
+}
+
+impl HttpError {
+    /// Имеет ли смысл повторить запрос
+    ///
+    /// 429 повторяется, только если сервер указал в `Retry-After` паузу не
+    /// длиннее [`MAX_RETRY_AFTER`]: иначе повтор лишь тратит лимит запросов.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            HttpError::Status {
+                status: StatusCode::TOO_MANY_REQUESTS,
+                retry_after,
+                ..
+            } => retry_after.is_some_and(|delay| delay <= MAX_RETRY_AFTER),
+            HttpError::Status { status, .. } => status.is_server_error(),
+            HttpError::Network(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+        }
+    }
+
+    /// Пауза перед повтором, которую указал сервер
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            HttpError::Status { retry_after, .. } => *retry_after,
+            HttpError::Network(_) => None,
+        }
+    }
+
+    /// HTTP статус ответа, если сервер ответил
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            HttpError::Status { status, .. } => Some(*status),
+            HttpError::Network(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::Status { status, body, .. } if body.is_empty() => {
+                write!(f, "HTTP {}", status)
+            }
+            HttpError::Status { status, body, .. } => write!(f, "HTTP {}: {}", status, body),
+            HttpError::Network(e) if e.is_timeout() => write!(f, "превышено время ожидания ответа"),
+            HttpError::Network(e) => write!(f, "сетевая ошибка: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for HttpError {}
+
+/// Выполняет GET запрос через общий клиент и возвращает тело ответа
+///
+/// Временные ошибки (таймауты, сбои соединения, 5xx) повторяются
+/// по [`RetryPolicy::default`], 429 - только после паузы из `Retry-After`.
+pub async fn get_text(url: &str) -> Result<String, HttpError> {
+    retry_with_backoff(RetryPolicy::default(), || fetch_once(url)).await
+}
+
+async fn fetch_once(url: &str) -> Result<String, HttpError> {
+    let response = shared_client()
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| HttpError::Network(e.without_url()))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        let body = response.text().await.unwrap_or_default();
+        return Err(HttpError::Status {
+            status,
+            body,
+            retry_after,
+        });
+    }
+
+    response
+        .text()
+        .await
+        .map_err(|e| HttpError::Network(e.without_url()))
+}
+
+/// Пауза из заголовка `Retry-After`: число секунд или HTTP дата
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let time = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    // Дата в прошлом означает, что ждать уже не нужно
+    Some(
+        (time.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// Повторяет операцию с экспоненциальной задержкой, пока ошибка временная
+///
+/// Если сервер указал паузу в `Retry-After`, ждем ее вместо экспоненциальной.
+pub async fn retry_with_backoff<T, F, Fut>(
+    policy: RetryPolicy,
+    mut operation: F,
+) -> Result<T, HttpError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, HttpError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_retryable() && attempt < policy.max_attempts => {
+                let delay = e.retry_after().unwrap_or_else(|| policy.backoff(attempt));
+                debug!(
+                    "Попытка {}/{} не удалась ({}), повтор через {} мс",
+                    attempt,
+                    policy.max_attempts,
+                    e,
+                    delay.as_millis()
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::cell::Cell;
+
+    fn status_error(code: u16) -> HttpError {
+        HttpError::Status {
+            status: StatusCode::from_u16(code).unwrap(),
+            body: String::new(),
+            retry_after: None,
+        }
+    }
+
+    fn rate_limited(retry_after: Option<Duration>) -> HttpError {
+        HttpError::Status {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            body: String::new(),
+            retry_after,
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(2), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(3), Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(status_error(503).is_retryable());
+        // 429 повторяем только по короткому Retry-After
+        assert!(!rate_limited(None).is_retryable());
+        assert!(rate_limited(Some(Duration::from_secs(2))).is_retryable());
+        assert!(!rate_limited(Some(Duration::from_secs(3600))).is_retryable());
+        assert!(!status_error(401).is_retryable());
+        assert!(!status_error(404).is_retryable());
+        assert_eq!(status_error(401).to_string(), "HTTP 401 Unauthorized");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_until_success() {
+        let attempts = Cell::new(0);
+        let result = retry_with_backoff(RetryPolicy::default(), || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                if attempt < 3 {
+                    Err(status_error(503))
+                } else {
+                    Ok("ok")
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_gives_up() {
+        let attempts = Cell::new(0);
+        let result: Result<(), HttpError> = retry_with_backoff(RetryPolicy::default(), || {
+            attempts.set(attempts.get() + 1);
+            async { Err(status_error(503)) }
+        })
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().status(),
+            Some(StatusCode::SERVICE_UNAVAILABLE)
+        );
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit_without_retry_after_is_not_retried() {
+        let attempts = Cell::new(0);
+        let result: Result<(), HttpError> = retry_with_backoff(RetryPolicy::default(), || {
+            attempts.set(attempts.get() + 1);
+            async { Err(rate_limited(None)) }
+        })
+        .await;
+
+        assert_eq!(
+            result.unwrap_err().status(),
+            Some(StatusCode::TOO_MANY_REQUESTS)
+        );
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limit_waits_retry_after() {
+        let attempts = Cell::new(0);
+        let started = tokio::time::Instant::now();
+        let result = retry_with_backoff(RetryPolicy::default(), || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                if attempt == 1 {
+                    Err(rate_limited(Some(Duration::from_secs(5))))
+                } else {
+                    Ok("ok")
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(started.elapsed(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("скоро"), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_no_retry_on_client_error() {
+        let attempts = Cell::new(0);
+        let result: Result<(), HttpError> = retry_with_backoff(RetryPolicy::default(), || {
+            attempts.set(attempts.get() + 1);
+            async { Err(status_error(401)) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[test]
+    fn test_shared_client_is_reused() {
+        let first = shared_client() as *const reqwest::Client;
+        let second = shared_client() as *const reqwest::Client;
+        assert_eq!(first, second);
+    }
+}
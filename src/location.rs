@@ -0,0 +1,338 @@
+//! # Location Module
+//!
+//! Место съемки: координаты, высота над уровнем моря, часовой пояс,
+//! предпочтительное направление на горизонт и профиль горизонта. Все расчеты
+//! дашборда выполняются для одного разрешенного [`Location`] - заданного в
+//! командной строке или выбранного из сохраненных локаций файла конфигурации.
+//!
+//! Время восхода, заката и часы прогноза выводятся в часовом поясе места
+//! съемки, а не компьютера: поездка в Тромсё планируется по норвежскому
+//! времени. Если пояс не задан, он определяется по координатам (см.
+//! [`timezone_at`]).
+//!
+//! ## Основные компоненты
+//!
+//! - [`Location`] - Место съемки
+//! - [`parse_direction`] - Разбор направления (румб или азимут в градусах)
+//! - [`compass_point`] - Румб для азимута
+//! - [`timezone_at`] - Часовой пояс IANA по координатам
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::location::{parse_direction, Location};
+//!
+//! let location = Location::new("Воробьевы горы", 55.7105, 37.5532)
+//!     .with_elevation(200.0)
+//!     .with_horizon_direction(parse_direction("СВ").unwrap());
+//!
+//! assert_eq!(location.horizon_direction, Some(45.0));
+//! assert!(location.validate().is_ok());
+//!
+//! // Часовой пояс задается явно или определяется по координатам
+//! let tromso = Location::new("Тромсё", 69.6496, 18.9560).with_timezone(chrono_tz::Europe::Oslo);
+//! assert_eq!(tromso.timezone(), chrono_tz::Europe::Oslo);
+//! assert_eq!(location.timezone(), chrono_tz::Europe::Moscow);
+//! ```
+
+use crate::validate_coordinates;
+use anyhow::{bail, Result};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tzf_rs::DefaultFinder;
+
+/// Румбы по часовой стрелке от севера с шагом 22.5°
+const COMPASS_POINTS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+/// Румбы на русском языке в том же порядке
+const COMPASS_POINTS_RU: [&str; 16] = [
+    "С", "ССВ", "СВ", "ВСВ", "В", "ВЮВ", "ЮВ", "ЮЮВ", "Ю", "ЮЮЗ", "ЮЗ", "ЗЮЗ", "З", "ЗСЗ", "СЗ",
+    "ССЗ",
+];
+
+/// Место съемки
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Location {
+    /// Название места (для отображения и поиска в конфигурации)
+    pub name: String,
+    /// Широта в градусах (от -90 до 90)
+    pub latitude: f64,
+    /// Долгота в градусах (от -180 до 180)
+    pub longitude: f64,
+    /// Высота над уровнем моря в метрах
+    #[serde(default)]
+    pub elevation: f64,
+    /// Предпочтительное направление на горизонт: азимут в градусах (0 - север, 90 - восток)
+    ///
+    /// В конфигурации задается числом или румбом (`"W"`, `"СЗ"`).
+    #[serde(default, alias = "horizon", deserialize_with = "deserialize_direction")]
+    pub horizon_direction: Option<f64>,
+    /// CSV файл с профилем горизонта (см. [`HorizonProfile`](crate::horizon::HorizonProfile))
+    ///
+    /// Относительный путь в конфигурации отсчитывается от каталога файла
+    /// конфигурации.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub horizon_profile: Option<PathBuf>,
+    /// Часовой пояс IANA (`"Europe/Oslo"`), в котором выводится время
+    ///
+    /// `None` - пояс определяется по координатам, см. [`Location::timezone`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<Tz>,
+}
+
+impl Location {
+    /// Создает место съемки на уровне моря без предпочтительного направления
+    pub fn new(name: impl Into<String>, latitude: f64, longitude: f64) -> Self {
+        Self {
+            name: name.into(),
+            latitude,
+            longitude,
+            elevation: 0.0,
+            horizon_direction: None,
+            horizon_profile: None,
+            timezone: None,
+        }
+    }
+
+    /// Задает высоту над уровнем моря в метрах
+    pub fn with_elevation(mut self, elevation: f64) -> Self {
+        self.elevation = elevation;
+        self
+    }
+
+    /// Задает CSV файл с профилем горизонта
+    pub fn with_horizon_profile(mut self, path: impl Into<PathBuf>) -> Self {
+        self.horizon_profile = Some(path.into());
+        self
+    }
+
+    /// Задает часовой пояс места съемки
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    /// Часовой пояс места съемки
+    ///
+    /// Заданный в конфигурации или командной строке, иначе - пояс по
+    /// координатам из [`timezone_at`].
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+            .unwrap_or_else(|| timezone_at(self.latitude, self.longitude))
+    }
+
+    /// Задает предпочтительное направление на горизонт (азимут в градусах)
+    pub fn with_horizon_direction(mut self, azimuth: f64) -> Self {
+        self.horizon_direction = Some(azimuth.rem_euclid(360.0));
+        self
+    }
+
+    /// Проверяет координаты и высоту
+    pub fn validate(&self) -> Result<()> {
+        if !validate_coordinates(self.latitude, self.longitude) {
+            bail!(
+                "Некорректные координаты локации '{}': lat={}, lon={} (широта от -90 до 90, долгота от -180 до 180)",
+                self.name,
+                self.latitude,
+                self.longitude
+            );
+        }
+        if !(-500.0..=9000.0).contains(&self.elevation) {
+            bail!(
+                "Некорректная высота локации '{}': {} м (ожидается от -500 до 9000)",
+                self.name,
+                self.elevation
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Разбирает направление: азимут в градусах (`"270"`) или румб (`"W"`, `"ЗСЗ"`)
+pub fn parse_direction(value: &str) -> Result<f64> {
+    let value = value.trim();
+    if let Ok(azimuth) = value.parse::<f64>() {
+        if !azimuth.is_finite() {
+            bail!("Некорректный азимут: {}", value);
+        }
+        return Ok(azimuth.rem_euclid(360.0));
+    }
+
+    let upper = value.to_uppercase();
+    COMPASS_POINTS
+        .iter()
+        .position(|point| *point == upper)
+        .or_else(|| COMPASS_POINTS_RU.iter().position(|point| *point == upper))
+        .map(|index| index as f64 * 22.5)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Неизвестное направление '{}': ожидается азимут в градусах или румб (N, NE, СЗ...)",
+                value
+            )
+        })
+}
+
+/// Ближайший румб на русском языке для азимута в градусах (`"СВ"`, `"ЮЗ"`)
+pub fn compass_point(azimuth: f64) -> &'static str {
+    let index = (azimuth.rem_euclid(360.0) / 22.5).round() as usize % COMPASS_POINTS_RU.len();
+    COMPASS_POINTS_RU[index]
+}
+
+/// Часовой пояс IANA по координатам
+///
+/// Пояс ищется по границам часовых поясов (tzf-rs), поэтому учитывает
+/// границы стран и летнее время: Санкт-Петербург получает `Europe/Moscow`.
+/// В открытом море это морской пояс `Etc/GMT±N`; если пояс не найден,
+/// он определяется по долготе.
+pub fn timezone_at(latitude: f64, longitude: f64) -> Tz {
+    // Границы поясов разворачиваются при первом обращении, один раз на процесс
+    static FINDER: OnceLock<DefaultFinder> = OnceLock::new();
+    FINDER
+        .get_or_init(DefaultFinder::new)
+        .get_tz_name(longitude, latitude)
+        .parse()
+        .unwrap_or_else(|_| timezone_from_longitude(longitude))
+}
+
+/// Морской пояс по долготе: ближайший пояс `Etc/GMT±N` с шагом 15°
+fn timezone_from_longitude(longitude: f64) -> Tz {
+    let offset = (longitude / 15.0).round().clamp(-12.0, 12.0) as i32;
+    // В именах Etc/GMT знак обратный: Etc/GMT-3 - это UTC+3
+    let name = match offset {
+        0 => "Etc/GMT".to_string(),
+        _ => format!("Etc/GMT{:+}", -offset),
+    };
+    name.parse().unwrap_or(Tz::UTC)
+}
+
+fn deserialize_direction<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawDirection {
+        Degrees(f64),
+        Named(String),
+    }
+
+    match Option::<RawDirection>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(RawDirection::Degrees(azimuth)) => Ok(Some(azimuth.rem_euclid(360.0))),
+        Some(RawDirection::Named(name)) => parse_direction(&name)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_direction() {
+        assert_eq!(parse_direction("N").unwrap(), 0.0);
+        assert_eq!(parse_direction("wsw").unwrap(), 247.5);
+        assert_eq!(parse_direction("СЗ").unwrap(), 315.0);
+        assert_eq!(parse_direction("270").unwrap(), 270.0);
+        assert_eq!(parse_direction("-90").unwrap(), 270.0);
+        assert!(parse_direction("вверх").is_err());
+    }
+
+    #[test]
+    fn test_compass_point() {
+        assert_eq!(compass_point(0.0), "С");
+        assert_eq!(compass_point(46.0), "СВ");
+        assert_eq!(compass_point(128.0), "ЮВ");
+        assert_eq!(compass_point(350.0), "С");
+        assert_eq!(compass_point(-90.0), "З");
+        for point in COMPASS_POINTS_RU {
+            assert_eq!(compass_point(parse_direction(point).unwrap()), point);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_location() {
+        let location: Location = serde_json::from_str(
+            r#"{"name": "Маяк", "latitude": 69.2, "longitude": 35.1, "elevation": 40, "horizon": "NE"}"#,
+        )
+        .unwrap();
+        assert_eq!(location.elevation, 40.0);
+        assert_eq!(location.horizon_direction, Some(45.0));
+
+        let location: Location = serde_json::from_str(
+            r#"{"name": "Поле", "latitude": 55.0, "longitude": 37.0, "horizon_direction": 200}"#,
+        )
+        .unwrap();
+        assert_eq!(location.horizon_direction, Some(200.0));
+
+        let location: Location =
+            serde_json::from_str(r#"{"name": "Дом", "latitude": 55.0, "longitude": 37.0}"#)
+                .unwrap();
+        assert_eq!(location, Location::new("Дом", 55.0, 37.0));
+    }
+
+    #[test]
+    fn test_timezone_at() {
+        use chrono::{Offset, TimeZone, Utc};
+
+        // Санкт-Петербург живет по московскому времени, UTC+3, а не UTC+2 по долготе
+        let spb = timezone_at(59.9311, 30.3609);
+        assert_eq!(spb, chrono_tz::Europe::Moscow);
+        let summer = Utc.with_ymd_and_hms(2024, 6, 21, 12, 0, 0).unwrap();
+        assert_eq!(
+            summer.with_timezone(&spb).offset().fix().local_minus_utc(),
+            3 * 3600
+        );
+
+        // Летнее время: в Осло летом UTC+2, зимой UTC+1
+        let oslo = timezone_at(59.91, 10.75);
+        assert_eq!(oslo, chrono_tz::Europe::Oslo);
+        assert_eq!(
+            summer.with_timezone(&oslo).offset().fix().local_minus_utc(),
+            2 * 3600
+        );
+
+        // В открытом море - морской пояс
+        assert!(timezone_at(0.0, -30.0).name().starts_with("Etc/GMT"));
+    }
+
+    #[test]
+    fn test_timezone() {
+        assert_eq!(timezone_from_longitude(37.6).name(), "Etc/GMT-3");
+        assert_eq!(timezone_from_longitude(-74.0).name(), "Etc/GMT+5");
+        assert_eq!(timezone_from_longitude(5.0).name(), "Etc/GMT");
+        assert_eq!(timezone_from_longitude(180.0).name(), "Etc/GMT-12");
+
+        let location: Location = serde_json::from_str(
+            r#"{"name": "Тромсё", "latitude": 69.65, "longitude": 18.96, "timezone": "Europe/Oslo"}"#,
+        )
+        .unwrap();
+        assert_eq!(location.timezone(), chrono_tz::Europe::Oslo);
+        assert_eq!(
+            Location::new("Тромсё", 69.65, 18.96).timezone(),
+            chrono_tz::Europe::Oslo
+        );
+        assert!(serde_json::from_str::<Location>(
+            r#"{"name": "Марс", "latitude": 0, "longitude": 0, "timezone": "Mars/Olympus"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Location::new("Москва", 55.7558, 37.6176).validate().is_ok());
+        assert!(Location::new("Ошибка", 95.0, 37.6176).validate().is_err());
+        assert!(Location::new("Эверест", 27.99, 86.93)
+            .with_elevation(12000.0)
+            .validate()
+            .is_err());
+    }
+}
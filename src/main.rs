@@ -1,227 +1,96 @@
-use colored::*;
-use log::{debug, error, info};
-use my_dashboard::{
-    generate_dashboard_output, load_environment_variables, validate_coordinates, DashboardOutput,
-};
+use anyhow::Context;
+use chrono_tz::Tz;
+use clap::Parser;
+use log::{debug, info};
+use my_dashboard::cache::set_cache_mode;
+use my_dashboard::cli::Cli;
+use my_dashboard::compare::compare_locations;
+use my_dashboard::render::{render, render_comparison};
+use my_dashboard::{enable_demo_mode, generate_dashboard_output, set_time_override};
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
-    // Инициализация логирования
-    env_logger::init();
-
-    // Загружаем переменные окружения из файла .env
+    // Загружаем переменные окружения из файла .env до разбора аргументов:
+    // они служат значениями по умолчанию для флагов
     dotenv::dotenv().ok();
 
-    info!("🚀 Запуск дашборда для фотографов...");
-
-    // Загружаем и валидируем параметры
-    let (api_key, city, latitude, longitude) = load_environment_variables();
+    // Некорректные аргументы (например, координаты вне диапазона)
+    // завершают программу с описанием ошибки
+    let cli = Cli::parse();
 
-    if !validate_coordinates(latitude, longitude) {
-        error!(
-            "Некорректные координаты: lat={}, lon={}",
-            latitude, longitude
-        );
-        return Ok(());
+    // Инициализация логирования: -v/-q имеют приоритет над RUST_LOG
+    match cli.log_level() {
+        Some(level) => env_logger::Builder::new().filter_level(level).init(),
+        None => env_logger::init(),
     }
 
-    debug!(
-        "Параметры: город={}, широта={}, долгота={}",
-        city, latitude, longitude
-    );
-
-    // Генерируем весь дашборд
-    let dashboard_output = match generate_dashboard_output(api_key, city, latitude, longitude).await
-    {
-        Ok(output) => output,
-        Err(e) => {
-            error!("Ошибка генерации дашборда: {}", e);
-            return Ok(());
-        }
-    };
-
-    // Выводим результаты
-    print_dashboard_output(&dashboard_output);
-
-    info!("Дашборд завершен успешно");
-    Ok(())
-}
-
-fn print_dashboard_output(output: &DashboardOutput) {
-    // Выводим основную сводку дашборда
-    print_dashboard_summary(&output.summary);
-
-    println!("\n{}", "📊 ДЕТАЛЬНАЯ ИНФОРМАЦИЯ".bold().cyan());
-
-    // Выводим данные погоды
-    println!("{}", output.weather_output.current_weather);
-    print!("{}  ", output.weather_output.temperature_range);
-    print!("{}  ", output.weather_output.best_hours);
-    println!("| ⭐ Оценка: {:.1}/10", output.weather_output.overall_score);
-
-    if !output.weather_output.recommendation.is_empty() {
-        print!("{}", output.weather_output.recommendation);
-    }
+    info!("🚀 Запуск дашборда для фотографов...");
 
-    if !output.weather_output.concerns.is_empty() {
-        print!(" | {}", output.weather_output.concerns);
+    if cli.demo {
+        enable_demo_mode();
     }
-    println!();
-
-    // Выводим анализ астрофотографии
-    print!(
-        "🌌 Астрофото: {} | ☁️{:.0}% | ",
-        if output.astrophotography_output.is_suitable {
-            "✅"
-        } else {
-            "❌"
-        },
-        output.astrophotography_output.avg_cloud_cover
-    );
 
-    if !output.astrophotography_output.best_hours.is_empty() {
-        print!("{} ", output.astrophotography_output.best_hours);
-    }
+    // --no-cache / --refresh управляют дисковым кэшем ответов API
+    let cache_mode = cli.cache_mode();
+    debug!("Режим кэша: {:?}", cache_mode);
+    set_cache_mode(cache_mode);
 
-    if !output.astrophotography_output.recommendation.is_empty() {
-        print!("| {}", output.astrophotography_output.recommendation);
-    }
-    println!();
+    // Конфигурация и место съемки: --location, координаты или локация по умолчанию
+    let config = cli.load_config()?;
 
-    // Выводим солнечные данные
-    println!("{}", output.solar_output.solar_wind);
-    println!("{}", output.solar_output.geomagnetic);
-    println!("{}", output.solar_output.aurora_forecast);
-    if !output.solar_output.best_viewing_hours.is_empty() {
-        println!("   {}", output.solar_output.best_viewing_hours);
+    // --compare: рейтинг нескольких сохраненных локаций вместо одного дашборда
+    if let Some(locations) = cli.resolve_comparison(&config)? {
+        // Локации сравниваются в один момент: время из --at без смещения
+        // читается по часам первой локации
+        apply_requested_time(&cli, locations[0].timezone())?;
+        let comparison = compare_locations(&config, &locations).await;
+        print!("{}", render_comparison(&comparison, cli.format)?);
+        info!("Сравнение локаций завершено");
+        return Ok(());
     }
 
-    // Выводим информацию о золотом часе
-    println!("{}", output.golden_hour_output.sunrise_sunset);
-    println!("{}", output.golden_hour_output.golden_hours);
-    println!("{}", output.golden_hour_output.blue_hours);
-    println!(
-        "💡 Текущие условия освещения: {}",
-        output.golden_hour_output.current_condition
+    let location = cli.resolve_location(&config).await?;
+    debug!(
+        "Параметры: место={}, широта={}, долгота={}, высота={} м, пояс={}",
+        location.name,
+        location.latitude,
+        location.longitude,
+        location.elevation,
+        location.timezone()
     );
+    apply_requested_time(&cli, location.timezone())?;
 
-    println!("\n{}", "=== СОВЕТЫ ДЛЯ ФОТОГРАФОВ ===".bold().green());
-
-    // Выводим персонализированные советы
-    print_personalized_tips(&output.tips_output);
-}
+    // Генерируем весь дашборд
+    let dashboard_output = generate_dashboard_output(&config, &location)
+        .await
+        .context("Ошибка генерации дашборда")?;
 
-fn print_dashboard_summary(summary: &my_dashboard::dashboard::DashboardSummary) {
-    println!("\n{}", "=== ФОТОГРАФИЧЕСКИЙ ДАШБОРД ===".bold().white());
-    println!("{}", "📊 ОБЩАЯ ОЦЕНКА".bold().cyan());
-    println!("   Погода: {:.1}/10", summary.weather_score);
-    println!(
-        "   Вероятность северных сияний: {:.0}%",
-        summary.aurora_probability * 100.0
-    );
-    println!(
-        "   Золотой час: {}",
-        if summary.is_golden_hour_today {
-            "Да"
-        } else {
-            "Нет"
-        }
+    // Выводим результаты
+    print!(
+        "{}",
+        render(&dashboard_output, cli.format, &cli.sections, config.units)?
     );
 
-    if !summary.best_shooting_hours.is_empty() {
-        // Сжимаем часы до интервалов
-        let mut intervals = Vec::new();
-        let mut start = summary.best_shooting_hours[0];
-        let mut end = start;
-
-        for &hour in &summary.best_shooting_hours[1..] {
-            if hour == end + 1 {
-                end = hour;
-            } else {
-                if start == end {
-                    intervals.push(format!("{:02}:00", start));
-                } else {
-                    intervals.push(format!("{:02}:00-{:02}:00", start, end));
-                }
-                start = hour;
-                end = hour;
-            }
-        }
-        // Добавляем последний интервал
-        if start == end {
-            intervals.push(format!("{:02}:00", start));
-        } else {
-            intervals.push(format!("{:02}:00-{:02}:00", start, end));
-        }
-
-        println!("   Лучшие часы: {}", intervals.join(", "));
-    }
-
-    if !summary.key_highlights.is_empty() {
-        println!("{}", "✨ КЛЮЧЕВЫЕ МОМЕНТЫ".bold().green());
-        for highlight in &summary.key_highlights {
-            println!("   • {}", highlight);
-        }
-    }
-
-    if !summary.warnings.is_empty() {
-        println!("{}", "⚠️ ПРЕДУПРЕЖДЕНИЯ".bold().yellow());
-        for warning in &summary.warnings {
-            println!("   • {}", warning);
-        }
-    }
-
-    println!("{}", "🎯 РЕКОМЕНДАЦИЯ".bold().blue());
-    println!("   {}", summary.overall_recommendation);
+    info!("Дашборд завершен успешно");
+    Ok(())
 }
 
-fn print_personalized_tips(tips_output: &my_dashboard::PhotographyTipsOutput) {
-    if !tips_output.equipment_recommendations.is_empty() {
-        println!("\n📷 РЕКОМЕНДАЦИИ ПО ОБОРУДОВАНИЮ:");
-        for (i, tip) in tips_output.equipment_recommendations.iter().enumerate() {
-            println!("{}. {}", i + 1, tip);
-        }
-    }
-
-    if !tips_output.shooting_tips.is_empty() {
-        println!("\n🎯 СОВЕТЫ ПО СЪЕМКЕ:");
-        for (i, tip) in tips_output.shooting_tips.iter().enumerate() {
-            println!("{}. {}", i + 1, tip);
-        }
-    }
-
-    if !tips_output.location_suggestions.is_empty() {
-        println!("\n📍 РЕКОМЕНДАЦИИ ПО ЛОКАЦИЯМ:");
-        for (i, tip) in tips_output.location_suggestions.iter().enumerate() {
-            println!("{}. {}", i + 1, tip);
-        }
-    }
-
-    if !tips_output.technical_settings.is_empty() {
-        println!("\n⚙️ ТЕХНИЧЕСКИЕ НАСТРОЙКИ:");
-        for (i, tip) in tips_output.technical_settings.iter().enumerate() {
-            println!("{}. {}", i + 1, tip);
-        }
-    }
-
-    println!("\n{}", "=== ОБЩИЕ РЕКОМЕНДАЦИИ ===".bold().blue());
-    for (i, tip) in tips_output.general_recommendations.iter().enumerate() {
-        println!("{}. {}", i + 1, tip);
+/// Задает момент из `--at`; время без смещения - по часам места съемки
+fn apply_requested_time(cli: &Cli, timezone: Tz) -> Result<(), anyhow::Error> {
+    if let Some(requested) = cli.at {
+        let time = requested.resolve(timezone)?;
+        info!(
+            "Дашборд строится на {}",
+            time.with_timezone(&timezone).format("%d.%m.%Y %H:%M %Z")
+        );
+        set_time_override(time);
     }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
-    #[test]
-    fn test_load_environment_variables() {
-        let (api_key, city, latitude, longitude) = load_environment_variables();
-
-        assert!(!api_key.is_empty());
-        assert!(!city.is_empty());
-        assert!(validate_coordinates(latitude, longitude));
-    }
+    use my_dashboard::validate_coordinates;
 
     #[test]
     fn test_validate_coordinates() {
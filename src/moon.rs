@@ -0,0 +1,566 @@
+//! # Moon Module
+//!
+//! Модуль для расчета положения и фазы Луны.
+//! Яркая Луна засвечивает небо: при полнолунии Млечный Путь и слабые
+//! северные сияния почти не видны, поэтому фаза, освещенность и время
+//! восхода и захода Луны учитываются при анализе астрофотографии.
+//!
+//! Положение Луны рассчитывается по упрощенной теории из "Astronomical
+//! Algorithms" Ж. Меёса (основные члены ряда), точность - несколько угловых
+//! минут, чего достаточно для планирования съемки.
+//!
+//! ## Основные компоненты
+//!
+//! - [`MoonService`] - Сервис для расчета Луны в заданной точке
+//! - [`MoonInfo`] - Фаза, освещенность, восход и заход Луны
+//! - [`MoonPhase`] - Фаза Луны
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use chrono::{TimeZone, Utc};
+//! use my_dashboard::moon::{MoonPhase, MoonService};
+//!
+//! // Полнолуние 25 января 2024 года
+//! let service = MoonService::new(55.7558, 37.6176);
+//! let position = service.position(Utc.with_ymd_and_hms(2024, 1, 25, 18, 0, 0).unwrap());
+//!
+//! assert_eq!(position.phase, MoonPhase::FullMoon);
+//! assert!(position.illumination > 0.99);
+//! ```
+
+use crate::location::timezone_at;
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+/// Освещенность, начиная с которой Луна заметно засвечивает небо (0-1)
+pub const BRIGHT_MOON_ILLUMINATION: f64 = 0.3;
+
+/// Освещенность, ниже которой Луна практически не мешает съемке (0-1)
+const DARK_MOON_ILLUMINATION: f64 = 0.1;
+
+/// Средняя продолжительность синодического месяца в сутках
+const SYNODIC_MONTH: f64 = 29.530_589;
+
+/// Среднее расстояние до Солнца в км
+const SUN_DISTANCE_KM: f64 = 149_597_870.7;
+
+/// Экваториальный радиус Земли в км
+const EARTH_RADIUS_KM: f64 = 6_378.14;
+
+/// Шаг поиска восхода и захода Луны
+const RISE_SET_STEP_MINUTES: i64 = 10;
+
+/// Фаза Луны
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoonPhase {
+    /// Новолуние
+    NewMoon,
+    /// Растущий серп
+    WaxingCrescent,
+    /// Первая четверть
+    FirstQuarter,
+    /// Растущая Луна
+    WaxingGibbous,
+    /// Полнолуние
+    FullMoon,
+    /// Убывающая Луна
+    WaningGibbous,
+    /// Последняя четверть
+    LastQuarter,
+    /// Убывающий серп
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    /// Фазы в порядке смены с шагом 45° элонгации
+    const ALL: [MoonPhase; 8] = [
+        MoonPhase::NewMoon,
+        MoonPhase::WaxingCrescent,
+        MoonPhase::FirstQuarter,
+        MoonPhase::WaxingGibbous,
+        MoonPhase::FullMoon,
+        MoonPhase::WaningGibbous,
+        MoonPhase::LastQuarter,
+        MoonPhase::WaningCrescent,
+    ];
+
+    /// Фаза по элонгации Луны от Солнца (0-360°, 180° - полнолуние)
+    fn from_elongation(elongation: f64) -> Self {
+        let index = (elongation.rem_euclid(360.0) / 45.0).round() as usize % 8;
+        Self::ALL[index]
+    }
+
+    /// Название фазы
+    pub fn name(self) -> &'static str {
+        match self {
+            MoonPhase::NewMoon => "Новолуние",
+            MoonPhase::WaxingCrescent => "Растущий серп",
+            MoonPhase::FirstQuarter => "Первая четверть",
+            MoonPhase::WaxingGibbous => "Растущая Луна",
+            MoonPhase::FullMoon => "Полнолуние",
+            MoonPhase::WaningGibbous => "Убывающая Луна",
+            MoonPhase::LastQuarter => "Последняя четверть",
+            MoonPhase::WaningCrescent => "Убывающий серп",
+        }
+    }
+
+    /// Значок фазы
+    pub fn emoji(self) -> &'static str {
+        match self {
+            MoonPhase::NewMoon => "🌑",
+            MoonPhase::WaxingCrescent => "🌒",
+            MoonPhase::FirstQuarter => "🌓",
+            MoonPhase::WaxingGibbous => "🌔",
+            MoonPhase::FullMoon => "🌕",
+            MoonPhase::WaningGibbous => "🌖",
+            MoonPhase::LastQuarter => "🌗",
+            MoonPhase::WaningCrescent => "🌘",
+        }
+    }
+}
+
+/// Положение и фаза Луны в заданный момент
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoonPosition {
+    /// Высота над горизонтом в градусах (с учетом параллакса, без рефракции)
+    pub altitude: f64,
+    /// Азимут в градусах (0 - север, 90 - восток)
+    pub azimuth: f64,
+    /// Освещенная доля диска (0-1)
+    pub illumination: f64,
+    /// Фаза
+    pub phase: MoonPhase,
+    /// Возраст Луны в сутках после новолуния
+    pub age_days: f64,
+    /// Расстояние до Луны в км
+    pub distance_km: f64,
+}
+
+/// Луна на дату съемки
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MoonInfo {
+    /// Фаза
+    pub phase: MoonPhase,
+    /// Освещенная доля диска (0-1)
+    pub illumination: f64,
+    /// Возраст Луны в сутках после новолуния
+    pub age_days: f64,
+    /// Высота над горизонтом в момент расчета, градусы
+    pub altitude: f64,
+    /// Азимут в момент расчета, градусы
+    pub azimuth: f64,
+    /// Восход Луны в эти сутки (бывают сутки без восхода)
+    pub moonrise: Option<DateTime<Tz>>,
+    /// Заход Луны в эти сутки (бывают сутки без захода)
+    pub moonset: Option<DateTime<Tz>>,
+}
+
+impl MoonInfo {
+    /// Находится ли Луна над горизонтом в момент расчета
+    pub fn is_up(&self) -> bool {
+        self.altitude > 0.0
+    }
+
+    /// Достаточно ли ярка Луна, чтобы засвечивать небо
+    pub fn is_bright(&self) -> bool {
+        self.illumination >= BRIGHT_MOON_ILLUMINATION
+    }
+
+    /// Практически темное небо: Луна почти не освещена
+    pub fn is_dark(&self) -> bool {
+        self.illumination < DARK_MOON_ILLUMINATION
+    }
+
+    /// Влияние Луны на наблюдение северных сияний
+    pub fn aurora_impact(&self) -> String {
+        if self.is_dark() {
+            "Луна не мешает: видны даже слабые сияния".to_string()
+        } else if self.is_bright() {
+            format!(
+                "Луна освещена на {:.0}% - слабые сияния будут плохо видны",
+                self.illumination * 100.0
+            )
+        } else {
+            "Луна слабо засвечивает небо".to_string()
+        }
+    }
+}
+
+/// Сервис для расчета Луны в заданной точке
+pub struct MoonService {
+    latitude: f64,
+    longitude: f64,
+    timezone: Tz,
+}
+
+impl MoonService {
+    /// Создает сервис для точки наблюдения
+    ///
+    /// # Аргументы
+    ///
+    /// * `latitude` - Широта в градусах (от -90 до 90)
+    /// * `longitude` - Долгота в градусах (от -180 до 180)
+    ///
+    /// Часовой пояс по умолчанию определяется по координатам.
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            timezone: timezone_at(latitude, longitude),
+        }
+    }
+
+    /// Задает часовой пояс места съемки: в нем считаются сутки восхода и захода
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Положение и фаза Луны в заданный момент
+    pub fn position(&self, time: DateTime<Utc>) -> MoonPosition {
+        let ecliptic = moon_ecliptic(time);
+        let (right_ascension, declination) = ecliptic.equatorial();
+
+        // Часовой угол и горизонтальные координаты
+        let hour_angle = (local_sidereal_time(time, self.longitude) - right_ascension).to_radians();
+        let latitude = self.latitude.to_radians();
+        let declination = declination.to_radians();
+
+        let sin_altitude = latitude.sin() * declination.sin()
+            + latitude.cos() * declination.cos() * hour_angle.cos();
+        let geocentric_altitude = sin_altitude.clamp(-1.0, 1.0).asin().to_degrees();
+        let azimuth = hour_angle
+            .sin()
+            .atan2(hour_angle.cos() * latitude.sin() - declination.tan() * latitude.cos())
+            .to_degrees()
+            + 180.0;
+
+        // Луна близко: из точки на поверхности Земли она видна ниже,
+        // чем из центра Земли (параллакс до 1°)
+        let parallax = (EARTH_RADIUS_KM / ecliptic.distance_km).asin().to_degrees();
+        let altitude = geocentric_altitude - parallax * geocentric_altitude.to_radians().cos();
+
+        // Фаза по элонгации Луны от Солнца
+        let elongation = (ecliptic.longitude - sun_longitude(time)).rem_euclid(360.0);
+        let separation = (ecliptic.latitude.to_radians().cos() * elongation.to_radians().cos())
+            .clamp(-1.0, 1.0)
+            .acos();
+        let phase_angle = (SUN_DISTANCE_KM * separation.sin())
+            .atan2(ecliptic.distance_km - SUN_DISTANCE_KM * separation.cos());
+
+        MoonPosition {
+            altitude,
+            azimuth: azimuth.rem_euclid(360.0),
+            illumination: (1.0 + phase_angle.cos()) / 2.0,
+            phase: MoonPhase::from_elongation(elongation),
+            age_days: elongation / 360.0 * SYNODIC_MONTH,
+            distance_km: ecliptic.distance_km,
+        }
+    }
+
+    /// Рассчитывает Луну на момент `time` и восход/заход в эти сутки
+    pub fn calculate_moon_info<Z: TimeZone>(&self, time: DateTime<Z>) -> MoonInfo {
+        let time = time.with_timezone(&self.timezone);
+        let position = self.position(time.with_timezone(&Utc));
+        let (moonrise, moonset) = self.rise_and_set(time);
+
+        MoonInfo {
+            phase: position.phase,
+            illumination: position.illumination,
+            age_days: position.age_days,
+            altitude: position.altitude,
+            azimuth: position.azimuth,
+            moonrise,
+            moonset,
+        }
+    }
+
+    /// Засвечивает ли Луна небо в заданный момент: она над горизонтом и ярка
+    pub fn brightens_sky(&self, time: DateTime<Utc>) -> bool {
+        let position = self.position(time);
+        position.altitude > 0.0 && position.illumination >= BRIGHT_MOON_ILLUMINATION
+    }
+
+    /// Восход и заход Луны в сутки места съемки, содержащие `time`
+    ///
+    /// Луна смещается по небу почти на час в сутки, поэтому в некоторые
+    /// сутки восхода или захода нет - тогда возвращается `None`.
+    fn rise_and_set(&self, time: DateTime<Tz>) -> (Option<DateTime<Tz>>, Option<DateTime<Tz>>) {
+        let day_start = time
+            .date_naive()
+            .and_time(NaiveTime::MIN)
+            .and_local_timezone(self.timezone)
+            .earliest()
+            .unwrap_or(time)
+            .with_timezone(&Utc);
+        let day_end = day_start + Duration::days(1);
+
+        let mut moonrise = None;
+        let mut moonset = None;
+        let mut previous = day_start;
+        let mut previous_height = self.height_above_horizon(previous);
+
+        while previous < day_end && (moonrise.is_none() || moonset.is_none()) {
+            let next = (previous + Duration::minutes(RISE_SET_STEP_MINUTES)).min(day_end);
+            let next_height = self.height_above_horizon(next);
+
+            if previous_height < 0.0 && next_height >= 0.0 && moonrise.is_none() {
+                moonrise = Some(self.refine_crossing(previous, next));
+            } else if previous_height >= 0.0 && next_height < 0.0 && moonset.is_none() {
+                moonset = Some(self.refine_crossing(previous, next));
+            }
+
+            previous = next;
+            previous_height = next_height;
+        }
+
+        (
+            moonrise.map(|time| time.with_timezone(&self.timezone)),
+            moonset.map(|time| time.with_timezone(&self.timezone)),
+        )
+    }
+
+    /// Высота верхнего края Луны над видимым горизонтом с учетом рефракции
+    fn height_above_horizon(&self, time: DateTime<Utc>) -> f64 {
+        // Рефракция у горизонта 34' и видимый радиус диска ~15.5'
+        self.position(time).altitude + 0.8333
+    }
+
+    /// Уточняет момент пересечения горизонта делением отрезка пополам
+    fn refine_crossing(&self, mut start: DateTime<Utc>, mut end: DateTime<Utc>) -> DateTime<Utc> {
+        let rising = self.height_above_horizon(start) < 0.0;
+        while end - start > Duration::seconds(30) {
+            let middle = start + (end - start) / 2;
+            if (self.height_above_horizon(middle) < 0.0) == rising {
+                start = middle;
+            } else {
+                end = middle;
+            }
+        }
+        start + (end - start) / 2
+    }
+}
+
+/// Эклиптические координаты Луны
+struct EclipticPosition {
+    /// Долгота, градусы
+    longitude: f64,
+    /// Широта, градусы
+    latitude: f64,
+    /// Расстояние, км
+    distance_km: f64,
+    /// Наклон эклиптики, градусы
+    obliquity: f64,
+}
+
+impl EclipticPosition {
+    /// Прямое восхождение и склонение в градусах
+    fn equatorial(&self) -> (f64, f64) {
+        let (longitude, latitude, obliquity) = (
+            self.longitude.to_radians(),
+            self.latitude.to_radians(),
+            self.obliquity.to_radians(),
+        );
+        let right_ascension = (longitude.sin() * obliquity.cos()
+            - latitude.tan() * obliquity.sin())
+        .atan2(longitude.cos());
+        let declination = (latitude.sin() * obliquity.cos()
+            + latitude.cos() * obliquity.sin() * longitude.sin())
+        .asin();
+        (right_ascension.to_degrees(), declination.to_degrees())
+    }
+}
+
+/// Юлианские столетия от эпохи J2000.0
+pub(crate) fn julian_centuries(time: DateTime<Utc>) -> f64 {
+    let julian_day = time.timestamp() as f64 / 86_400.0 + 2_440_587.5;
+    (julian_day - 2_451_545.0) / 36_525.0
+}
+
+/// Местное звездное время в градусах
+pub(crate) fn local_sidereal_time(time: DateTime<Utc>, longitude: f64) -> f64 {
+    let t = julian_centuries(time);
+    let days = t * 36_525.0;
+    (280.460_618_37 + 360.985_647_366_29 * days + 0.000_387_933 * t * t + longitude)
+        .rem_euclid(360.0)
+}
+
+/// Видимая эклиптическая долгота Солнца в градусах
+pub(crate) fn sun_longitude(time: DateTime<Utc>) -> f64 {
+    let t = julian_centuries(time);
+    let mean_longitude = 280.466_46 + 36_000.769_83 * t;
+    let mean_anomaly = (357.529_11 + 35_999.050_29 * t).to_radians();
+    let center = (1.914_602 - 0.004_817 * t) * mean_anomaly.sin()
+        + 0.019_993 * (2.0 * mean_anomaly).sin()
+        + 0.000_289 * (3.0 * mean_anomaly).sin();
+    (mean_longitude + center).rem_euclid(360.0)
+}
+
+/// Положение Луны по основным членам теории Меёса (глава 47)
+fn moon_ecliptic(time: DateTime<Utc>) -> EclipticPosition {
+    let t = julian_centuries(time);
+
+    let mean_longitude = 218.316_447_7 + 481_267.881_234_21 * t;
+    let elongation = (297.850_192_1 + 445_267.111_403_4 * t).to_radians();
+    let sun_anomaly = (357.529_109_2 + 35_999.050_290_9 * t).to_radians();
+    let moon_anomaly = (134.963_396_4 + 477_198.867_505_5 * t).to_radians();
+    let latitude_argument = (93.272_095_0 + 483_202.017_523_3 * t).to_radians();
+    // Поправка на эксцентриситет орбиты Земли для членов с аномалией Солнца
+    let e = 1.0 - 0.002_516 * t;
+
+    let (d, m, mp, f) = (elongation, sun_anomaly, moon_anomaly, latitude_argument);
+
+    let longitude = mean_longitude
+        + 6.288_774 * mp.sin()
+        + 1.274_027 * (2.0 * d - mp).sin()
+        + 0.658_314 * (2.0 * d).sin()
+        + 0.213_618 * (2.0 * mp).sin()
+        - 0.185_116 * e * m.sin()
+        - 0.114_332 * (2.0 * f).sin()
+        + 0.058_793 * (2.0 * d - 2.0 * mp).sin()
+        + 0.057_066 * e * (2.0 * d - m - mp).sin()
+        + 0.053_322 * (2.0 * d + mp).sin()
+        + 0.045_758 * e * (2.0 * d - m).sin()
+        - 0.040_923 * e * (m - mp).sin()
+        - 0.034_720 * d.sin()
+        - 0.030_383 * e * (m + mp).sin()
+        + 0.015_327 * (2.0 * d - 2.0 * f).sin()
+        - 0.012_528 * (mp + 2.0 * f).sin()
+        + 0.010_980 * (mp - 2.0 * f).sin();
+
+    let latitude = 5.128_122 * f.sin()
+        + 0.280_602 * (mp + f).sin()
+        + 0.277_693 * (mp - f).sin()
+        + 0.173_237 * (2.0 * d - f).sin()
+        + 0.055_413 * (2.0 * d - mp + f).sin()
+        + 0.046_271 * (2.0 * d - mp - f).sin()
+        + 0.032_573 * (2.0 * d + f).sin()
+        + 0.017_198 * (2.0 * mp + f).sin();
+
+    let distance_km = 385_000.56
+        - 20_905.355 * mp.cos()
+        - 3_699.111 * (2.0 * d - mp).cos()
+        - 2_955.968 * (2.0 * d).cos()
+        - 569.925 * (2.0 * mp).cos()
+        + 48.888 * e * m.cos()
+        - 3.149 * (2.0 * f).cos()
+        + 246.158 * (2.0 * d - 2.0 * mp).cos()
+        - 152.138 * e * (2.0 * d - m - mp).cos()
+        - 170.733 * (2.0 * d + mp).cos();
+
+    EclipticPosition {
+        longitude: longitude.rem_euclid(360.0),
+        latitude,
+        distance_km,
+        obliquity: 23.439_291 - 0.013_004_2 * t,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    fn moscow() -> MoonService {
+        MoonService::new(55.7558, 37.6176)
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_phases_and_illumination() {
+        let service = moscow();
+
+        // Новолуние 11.01.2024 11:57 UTC
+        let new_moon = service.position(utc(2024, 1, 11, 12, 0));
+        assert_eq!(new_moon.phase, MoonPhase::NewMoon);
+        assert!(new_moon.illumination < 0.01);
+        assert!(new_moon.age_days < 0.5 || new_moon.age_days > 29.0);
+
+        // Первая четверть 18.01.2024 03:53 UTC
+        let first_quarter = service.position(utc(2024, 1, 18, 3, 53));
+        assert_eq!(first_quarter.phase, MoonPhase::FirstQuarter);
+        assert!((first_quarter.illumination - 0.5).abs() < 0.03);
+
+        // Полнолуние 25.01.2024 17:54 UTC
+        let full_moon = service.position(utc(2024, 1, 25, 17, 54));
+        assert_eq!(full_moon.phase, MoonPhase::FullMoon);
+        assert!(full_moon.illumination > 0.99);
+        assert!((full_moon.age_days - SYNODIC_MONTH / 2.0).abs() < 0.5);
+
+        // Убывающая Луна после полнолуния
+        let waning = service.position(utc(2024, 1, 29, 0, 0));
+        assert_eq!(waning.phase, MoonPhase::WaningGibbous);
+    }
+
+    #[test]
+    fn test_altitude_and_distance() {
+        let service = moscow();
+
+        // Зимняя полная Луна около местной полуночи стоит высоко на юге
+        let position = service.position(utc(2024, 1, 25, 21, 30));
+        assert!(position.altitude > 40.0, "altitude {}", position.altitude);
+        assert!((position.azimuth - 180.0).abs() < 20.0);
+
+        // Расстояние в пределах перигея и апогея
+        assert!((356_000.0..407_000.0).contains(&position.distance_km));
+
+        // Через 12 часов Луна под горизонтом
+        assert!(service.position(utc(2024, 1, 26, 9, 30)).altitude < 0.0);
+    }
+
+    #[test]
+    fn test_moonrise_near_sunset_at_full_moon() {
+        let service = moscow();
+        let time = utc(2024, 1, 25, 12, 0);
+        let info = service.calculate_moon_info(time);
+
+        // В полнолуние Луна восходит около заката (~13:20 UTC в Москве)
+        let moonrise = info.moonrise.expect("moonrise").with_timezone(&Utc);
+        let sunset = utc(2024, 1, 25, 13, 20);
+        assert!((moonrise - sunset).num_minutes().abs() < 90);
+        assert!(info.is_bright());
+        assert!(!info.is_dark());
+
+        // Луна восходит и заходит на границе горизонта
+        let height = service.height_above_horizon(moonrise);
+        assert!(height.abs() < 0.1, "height at moonrise {}", height);
+    }
+
+    #[test]
+    fn test_brightens_sky() {
+        let service = moscow();
+        // Полная Луна над горизонтом
+        assert!(service.brightens_sky(utc(2024, 1, 25, 21, 30)));
+        // Полная Луна под горизонтом
+        assert!(!service.brightens_sky(utc(2024, 1, 26, 9, 30)));
+        // Новолуние
+        assert!(!service.brightens_sky(utc(2024, 1, 11, 10, 0)));
+    }
+
+    #[test]
+    fn test_aurora_impact() {
+        let service = moscow();
+        let full = service.calculate_moon_info(utc(2024, 1, 25, 21, 0));
+        assert!(full.aurora_impact().contains("плохо видны"));
+        let new = service.calculate_moon_info(utc(2024, 1, 11, 21, 0));
+        assert!(new.aurora_impact().contains("не мешает"));
+    }
+
+    #[test]
+    fn test_phase_names() {
+        assert_eq!(MoonPhase::from_elongation(0.0), MoonPhase::NewMoon);
+        assert_eq!(MoonPhase::from_elongation(350.0), MoonPhase::NewMoon);
+        assert_eq!(MoonPhase::from_elongation(90.0), MoonPhase::FirstQuarter);
+        assert_eq!(MoonPhase::from_elongation(270.0), MoonPhase::LastQuarter);
+        assert_eq!(MoonPhase::FullMoon.name(), "Полнолуние");
+        assert_eq!(MoonPhase::WaningCrescent.emoji(), "🌘");
+    }
+}
@@ -32,6 +32,9 @@
 //! }
 //! ```
 
+use crate::golden_hour::{Daylight, GoldenHourInfo};
+use crate::moon::MoonInfo;
+
 /// Структура с рекомендациями по фотографии
 ///
 /// Содержит персонализированные советы по оборудованию, съемке,
@@ -193,6 +196,96 @@ impl PhotographyTipsService {
         tips
     }
 
+    /// Дополняет советы с учетом фазы и положения Луны
+    ///
+    /// Яркая Луна мешает съемке Млечного Пути и слабых сияний, но хорошо
+    /// освещает ночной пейзаж; в новолуние небо максимально темное.
+    ///
+    /// # Аргументы
+    ///
+    /// * `tips` - Советы, полученные из [`get_tips_for_weather`](Self::get_tips_for_weather)
+    /// * `moon` - Луна на дату съемки
+    /// * `aurora_probability` - Вероятность северных сияний (0-1)
+    pub fn add_moon_tips(
+        &self,
+        tips: &mut PhotographyTips,
+        moon: &MoonInfo,
+        aurora_probability: f64,
+    ) {
+        if moon.is_dark() {
+            tips.shooting_tips.push(format!(
+                "{} - лучшее время для Млечного Пути и звездного неба",
+                moon.phase.name()
+            ));
+            return;
+        }
+
+        if !moon.is_bright() {
+            return;
+        }
+
+        match moon.moonset {
+            Some(moonset) => tips.shooting_tips.push(format!(
+                "Млечный Путь снимайте после захода Луны в {}",
+                moonset.format("%H:%M")
+            )),
+            None => tips
+                .shooting_tips
+                .push("Луна светит всю ночь - Млечный Путь будет едва заметен".to_string()),
+        }
+        tips.shooting_tips.push(format!(
+            "{} ({:.0}%) - используйте лунный свет для ночных пейзажей",
+            moon.phase.name(),
+            moon.illumination * 100.0
+        ));
+        tips.technical_settings
+            .push("Ночной пейзаж при Луне: ISO 400-800, выдержка 5-15 секунд".to_string());
+
+        if aurora_probability > 0.5 {
+            tips.location_suggestions
+                .push("Стойте спиной к Луне: так слабые сияния контрастнее".to_string());
+        }
+    }
+
+    /// Добавляет советы для полярного дня, полярной ночи и суток без золотого часа
+    ///
+    /// # Аргументы
+    ///
+    /// * `tips` - Советы, полученные из [`get_tips_for_weather`](Self::get_tips_for_weather)
+    /// * `golden_hour` - Золотой час и смена дня и ночи на дату съемки
+    pub fn add_daylight_tips(&self, tips: &mut PhotographyTips, golden_hour: &GoldenHourInfo) {
+        match golden_hour.daylight {
+            Daylight::PolarNight => {
+                tips.shooting_tips.push(
+                    "Полярная ночь: около полудня небо светится сумеречным светом - снимайте пейзажи в это время"
+                        .to_string(),
+                );
+                tips.technical_settings
+                    .push("Полярная ночь: штатив и выдержка 1-4 секунды даже днем".to_string());
+                tips.equipment_recommendations
+                    .push("Запасные аккумуляторы: на морозе они разряжаются быстрее".to_string());
+            }
+            Daylight::PolarDay => {
+                tips.shooting_tips.push(
+                    "Полярный день: самый мягкий свет около полуночи, когда Солнце ниже всего"
+                        .to_string(),
+                );
+                tips.shooting_tips
+                    .push("Звезды и северные сияния на светлом небе не видны".to_string());
+            }
+            Daylight::Normal => {}
+        }
+
+        if !golden_hour.has_golden_hour {
+            let tip = if golden_hour.has_blue_hour {
+                "Золотого часа сегодня нет - снимайте в синий час"
+            } else {
+                "Золотого часа сегодня нет - ищите мягкий свет в облачную погоду"
+            };
+            tips.shooting_tips.push(tip.to_string());
+        }
+    }
+
     /// Возвращает общие рекомендации по фотографии
     ///
     /// Содержит универсальные советы, которые применимы в любых условиях
@@ -428,6 +521,94 @@ mod tests {
         }
     }
 
+    fn moon(illumination: f64, moonset_hour: Option<u32>) -> MoonInfo {
+        use chrono::TimeZone;
+        use chrono_tz::Europe::Moscow;
+
+        MoonInfo {
+            phase: crate::moon::MoonPhase::WaxingGibbous,
+            illumination,
+            age_days: 10.0,
+            altitude: 30.0,
+            azimuth: 180.0,
+            moonrise: None,
+            moonset: moonset_hour
+                .map(|hour| Moscow.with_ymd_and_hms(2024, 1, 20, hour, 15, 0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_add_moon_tips() {
+        let service = PhotographyTipsService::new();
+
+        // Яркая Луна заходит ночью: снимать Млечный Путь после захода
+        let mut tips = service.get_tips_for_weather(8.0, false, 0.8);
+        service.add_moon_tips(&mut tips, &moon(0.8, Some(3)), 0.8);
+        assert!(tips
+            .shooting_tips
+            .iter()
+            .any(|tip| tip.contains("после захода Луны в 03:15")));
+        assert!(tips
+            .location_suggestions
+            .iter()
+            .any(|tip| tip.contains("спиной к Луне")));
+
+        // Новолуние: темное небо
+        let mut tips = service.get_tips_for_weather(8.0, false, 0.1);
+        service.add_moon_tips(&mut tips, &moon(0.02, None), 0.1);
+        assert!(tips
+            .shooting_tips
+            .iter()
+            .any(|tip| tip.contains("лучшее время для Млечного Пути")));
+
+        // Тонкий серп не требует отдельных советов
+        let mut tips = service.get_tips_for_weather(8.0, false, 0.1);
+        let before = tips.shooting_tips.len();
+        service.add_moon_tips(&mut tips, &moon(0.2, None), 0.1);
+        assert_eq!(tips.shooting_tips.len(), before);
+    }
+
+    #[test]
+    fn test_add_daylight_tips() {
+        use crate::golden_hour::GoldenHourService;
+        use chrono::NaiveDate;
+
+        let service = PhotographyTipsService::new();
+        let december = NaiveDate::from_ymd_opt(2024, 12, 21).unwrap();
+        let june = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+
+        // Шпицберген зимой: полярная ночь без золотого часа
+        let svalbard = GoldenHourService::new(78.2232, 15.6267);
+        let mut tips = service.get_tips_for_weather(8.0, false, 0.5);
+        service.add_daylight_tips(
+            &mut tips,
+            &svalbard.calculate_golden_hours_for_date(december),
+        );
+        assert!(tips
+            .shooting_tips
+            .iter()
+            .any(|tip| tip.starts_with("Полярная ночь")));
+        assert!(tips
+            .shooting_tips
+            .iter()
+            .any(|tip| tip.starts_with("Золотого часа сегодня нет")));
+
+        // Шпицберген летом: полярный день
+        let mut tips = service.get_tips_for_weather(8.0, false, 0.5);
+        service.add_daylight_tips(&mut tips, &svalbard.calculate_golden_hours_for_date(june));
+        assert!(tips
+            .shooting_tips
+            .iter()
+            .any(|tip| tip.starts_with("Полярный день")));
+
+        // Обычный день в Москве не добавляет советов
+        let moscow = GoldenHourService::new(55.7558, 37.6176);
+        let mut tips = service.get_tips_for_weather(8.0, false, 0.5);
+        let before = tips.shooting_tips.len();
+        service.add_daylight_tips(&mut tips, &moscow.calculate_golden_hours_for_date(june));
+        assert_eq!(tips.shooting_tips.len(), before);
+    }
+
     #[test]
     fn test_edge_cases() {
         let service = PhotographyTipsService::new();
@@ -0,0 +1,1172 @@
+//! # Render Module
+//!
+//! Вывод дашборда в текстовом виде и в формате JSON.
+//! Оба формата строятся из одной структурированной модели [`DashboardOutput`].
+//!
+//! ## Основные компоненты
+//!
+//! - [`OutputFormat`] - Формат вывода (`--format text|json`)
+//! - [`DashboardSection`] - Секции для вывода (`--sections`)
+//! - [`render_text`] - Текстовый вывод для терминала
+//! - [`render_json`] - JSON с исходными числами для скриптов и автоматизаций
+//! - [`render_comparison`] - Таблица сравнения нескольких локаций
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::render::{DashboardSection, OutputFormat};
+//!
+//! assert_eq!(OutputFormat::default(), OutputFormat::Text);
+//! assert_eq!(DashboardSection::ALL.len(), 8);
+//! assert!(!DashboardSection::DEFAULT.contains(&DashboardSection::Timeline));
+//!
+//! // Для асинхронного использования:
+//! // let output = my_dashboard::generate_dashboard_output(&config, &location).await?;
+//! // println!("{}", my_dashboard::render::render_json(&output, &[DashboardSection::GoldenHour])?);
+//! ```
+
+use crate::compare::{ComparisonEntry, LocationComparison};
+use crate::config::Units;
+use crate::dashboard::DashboardSummary;
+use crate::golden_hour::{Daylight, LightingPhase, TwilightInfo};
+use crate::location::compass_point;
+use crate::moon::MoonInfo;
+use crate::scoring::ProfileScore;
+use crate::sky_fire::SkyFirePrediction;
+use crate::solar::AuroraForecast;
+use crate::{
+    AstrophotographyOutput, DashboardOutput, GoldenHourOutput, PhotographyTipsOutput, Section,
+    TimelineHour, WeatherOutput,
+};
+use anyhow::Result;
+use chrono::Timelike;
+use clap::ValueEnum;
+use colored::*;
+use std::fmt::Write;
+
+/// Формат вывода дашборда
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Текст для терминала
+    #[default]
+    Text,
+    /// JSON со структурированной моделью дашборда
+    Json,
+}
+
+/// Секция дашборда, которую можно включить в вывод (`--sections`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DashboardSection {
+    /// Общая оценка, ключевые моменты, предупреждения и план по дням
+    Summary,
+    /// Погода на ближайшие сутки
+    Weather,
+    /// Почасовой прогноз: погода, оценка и освещение каждого часа
+    Timeline,
+    /// Оценка по профилям жанров съемки
+    Profiles,
+    /// Условия для астрофотографии
+    Astro,
+    /// Солнечный ветер и прогноз северных сияний
+    Aurora,
+    /// Восход, закат, золотой и синий час
+    GoldenHour,
+    /// Советы для фотографов
+    Tips,
+}
+
+impl DashboardSection {
+    /// Все секции в порядке вывода
+    pub const ALL: [DashboardSection; 8] = [
+        DashboardSection::Summary,
+        DashboardSection::Weather,
+        DashboardSection::Timeline,
+        DashboardSection::Profiles,
+        DashboardSection::Astro,
+        DashboardSection::Aurora,
+        DashboardSection::GoldenHour,
+        DashboardSection::Tips,
+    ];
+
+    /// Секции, выводимые без `--sections`: все, кроме почасового прогноза
+    pub const DEFAULT: [DashboardSection; 7] = [
+        DashboardSection::Summary,
+        DashboardSection::Weather,
+        DashboardSection::Profiles,
+        DashboardSection::Astro,
+        DashboardSection::Aurora,
+        DashboardSection::GoldenHour,
+        DashboardSection::Tips,
+    ];
+
+    /// Ключи секции в JSON модели
+    fn json_keys(self) -> &'static [&'static str] {
+        match self {
+            DashboardSection::Summary => &["summary"],
+            DashboardSection::Weather => &["weather"],
+            // Почасовой прогноз входит в модель погоды (`weather.hourly`)
+            DashboardSection::Timeline => &["weather"],
+            DashboardSection::Profiles => &["profiles"],
+            DashboardSection::Astro => &["astrophotography", "moon"],
+            DashboardSection::Aurora => &["aurora"],
+            DashboardSection::GoldenHour => &["golden_hour", "sky_fire"],
+            DashboardSection::Tips => &["tips"],
+        }
+    }
+}
+
+/// Выводит выбранные секции дашборда в заданном формате
+///
+/// Единицы измерения применяются только к текстовому выводу.
+pub fn render(
+    output: &DashboardOutput,
+    format: OutputFormat,
+    sections: &[DashboardSection],
+    units: Units,
+) -> Result<String> {
+    match format {
+        OutputFormat::Text => Ok(render_text(output, sections, units)),
+        OutputFormat::Json => render_json(output, sections),
+    }
+}
+
+/// Сериализует дашборд в JSON
+///
+/// Невыбранные секции не попадают в результат; время построения и место
+/// съемки выводятся всегда.
+pub fn render_json(output: &DashboardOutput, sections: &[DashboardSection]) -> Result<String> {
+    let mut value = serde_json::to_value(output)?;
+    if let Some(object) = value.as_object_mut() {
+        // Ключ удаляется, только если его не использует ни одна выбранная секция
+        let selected: Vec<&str> = sections
+            .iter()
+            .flat_map(|section| section.json_keys())
+            .copied()
+            .collect();
+        for section in DashboardSection::ALL {
+            for key in section.json_keys() {
+                if !selected.contains(key) {
+                    object.remove(*key);
+                }
+            }
+        }
+    }
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
+/// Строит текстовый вывод выбранных секций дашборда
+pub fn render_text(
+    output: &DashboardOutput,
+    sections: &[DashboardSection],
+    units: Units,
+) -> String {
+    let mut out = String::new();
+    let show = |section: DashboardSection| sections.contains(&section);
+
+    // Основная сводка дашборда
+    if show(DashboardSection::Summary) {
+        write_summary(&mut out, &output.location_name, &output.summary, units);
+    }
+
+    let details = [
+        DashboardSection::Weather,
+        DashboardSection::Timeline,
+        DashboardSection::Profiles,
+        DashboardSection::Astro,
+        DashboardSection::Aurora,
+        DashboardSection::GoldenHour,
+    ];
+    if details.into_iter().any(show) {
+        line(
+            &mut out,
+            format!("\n{}", "📊 ДЕТАЛЬНАЯ ИНФОРМАЦИЯ".bold().cyan()),
+        );
+    }
+
+    if show(DashboardSection::Weather) {
+        match &output.weather_output {
+            Section::Available(weather_output) => write_weather(&mut out, weather_output, units),
+            Section::Unavailable(_) => write_unavailable(&mut out, "🌤️ Погода"),
+        }
+    }
+
+    if show(DashboardSection::Timeline) {
+        match &output.weather_output {
+            Section::Available(weather_output) => write_timeline(
+                &mut out,
+                &weather_output.hourly,
+                output.location.timezone(),
+                units,
+            ),
+            Section::Unavailable(_) => write_unavailable(&mut out, "🕒 Почасовой прогноз"),
+        }
+    }
+
+    if show(DashboardSection::Profiles) {
+        match &output.profiles {
+            Section::Available(profiles) => write_profiles(&mut out, profiles),
+            Section::Unavailable(_) => write_unavailable(&mut out, "🎯 Профили съемки"),
+        }
+    }
+
+    if show(DashboardSection::Astro) {
+        match &output.astrophotography_output {
+            Section::Available(astro_output) => write_astrophotography(&mut out, astro_output),
+            Section::Unavailable(_) => write_unavailable(&mut out, "🌌 Астрофото"),
+        }
+        write_moon(&mut out, &output.moon);
+    }
+
+    if show(DashboardSection::Aurora) {
+        match &output.solar_output {
+            Section::Available(aurora_forecast) => write_aurora(
+                &mut out,
+                aurora_forecast,
+                &output.moon,
+                output.location.timezone(),
+            ),
+            Section::Unavailable(_) => write_unavailable(&mut out, "🌌 Северные сияния"),
+        }
+    }
+
+    if show(DashboardSection::GoldenHour) {
+        write_golden_hour(&mut out, &output.golden_hour_output);
+        if let Section::Available(predictions) = &output.sky_fire {
+            write_sky_fire(&mut out, predictions);
+        }
+    }
+
+    if show(DashboardSection::Tips) {
+        line(
+            &mut out,
+            format!("\n{}", "=== СОВЕТЫ ДЛЯ ФОТОГРАФОВ ===".bold().green()),
+        );
+        write_tips(&mut out, &output.tips_output);
+    }
+
+    out
+}
+
+/// Выводит рейтинг локаций в заданном формате
+pub fn render_comparison(comparison: &LocationComparison, format: OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Text => Ok(render_comparison_text(comparison)),
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(comparison)?),
+    }
+}
+
+/// Строит таблицу сравнения локаций, лучшая локация - первая строка
+pub fn render_comparison_text(comparison: &LocationComparison) -> String {
+    let mut out = String::new();
+    line(
+        &mut out,
+        format!("\n{}", "=== СРАВНЕНИЕ ЛОКАЦИЙ ===".bold().white()),
+    );
+    line(
+        &mut out,
+        format!(
+            "🕐 {}",
+            comparison.generated_at.format("%d.%m.%Y %H:%M UTC")
+        ),
+    );
+
+    let name_width = comparison
+        .entries
+        .iter()
+        .map(|entry| entry.location.name.chars().count())
+        .chain(std::iter::once("Локация".chars().count()))
+        .max()
+        .unwrap_or(0);
+
+    line(
+        &mut out,
+        format!(
+            "{:>2}  {:<name_width$}  {:>7}  {:<5}  {:>6}  {:<11}  {:<11}  {}",
+            "#", "Локация", "Погода", "Астро", "Сияния", "Утро", "Вечер", "Лучшие часы"
+        )
+        .bold(),
+    );
+    for entry in &comparison.entries {
+        write_comparison_row(&mut out, entry, name_width);
+    }
+
+    if let Some(best) = comparison
+        .entries
+        .first()
+        .filter(|entry| entry.error.is_none())
+    {
+        line(
+            &mut out,
+            format!("\n🎯 Лучшая локация: {}", best.location.name.bold().green()),
+        );
+    }
+    out
+}
+
+fn write_comparison_row(out: &mut String, entry: &ComparisonEntry, name_width: usize) {
+    let name = format!("{:<name_width$}", entry.location.name);
+    if let Some(error) = &entry.error {
+        line(
+            out,
+            format!(
+                "{:>2}  {}  {}",
+                entry.rank,
+                name,
+                format!("⚠️ {}", error).yellow()
+            ),
+        );
+        return;
+    }
+
+    let weather = match entry.weather_score {
+        Some(score) => {
+            let cell = format!("{:>7}", format!("{:.1}/10", score));
+            if score >= 7.0 {
+                cell.green().to_string()
+            } else if score >= 4.0 {
+                cell.yellow().to_string()
+            } else {
+                cell.red().to_string()
+            }
+        }
+        None => format!("{:>7}", "-"),
+    };
+    let astro = match entry.astro_suitable {
+        Some(true) => format!("{:<5}", "да").green().to_string(),
+        Some(false) => format!("{:<5}", "нет"),
+        None => format!("{:<5}", "-"),
+    };
+    let aurora = match entry.aurora_probability {
+        Some(probability) => format!("{:>5.0}%", probability * 100.0),
+        None => format!("{:>6}", "-"),
+    };
+    let (morning, evening) = match &entry.golden_hour {
+        Some(info) => (
+            time_window(info.golden_hour_morning_start, info.golden_hour_morning_end),
+            time_window(info.golden_hour_evening_start, info.golden_hour_evening_end),
+        ),
+        None => ("-".to_string(), "-".to_string()),
+    };
+    let best_hours = if entry.best_hours.is_empty() {
+        "нет".to_string()
+    } else {
+        short_intervals(&entry.best_hours, 3)
+    };
+
+    line(
+        out,
+        format!(
+            "{:>2}  {}  {}  {}  {}  {:<11}  {:<11}  {}",
+            entry.rank, name, weather, astro, aurora, morning, evening, best_hours
+        ),
+    );
+}
+
+fn line(out: &mut String, text: impl std::fmt::Display) {
+    let _ = writeln!(out, "{}", text);
+}
+
+fn write_unavailable(out: &mut String, title: &str) {
+    // Причина недоступности выводится в предупреждениях сводки
+    line(out, format!("{}: {}", title, "⚠️ нет данных".yellow()));
+}
+
+fn write_summary(out: &mut String, location_name: &str, summary: &DashboardSummary, units: Units) {
+    line(
+        out,
+        format!("\n{}", "=== ФОТОГРАФИЧЕСКИЙ ДАШБОРД ===".bold().white()),
+    );
+    line(out, format!("📍 Локация: {}", location_name));
+    line(out, "📊 ОБЩАЯ ОЦЕНКА".bold().cyan());
+    match summary.weather_score {
+        Some(score) => line(out, format!("   Погода: {:.1}/10", score)),
+        None => line(out, "   Погода: нет данных"),
+    }
+    match summary.aurora_probability {
+        Some(probability) => line(
+            out,
+            format!(
+                "   Вероятность северных сияний: {:.0}%",
+                probability * 100.0
+            ),
+        ),
+        None => line(out, "   Вероятность северных сияний: нет данных"),
+    }
+    line(
+        out,
+        format!(
+            "   Золотой час: {}",
+            if summary.is_golden_hour_today {
+                "Да"
+            } else {
+                "Нет"
+            }
+        ),
+    );
+
+    if !summary.best_shooting_hours.is_empty() {
+        line(
+            out,
+            format!(
+                "   Лучшие часы: {}",
+                time_intervals(&summary.best_shooting_hours).join(", ")
+            ),
+        );
+    }
+
+    if let Some(fog) = &summary.fog_morning {
+        line(
+            out,
+            format!(
+                "🌫️ ТУМАННОЕ УТРО: {}, вероятность тумана до {:.0}% (восход {})",
+                time_window(fog.start, fog.end),
+                fog.probability,
+                fog.sunrise.format("%H:%M")
+            )
+            .bold()
+            .cyan(),
+        );
+    }
+
+    if !summary.key_highlights.is_empty() {
+        line(out, "✨ КЛЮЧЕВЫЕ МОМЕНТЫ".bold().green());
+        for highlight in &summary.key_highlights {
+            line(out, format!("   • {}", highlight));
+        }
+    }
+
+    if !summary.warnings.is_empty() {
+        line(out, "⚠️ ПРЕДУПРЕЖДЕНИЯ".bold().yellow());
+        for warning in &summary.warnings {
+            line(out, format!("   • {}", warning));
+        }
+    }
+
+    line(out, "🎯 РЕКОМЕНДАЦИЯ".bold().blue());
+    line(out, format!("   {}", summary.overall_recommendation));
+
+    if !summary.daily_plans.is_empty() {
+        write_daily_plans(out, summary, units);
+    }
+}
+
+fn write_daily_plans(out: &mut String, summary: &DashboardSummary, units: Units) {
+    line(
+        out,
+        format!("\n{}", "📅 ПЛАН НА НЕСКОЛЬКО ДНЕЙ".bold().cyan()),
+    );
+    for plan in &summary.daily_plans {
+        let rank = summary
+            .best_days
+            .iter()
+            .position(|date| *date == plan.date)
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+        let best_hours = if plan.best_hours.is_empty() {
+            "нет".to_string()
+        } else {
+            time_intervals(&plan.best_hours).join(", ")
+        };
+        line(
+            out,
+            format!(
+                "   #{} {} | ⭐ {:.1}/10 | 🌡️{:.0}..{:.0}{} | ☁️{:.0}% | 🌅 {} | 🌆 {} | 🕐 {}",
+                rank,
+                plan.date.format("%d.%m"),
+                plan.weather_score,
+                units.temperature(plan.min_temperature),
+                units.temperature(plan.max_temperature),
+                units.temperature_unit(),
+                plan.avg_cloud_cover,
+                time_window(
+                    plan.golden_hour.golden_hour_morning_start,
+                    plan.golden_hour.golden_hour_morning_end
+                ),
+                time_window(
+                    plan.golden_hour.golden_hour_evening_start,
+                    plan.golden_hour.golden_hour_evening_end
+                ),
+                best_hours
+            ),
+        );
+    }
+}
+
+fn write_weather(out: &mut String, weather_output: &WeatherOutput, units: Units) {
+    match &weather_output.current {
+        Some(current) => line(
+            out,
+            format!(
+                "🌤️ Погода: 🌡️{:.1}{}  ☁️{:.0}%  💨{:.1}{}  🌧️{:.0}%  📝{}",
+                units.temperature(current.temperature),
+                units.temperature_unit(),
+                current.cloud_cover,
+                units.speed(current.wind_speed),
+                units.speed_unit(),
+                current.precipitation_probability,
+                current.description
+            ),
+        ),
+        None => line(out, "Нет данных о погоде"),
+    }
+
+    let best_hours = if weather_output.best_hours.is_empty() {
+        "Нет подходящих часов".to_string()
+    } else {
+        format!(
+            "🕐 Лучшие часы: {}",
+            short_intervals(&weather_output.best_hours, 3)
+        )
+    };
+    line(
+        out,
+        format!(
+            "📊 Диапазон: 🌡️{}-{}{}  💨Ветер до {:.1}{}  🌧️Осадки до {:.0}%  {}  | ⭐ Оценка: {:.1}/10",
+            units.temperature(weather_output.min_temperature) as i32,
+            units.temperature(weather_output.max_temperature) as i32,
+            units.temperature_unit(),
+            units.speed(weather_output.max_wind_speed),
+            units.speed_unit(),
+            weather_output.max_precipitation_probability,
+            best_hours,
+            weather_output.overall_score
+        ),
+    );
+
+    let mut details = String::new();
+    if let Some(recommendation) = weather_output.recommendations.first() {
+        details.push_str(&format!("💡 Рекомендация: {}", recommendation));
+    }
+    if let Some(concern) = weather_output.concerns.first() {
+        details.push_str(&format!(" | ⚠️ Проблемы: {}", concern));
+    }
+    line(out, details);
+}
+
+/// Таблица почасового прогноза: строка на каждый час, при смене даты -
+/// строка с новой датой
+fn write_timeline(
+    out: &mut String,
+    hourly: &[TimelineHour],
+    timezone: chrono_tz::Tz,
+    units: Units,
+) {
+    // Ширина колонки ветра зависит от единиц измерения
+    let wind_header = format!("Ветер, {}", units.speed_unit());
+    let wind_width = wind_header.chars().count();
+
+    line(out, "🕒 Почасовой прогноз:");
+    line(
+        out,
+        format!(
+            "   {:<5}  {:>6}  {:>5}  {:>wind_width$}  {:>6}  {:>5}  {:>6}  {}",
+            "Время",
+            format!("t, {}", units.temperature_unit()),
+            "Обл.",
+            wind_header,
+            "Осадки",
+            "Туман",
+            "Оценка",
+            "Свет"
+        )
+        .dimmed(),
+    );
+
+    let mut current_date = None;
+    for hour in hourly {
+        let time = hour.weather.timestamp.with_timezone(&timezone);
+        if current_date.is_some_and(|date| date != time.date_naive()) {
+            line(
+                out,
+                format!("   {}", time.format("%d.%m").to_string().dimmed()),
+            );
+        }
+        current_date = Some(time.date_naive());
+
+        let score = format!("{:>6.1}", hour.rating.score);
+        let score = if hour.rating.score >= 7.0 {
+            score.green()
+        } else if hour.rating.score >= 5.0 {
+            score.yellow()
+        } else {
+            score.red()
+        };
+        let lighting = format!("{:<11}", hour.lighting.name());
+        let lighting = match hour.lighting {
+            LightingPhase::GoldenHour => lighting.yellow(),
+            LightingPhase::BlueHour => lighting.blue(),
+            LightingPhase::Day => lighting.normal(),
+            LightingPhase::Night => lighting.dimmed(),
+        };
+        // Главная причина снижения оценки часа
+        let concern = hour
+            .rating
+            .concerns
+            .first()
+            .map(|concern| format!("  ⚠️ {}", concern))
+            .unwrap_or_default();
+        line(
+            out,
+            format!(
+                "   {:<5}  {:>6.1}  {:>4.0}%  {:>wind_width$.1}  {:>5.0}%  {:>4.0}%  {}  {}{}",
+                time.format("%H:%M"),
+                units.temperature(hour.weather.temperature),
+                hour.weather.cloud_cover,
+                units.speed(hour.weather.wind_speed),
+                hour.weather.precipitation_probability,
+                hour.fog_probability,
+                score,
+                lighting,
+                concern.dimmed()
+            )
+            .trim_end(),
+        );
+    }
+}
+
+fn write_profiles(out: &mut String, profiles: &[ProfileScore]) {
+    let title_width = profiles
+        .iter()
+        .map(|profile| profile.title.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    line(out, "🎯 Профили съемки:");
+    for profile in profiles {
+        let best_hours = if profile.best_hours.is_empty() {
+            "Нет подходящих часов".to_string()
+        } else {
+            format!("🕐 {}", short_intervals(&profile.best_hours, 3))
+        };
+        line(
+            out,
+            format!(
+                "   {:<title_width$}  ⭐ {:>4.1}/10  {}",
+                profile.title, profile.score, best_hours
+            ),
+        );
+    }
+}
+
+fn write_astrophotography(out: &mut String, astro_output: &AstrophotographyOutput) {
+    let best_hours = if astro_output.best_hours.is_empty() {
+        "Нет подходящих часов".to_string()
+    } else {
+        format!(
+            "🕐 Лучшие часы: {}",
+            short_intervals(&astro_output.best_hours, 2)
+        )
+    };
+    let mut text = format!(
+        "🌌 Астрофото: {} | ☁️{:.0}% | {} ",
+        if astro_output.is_suitable {
+            "✅"
+        } else {
+            "❌"
+        },
+        astro_output.avg_cloud_cover,
+        best_hours
+    );
+    if let Some(recommendation) = astro_output.recommendations.first() {
+        text.push_str(&format!("| 💡 {}", recommendation));
+    }
+    line(out, text);
+}
+
+fn write_moon(out: &mut String, moon: &MoonInfo) {
+    let time = |event: Option<chrono::DateTime<chrono_tz::Tz>>| {
+        event
+            .map(|time| time.format("%H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+    line(
+        out,
+        format!(
+            "🌙 Луна: {} {} {:.0}% | ⬆️ Восход {} | ⬇️ Заход {} | 📐 {:.0}°",
+            moon.phase.emoji(),
+            moon.phase.name(),
+            moon.illumination * 100.0,
+            time(moon.moonrise),
+            time(moon.moonset),
+            moon.altitude
+        ),
+    );
+}
+
+fn write_aurora(
+    out: &mut String,
+    aurora_forecast: &AuroraForecast,
+    moon: &MoonInfo,
+    timezone: chrono_tz::Tz,
+) {
+    // Данные NOAA в UTC: время измерения выводим по часам места съемки
+    let time = |timestamp: chrono::DateTime<chrono::Utc>| {
+        timestamp
+            .with_timezone(&timezone)
+            .format("%H:%M")
+            .to_string()
+    };
+    line(
+        out,
+        format!(
+            "🌞 Солнечный ветер: 💨{:.1}км/с  📊{:.1}частиц/см³  🌡️{:.0}K  🕐{}",
+            aurora_forecast.solar_wind.speed,
+            aurora_forecast.solar_wind.density,
+            aurora_forecast.solar_wind.temperature,
+            time(aurora_forecast.solar_wind.timestamp)
+        ),
+    );
+    line(
+        out,
+        format!(
+            "🌍 Геомагнитные данные: 🧲Kp {:.1}  🌌Активность сияний {:.1}/10  🕐{}",
+            aurora_forecast.geomagnetic.kp_index,
+            aurora_forecast.geomagnetic.aurora_activity,
+            time(aurora_forecast.geomagnetic.timestamp)
+        ),
+    );
+    line(
+        out,
+        format!(
+            "🌌 Прогноз северных сияний: {}%  📊{}  💡{}",
+            (aurora_forecast.visibility_probability * 100.0) as i32,
+            aurora_forecast.intensity_level,
+            aurora_forecast.conditions
+        ),
+    );
+    if !aurora_forecast.best_viewing_hours.is_empty() {
+        line(
+            out,
+            format!(
+                "   🕐 Лучшие часы для наблюдения: {}",
+                hour_intervals(&aurora_forecast.best_viewing_hours).join(", ")
+            ),
+        );
+    }
+    line(out, format!("   🌙 {}", moon.aurora_impact()));
+}
+
+fn write_golden_hour(out: &mut String, golden_hour_output: &GoldenHourOutput) {
+    let info = &golden_hour_output.info;
+    let time = |event: Option<chrono::DateTime<chrono_tz::Tz>>| {
+        event
+            .map(|time| time.format("%H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+    match info.daylight {
+        Daylight::PolarDay => line(out, "☀️ Полярный день: Солнце не заходит"),
+        Daylight::PolarNight => line(out, "🌑 Полярная ночь: Солнце не восходит"),
+        Daylight::Normal => line(
+            out,
+            format!(
+                "🌅 Восход: {}{} | 🌆 Закат: {}{}",
+                time(info.sunrise),
+                bearing(info.sunrise_azimuth),
+                time(info.sunset),
+                bearing(info.sunset_azimuth)
+            ),
+        ),
+    }
+    if info.has_golden_hour {
+        line(
+            out,
+            format!(
+                "🌅 Золотой час утро: {} | 🌆 Золотой час вечер: {}",
+                time_window(info.golden_hour_morning_start, info.golden_hour_morning_end),
+                time_window(info.golden_hour_evening_start, info.golden_hour_evening_end)
+            ),
+        );
+    } else {
+        line(out, "🌅 Золотой час: нет в эти сутки");
+    }
+    if info.has_blue_hour {
+        line(
+            out,
+            format!(
+                "🌅 Синий час утро: {} | 🌆 Синий час вечер: {}",
+                time_window(info.blue_hour_morning_start, info.blue_hour_morning_end),
+                time_window(info.blue_hour_evening_start, info.blue_hour_evening_end)
+            ),
+        );
+    } else {
+        line(out, "🌅 Синий час: нет в эти сутки");
+    }
+    write_twilight(out, &info.twilight);
+    line(
+        out,
+        format!(
+            "💡 Текущие условия освещения: {}",
+            golden_hour_output.current_condition
+        ),
+    );
+}
+
+/// Прогноз яркости восходов и закатов, по строке на событие
+fn write_sky_fire(out: &mut String, predictions: &[SkyFirePrediction]) {
+    for prediction in predictions {
+        line(
+            out,
+            format!(
+                "🔥 {} {}: ⭐ {:.1}/10, {} - {}",
+                prediction.event.name(),
+                prediction.time.format("%H:%M"),
+                prediction.score,
+                prediction.quality.name(),
+                prediction.reasons.join("; ")
+            ),
+        );
+    }
+}
+
+/// Направление " (ЮВ, 128°)" или пустая строка, если азимута нет
+fn bearing(azimuth: Option<f64>) -> String {
+    azimuth
+        .map(|azimuth| format!(" ({}, {:.0}°)", compass_point(azimuth), azimuth))
+        .unwrap_or_default()
+}
+
+/// Интервал "HH:MM-HH:MM" или "нет", если он пустой
+fn time_window(
+    start: chrono::DateTime<chrono_tz::Tz>,
+    end: chrono::DateTime<chrono_tz::Tz>,
+) -> String {
+    if start < end {
+        format!("{}-{}", start.format("%H:%M"), end.format("%H:%M"))
+    } else {
+        "нет".to_string()
+    }
+}
+
+fn write_twilight(out: &mut String, twilight: &TwilightInfo) {
+    let time = |event: Option<chrono::DateTime<chrono_tz::Tz>>| {
+        event
+            .map(|time| time.format("%H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let darkness = twilight
+        .darkness
+        .as_ref()
+        .map(|window| {
+            format!(
+                "{}-{}",
+                window.start.format("%H:%M"),
+                window.end.format("%H:%M")
+            )
+        })
+        .unwrap_or_else(|| "нет".to_string());
+    line(
+        out,
+        format!(
+            "🌃 Сумерки вечер: гражданские {} | навигационные {} | астрономические {} | 🌌 Темнота: {}",
+            time(twilight.civil.dusk),
+            time(twilight.nautical.dusk),
+            time(twilight.astronomical.dusk),
+            darkness
+        ),
+    );
+}
+
+fn write_tips(out: &mut String, tips_output: &PhotographyTipsOutput) {
+    write_numbered(
+        out,
+        "\n📷 РЕКОМЕНДАЦИИ ПО ОБОРУДОВАНИЮ:",
+        &tips_output.equipment_recommendations,
+    );
+    write_numbered(out, "\n🎯 СОВЕТЫ ПО СЪЕМКЕ:", &tips_output.shooting_tips);
+    write_numbered(
+        out,
+        "\n📍 РЕКОМЕНДАЦИИ ПО ЛОКАЦИЯМ:",
+        &tips_output.location_suggestions,
+    );
+    write_numbered(
+        out,
+        "\n⚙️ ТЕХНИЧЕСКИЕ НАСТРОЙКИ:",
+        &tips_output.technical_settings,
+    );
+
+    line(
+        out,
+        format!("\n{}", "=== ОБЩИЕ РЕКОМЕНДАЦИИ ===".bold().blue()),
+    );
+    for (i, tip) in tips_output.general_recommendations.iter().enumerate() {
+        line(out, format!("{}. {}", i + 1, tip));
+    }
+}
+
+fn write_numbered(out: &mut String, title: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    line(out, title);
+    for (i, item) in items.iter().enumerate() {
+        line(out, format!("{}. {}", i + 1, item));
+    }
+}
+
+/// Первые `limit` интервалов часов через пробел и число оставшихся
+///
+/// Пример: "06:00-08:00 12:00 и еще 2".
+fn short_intervals(hours: &[chrono::DateTime<chrono_tz::Tz>], limit: usize) -> String {
+    let intervals = time_intervals(hours);
+    let shown = intervals[..intervals.len().min(limit)].join(" ");
+    match intervals.len().saturating_sub(limit) {
+        0 => shown,
+        rest => format!("{} и еще {}", shown, rest),
+    }
+}
+
+/// Сжимает часы в интервалы, сгруппированные по дням
+///
+/// Часы идут в хронологическом порядке. Если они относятся к нескольким
+/// дням, первый интервал каждого дня начинается с даты:
+/// "15.01 05:00-21:00 16.01 00:00".
+pub fn time_intervals(hours: &[chrono::DateTime<chrono_tz::Tz>]) -> Vec<String> {
+    let mut days: Vec<(chrono::NaiveDate, Vec<usize>)> = Vec::new();
+    for hour in hours {
+        let date = hour.date_naive();
+        match days.last_mut() {
+            Some((last_date, day_hours)) if *last_date == date => {
+                day_hours.push(hour.hour() as usize)
+            }
+            _ => days.push((date, vec![hour.hour() as usize])),
+        }
+    }
+
+    let several_days = days.len() > 1;
+    days.into_iter()
+        .flat_map(|(date, day_hours)| {
+            let mut intervals = hour_intervals(&day_hours);
+            if several_days {
+                intervals[0] = format!("{} {}", date.format("%d.%m"), intervals[0]);
+            }
+            intervals
+        })
+        .collect()
+}
+
+/// Сжимает отсортированные часы в интервалы вида `06:00-08:00`
+pub fn hour_intervals(hours: &[usize]) -> Vec<String> {
+    let Some((&first, rest)) = hours.split_first() else {
+        return Vec::new();
+    };
+    let mut intervals = Vec::new();
+    let mut start = first;
+    let mut end = first;
+
+    for &hour in rest {
+        if hour == end + 1 {
+            end = hour;
+        } else {
+            intervals.push((start, end));
+            start = hour;
+            end = hour;
+        }
+    }
+    intervals.push((start, end));
+
+    intervals
+        .into_iter()
+        .map(|(start, end)| {
+            if start == end {
+                format!("{:02}:00", start)
+            } else {
+                format!("{:02}:00-{:02}:00", start, end)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::location::Location;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::Moscow;
+    use pretty_assertions::assert_eq;
+
+    fn moscow_hours(day: u32, hours: &[u32]) -> Vec<chrono::DateTime<chrono_tz::Tz>> {
+        hours
+            .iter()
+            .map(|&hour| Moscow.with_ymd_and_hms(2024, 1, day, hour, 0, 0).unwrap())
+            .collect()
+    }
+
+    async fn demo_output() -> DashboardOutput {
+        crate::generate_dashboard_output(
+            &Config::default(),
+            &Location::new("Moscow", 55.7558, 37.6176),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_render_text_sections() {
+        let output = demo_output().await;
+
+        let full = render_text(&output, &DashboardSection::ALL, Units::Metric);
+        assert!(full.contains("ФОТОГРАФИЧЕСКИЙ ДАШБОРД"));
+        assert!(full.contains("🌅 Восход:"));
+        assert!(full.contains("ОБЩИЕ РЕКОМЕНДАЦИИ"));
+        assert!(full.contains("🌙 Луна:"));
+
+        let golden_only = render_text(&output, &[DashboardSection::GoldenHour], Units::Metric);
+        assert!(!golden_only.contains("ФОТОГРАФИЧЕСКИЙ ДАШБОРД"));
+        assert!(golden_only.contains("ДЕТАЛЬНАЯ ИНФОРМАЦИЯ"));
+        assert!(golden_only.contains("🌅 Восход:"));
+        assert!(!golden_only.contains("🌤️ Погода"));
+        assert!(!golden_only.contains("🌙 Луна"));
+        assert!(!golden_only.contains("СОВЕТЫ ДЛЯ ФОТОГРАФОВ"));
+
+        let tips_only = render_text(&output, &[DashboardSection::Tips], Units::Metric);
+        assert!(!tips_only.contains("ДЕТАЛЬНАЯ ИНФОРМАЦИЯ"));
+        assert!(tips_only.contains("СОВЕТЫ ДЛЯ ФОТОГРАФОВ"));
+    }
+
+    #[tokio::test]
+    async fn test_render_text_units() {
+        let output = demo_output().await;
+
+        let metric = render_text(&output, &[DashboardSection::Weather], Units::Metric);
+        assert!(metric.contains("°C"));
+        assert!(metric.contains("м/с"));
+
+        let imperial = render_text(&output, &[DashboardSection::Weather], Units::Imperial);
+        assert!(imperial.contains("°F"));
+        assert!(imperial.contains("миль/ч"));
+        let current = imperial
+            .lines()
+            .find(|line| line.contains("Погода:"))
+            .unwrap();
+        assert!(!current.contains("°C"));
+    }
+
+    #[tokio::test]
+    async fn test_render_timeline() {
+        let output = demo_output().await;
+        let hours = output.weather_output.as_option().unwrap().hourly.len();
+
+        let text = render_text(&output, &[DashboardSection::Timeline], Units::Metric);
+        assert!(text.contains("🕒 Почасовой прогноз"));
+        assert!(!text.contains("🌤️ Погода"));
+        let rows = text
+            .lines()
+            .filter(|line| line.trim_start().chars().nth(2) == Some(':'))
+            .count();
+        assert_eq!(rows, hours);
+
+        // Почасовой прогноз не выводится по умолчанию
+        let default = render_text(&output, &DashboardSection::DEFAULT, Units::Metric);
+        assert!(!default.contains("🕒 Почасовой прогноз"));
+
+        // В JSON почасовой прогноз - часть секции погоды
+        let json = render_json(&output, &[DashboardSection::Timeline]).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let first = &value["weather"]["data"]["hourly"][0];
+        assert!(first["rating"]["score"].is_number());
+        assert!(first["rating"]["components"]["cloud"].is_number());
+        assert!(first["lighting"].is_string());
+        assert!(first["temperature"].is_number());
+    }
+
+    #[tokio::test]
+    async fn test_render_profiles() {
+        let output = demo_output().await;
+
+        let text = render_text(&output, &[DashboardSection::Profiles], Units::Metric);
+        assert!(text.contains("🎯 Профили съемки"));
+        for title in ["Пейзаж", "Астрофото", "Макро", "Стрит", "Дрон", "Туман"]
+        {
+            assert!(text.contains(title), "нет профиля {}", title);
+        }
+
+        let json = render_json(&output, &[DashboardSection::Profiles]).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["profiles"]["data"][0]["name"], "landscape");
+        assert!(value.get("weather").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_render_json_sections() {
+        let output = demo_output().await;
+        let json = render_json(&output, &[DashboardSection::Summary]).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert!(value["summary"].is_object());
+        assert!(value["location_name"].is_string());
+        assert!(value.get("weather").is_none());
+        assert!(value.get("moon").is_none());
+        assert!(value.get("tips").is_none());
+    }
+
+    #[test]
+    fn test_render_comparison() {
+        let moscow = Location::new("Москва", 55.7558, 37.6176);
+        let mut field = ComparisonEntry::failed(Location::new("Поле", 55.0, 37.0), "");
+        field.error = None;
+        field.weather_score = Some(8.5);
+        field.astro_suitable = Some(true);
+        field.aurora_probability = Some(0.25);
+        field.best_hours = moscow_hours(15, &[20, 21, 22]);
+        let comparison =
+            LocationComparison::new(vec![ComparisonEntry::failed(moscow, "нет сети"), field]);
+
+        let text = render_comparison_text(&comparison);
+        assert!(text.contains("СРАВНЕНИЕ ЛОКАЦИЙ"));
+        let rows: Vec<&str> = text
+            .lines()
+            .filter(|line| line.contains("Поле") || line.contains("Москва"))
+            .collect();
+        assert!(rows[0].contains("Поле"));
+        assert!(rows[0].contains("8.5/10"));
+        assert!(rows[0].contains("25%"));
+        assert!(rows[0].contains("20:00-22:00"));
+        assert!(rows[1].contains("нет сети"));
+        let best = text
+            .lines()
+            .find(|line| line.contains("Лучшая локация"))
+            .unwrap();
+        assert!(best.contains("Поле"));
+
+        let json = render_comparison(&comparison, OutputFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["entries"][0]["rank"], 1);
+        assert_eq!(value["entries"][1]["error"], "нет сети");
+    }
+
+    #[test]
+    fn test_hour_intervals() {
+        assert_eq!(
+            hour_intervals(&[6, 7, 8, 12, 18, 19]),
+            vec!["06:00-08:00", "12:00", "18:00-19:00"]
+        );
+        assert!(hour_intervals(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_time_intervals_grouped_by_day() {
+        // Сутки прогноза с 02:00: 00:00 следующего дня не сливается с утром
+        let hours = [moscow_hours(15, &[5, 6, 7, 23]), moscow_hours(16, &[0, 1])].concat();
+        assert_eq!(
+            time_intervals(&hours),
+            vec!["15.01 05:00-07:00", "23:00", "16.01 00:00-01:00"]
+        );
+        assert_eq!(
+            time_intervals(&moscow_hours(15, &[6, 7, 12])),
+            vec!["06:00-07:00", "12:00"]
+        );
+    }
+
+    #[test]
+    fn test_short_intervals_counts_hidden() {
+        assert_eq!(
+            short_intervals(&moscow_hours(15, &[6, 7, 12]), 3),
+            "06:00-07:00 12:00"
+        );
+        assert_eq!(
+            short_intervals(&moscow_hours(15, &[1, 3, 5, 7, 9]), 3),
+            "01:00 03:00 05:00 и еще 2"
+        );
+    }
+}
@@ -0,0 +1,578 @@
+//! # Scoring Module
+//!
+//! Профили оценки погоды для разных жанров съемки. Пейзажисту нужны
+//! облака для драматичного неба, макро - почти полный штиль, астрофото -
+//! ясная ночь, а любителям тумана - низкая видимость и высокая влажность.
+//!
+//! Профиль задает для каждого условия диапазон и вес: час получает полный
+//! вес условия, если значение попадает в диапазон, и линейно меньше в
+//! пределах `tolerance` за его границами. Сумма баллов приводится к шкале
+//! 0-10, поэтому веса задают только относительную важность условий.
+//!
+//! ## Основные компоненты
+//!
+//! - [`ScoringProfile`] - Диапазоны и веса условий для жанра
+//! - [`ScoreRange`] - Диапазон значения и его вес
+//! - [`ProfileScore`] - Оценка прогноза по профилю
+//! - [`builtin_profiles`] - Встроенные профили: `landscape`, `astro`, `macro`,
+//!   `street`, `drone`, `fog`
+//!
+//! ## Пример файла конфигурации
+//!
+//! ```toml
+//! # Переопределяет встроенный профиль пейзажа
+//! [profiles.landscape]
+//! title = "Пейзаж"
+//! cloud = { min = 30, max = 60, weight = 3, tolerance = 20 }
+//! wind = { max = 8, weight = 1 }
+//! lighting = { golden_hour = 4, blue_hour = 2 }
+//! ```
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::golden_hour::LightingPhase;
+//! use my_dashboard::scoring::builtin_profiles;
+//! use my_dashboard::weather::{WeatherData, WeatherDataSource};
+//!
+//! let profiles = builtin_profiles();
+//! let (_, drone) = profiles.iter().find(|(name, _)| name == "drone").unwrap();
+//!
+//! let weather = WeatherData {
+//!     temperature: 15.0,
+//!     humidity: 60.0,
+//!     wind_speed: 14.0,
+//!     cloud_cover: 20.0,
+//!     visibility: 10.0,
+//!     precipitation_probability: 0.0,
+//!     description: "ясно".to_string(),
+//!     timestamp: chrono::Utc::now(),
+//!     source: WeatherDataSource::Forecast,
+//!     cloud_layers: None,
+//!     dew_point: None,
+//! };
+//!
+//! // Сильный ветер - час не подходит для дрона
+//! assert!(drone.score_hour(&weather, LightingPhase::Day) < 7.0);
+//! ```
+
+use crate::golden_hour::{GoldenHourService, LightingPhase};
+use crate::weather::{WeatherData, WeatherForecast};
+use anyhow::{bail, Context, Result};
+use chrono::DateTime;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Оценка часа, начиная с которой час считается подходящим для съемки
+const GOOD_HOUR_SCORE: f64 = 7.0;
+
+/// Диапазон значения условия и его вес в оценке часа
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScoreRange {
+    /// Нижняя граница (без границы, если не задана)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    /// Верхняя граница (без границы, если не задана)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Баллы за значение внутри диапазона
+    pub weight: f64,
+    /// Расстояние от границы, на котором баллы линейно убывают до нуля
+    #[serde(default)]
+    pub tolerance: f64,
+}
+
+impl ScoreRange {
+    /// Диапазон без допуска за границами
+    pub fn new(min: Option<f64>, max: Option<f64>, weight: f64) -> Self {
+        Self {
+            min,
+            max,
+            weight,
+            tolerance: 0.0,
+        }
+    }
+
+    /// Задает расстояние от границы, на котором баллы убывают до нуля
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Баллы за значение `value`
+    pub fn points(&self, value: f64) -> f64 {
+        let below = self.min.map_or(0.0, |min| min - value);
+        let above = self.max.map_or(0.0, |max| value - max);
+        let distance = below.max(above).max(0.0);
+
+        if distance == 0.0 {
+            self.weight
+        } else if distance < self.tolerance {
+            self.weight * (1.0 - distance / self.tolerance)
+        } else {
+            0.0
+        }
+    }
+
+    fn validate(&self, condition: &str) -> Result<()> {
+        if self.weight < 0.0 || self.tolerance < 0.0 {
+            bail!(
+                "{}: вес и допуск не могут быть отрицательными ({} / {})",
+                condition,
+                self.weight,
+                self.tolerance
+            );
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                bail!("{}: min {} больше max {}", condition, min, max);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Диапазоны и веса условий для жанра съемки
+///
+/// Условия без диапазона не влияют на оценку. Задаются в секциях
+/// `[profiles.<название>]` файла конфигурации.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScoringProfile {
+    /// Название жанра для вывода
+    pub title: String,
+    /// Температура, °C
+    pub temperature: Option<ScoreRange>,
+    /// Скорость ветра, м/с
+    pub wind: Option<ScoreRange>,
+    /// Облачность, %
+    pub cloud: Option<ScoreRange>,
+    /// Видимость, км
+    pub visibility: Option<ScoreRange>,
+    /// Вероятность осадков, %
+    pub precipitation: Option<ScoreRange>,
+    /// Влажность, %
+    pub humidity: Option<ScoreRange>,
+    /// Баллы за условия освещения (`day`, `golden_hour`, `blue_hour`, `night`)
+    pub lighting: BTreeMap<LightingPhase, f64>,
+}
+
+impl ScoringProfile {
+    /// Создает профиль без условий
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Задает диапазон температуры
+    pub fn with_temperature(mut self, range: ScoreRange) -> Self {
+        self.temperature = Some(range);
+        self
+    }
+
+    /// Задает диапазон скорости ветра
+    pub fn with_wind(mut self, range: ScoreRange) -> Self {
+        self.wind = Some(range);
+        self
+    }
+
+    /// Задает диапазон облачности
+    pub fn with_cloud(mut self, range: ScoreRange) -> Self {
+        self.cloud = Some(range);
+        self
+    }
+
+    /// Задает диапазон видимости
+    pub fn with_visibility(mut self, range: ScoreRange) -> Self {
+        self.visibility = Some(range);
+        self
+    }
+
+    /// Задает диапазон вероятности осадков
+    pub fn with_precipitation(mut self, range: ScoreRange) -> Self {
+        self.precipitation = Some(range);
+        self
+    }
+
+    /// Задает диапазон влажности
+    pub fn with_humidity(mut self, range: ScoreRange) -> Self {
+        self.humidity = Some(range);
+        self
+    }
+
+    /// Задает баллы за условия освещения
+    pub fn with_lighting(mut self, phase: LightingPhase, weight: f64) -> Self {
+        self.lighting.insert(phase, weight);
+        self
+    }
+
+    /// Проверяет диапазоны и веса
+    pub fn validate(&self) -> Result<()> {
+        for (condition, range) in self.conditions() {
+            if let Some(range) = range {
+                range.validate(condition)?;
+            }
+        }
+        if self.lighting.values().any(|weight| *weight < 0.0) {
+            bail!("Баллы за освещение не могут быть отрицательными");
+        }
+        if self.max_points() <= 0.0 {
+            bail!("В профиле нет ни одного условия с положительным весом");
+        }
+        Ok(())
+    }
+
+    /// Оценка часа (0-10) при условиях освещения `lighting`
+    pub fn score_hour(&self, weather: &WeatherData, lighting: LightingPhase) -> f64 {
+        let values = [
+            weather.temperature,
+            weather.wind_speed,
+            weather.cloud_cover,
+            weather.visibility,
+            weather.precipitation_probability,
+            weather.humidity,
+        ];
+        let points: f64 = self
+            .conditions()
+            .iter()
+            .zip(values)
+            .filter_map(|((_, range), value)| range.map(|range| range.points(value)))
+            .sum::<f64>()
+            + self.lighting.get(&lighting).copied().unwrap_or(0.0);
+
+        let max_points = self.max_points();
+        if max_points > 0.0 {
+            points / max_points * 10.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Условия в порядке полей [`WeatherData`], используемых в оценке
+    fn conditions(&self) -> [(&'static str, Option<ScoreRange>); 6] {
+        [
+            ("temperature", self.temperature),
+            ("wind", self.wind),
+            ("cloud", self.cloud),
+            ("visibility", self.visibility),
+            ("precipitation", self.precipitation),
+            ("humidity", self.humidity),
+        ]
+    }
+
+    /// Сумма баллов идеального часа
+    fn max_points(&self) -> f64 {
+        self.conditions()
+            .iter()
+            .filter_map(|(_, range)| range.map(|range| range.weight))
+            .sum::<f64>()
+            + self.lighting.values().copied().fold(0.0, f64::max)
+    }
+}
+
+/// Встроенные профили в порядке вывода
+pub fn builtin_profiles() -> Vec<(String, ScoringProfile)> {
+    use LightingPhase::{BlueHour, Day, GoldenHour, Night};
+
+    let landscape = ScoringProfile::new("Пейзаж")
+        // Облака дают драматичное небо, сплошная облачность - серое
+        .with_cloud(ScoreRange::new(Some(20.0), Some(70.0), 3.0).with_tolerance(20.0))
+        .with_precipitation(ScoreRange::new(None, Some(20.0), 2.0).with_tolerance(20.0))
+        .with_visibility(ScoreRange::new(Some(10.0), None, 2.0).with_tolerance(5.0))
+        .with_wind(ScoreRange::new(None, Some(10.0), 1.0).with_tolerance(5.0))
+        .with_lighting(GoldenHour, 4.0)
+        .with_lighting(BlueHour, 2.0);
+    let astro = ScoringProfile::new("Астрофото")
+        .with_cloud(ScoreRange::new(None, Some(10.0), 5.0).with_tolerance(20.0))
+        .with_precipitation(ScoreRange::new(None, Some(10.0), 1.0).with_tolerance(20.0))
+        // Во влажном воздухе оптика запотевает
+        .with_humidity(ScoreRange::new(None, Some(85.0), 1.0).with_tolerance(10.0))
+        .with_wind(ScoreRange::new(None, Some(7.0), 1.0).with_tolerance(5.0))
+        .with_visibility(ScoreRange::new(Some(10.0), None, 1.0).with_tolerance(5.0))
+        .with_lighting(Night, 5.0);
+    let macro_profile = ScoringProfile::new("Макро")
+        // Малейший ветер качает объект съемки
+        .with_wind(ScoreRange::new(None, Some(2.0), 5.0).with_tolerance(3.0))
+        .with_precipitation(ScoreRange::new(None, Some(20.0), 1.0).with_tolerance(20.0))
+        .with_temperature(ScoreRange::new(Some(10.0), Some(25.0), 2.0).with_tolerance(5.0))
+        // Легкая облачность дает мягкий рассеянный свет
+        .with_cloud(ScoreRange::new(Some(40.0), None, 1.0).with_tolerance(30.0))
+        .with_lighting(GoldenHour, 2.0)
+        .with_lighting(Day, 1.0);
+    let street = ScoringProfile::new("Стрит")
+        .with_precipitation(ScoreRange::new(None, Some(30.0), 2.0).with_tolerance(30.0))
+        .with_temperature(ScoreRange::new(Some(5.0), Some(28.0), 2.0).with_tolerance(5.0))
+        .with_wind(ScoreRange::new(None, Some(10.0), 1.0).with_tolerance(5.0))
+        .with_lighting(GoldenHour, 2.0)
+        .with_lighting(BlueHour, 2.0)
+        .with_lighting(Day, 1.0);
+    let drone = ScoringProfile::new("Дрон")
+        .with_wind(ScoreRange::new(None, Some(8.0), 5.0).with_tolerance(4.0))
+        .with_precipitation(ScoreRange::new(None, Some(10.0), 3.0).with_tolerance(10.0))
+        .with_temperature(ScoreRange::new(Some(0.0), Some(35.0), 1.0).with_tolerance(5.0))
+        .with_visibility(ScoreRange::new(Some(5.0), None, 1.0).with_tolerance(3.0))
+        .with_lighting(GoldenHour, 2.0)
+        .with_lighting(Day, 1.0);
+    let fog = ScoringProfile::new("Туман и настроение")
+        .with_visibility(ScoreRange::new(None, Some(1.0), 5.0).with_tolerance(4.0))
+        .with_humidity(ScoreRange::new(Some(90.0), None, 3.0).with_tolerance(10.0))
+        .with_wind(ScoreRange::new(None, Some(3.0), 2.0).with_tolerance(3.0))
+        .with_lighting(GoldenHour, 2.0)
+        .with_lighting(BlueHour, 1.0);
+
+    vec![
+        ("landscape".to_string(), landscape),
+        ("astro".to_string(), astro),
+        ("macro".to_string(), macro_profile),
+        ("street".to_string(), street),
+        ("drone".to_string(), drone),
+        ("fog".to_string(), fog),
+    ]
+}
+
+/// Встроенные профили, дополненные профилями из конфигурации
+///
+/// Профиль конфигурации с именем встроенного заменяет его, остальные
+/// добавляются после встроенных в алфавитном порядке.
+pub fn merge_profiles(custom: &BTreeMap<String, ScoringProfile>) -> Vec<(String, ScoringProfile)> {
+    let mut profiles: Vec<(String, ScoringProfile)> = builtin_profiles()
+        .into_iter()
+        .map(|(name, profile)| {
+            let profile = custom.get(&name).cloned().unwrap_or(profile);
+            (name, profile)
+        })
+        .collect();
+    for (name, profile) in custom {
+        if !profiles.iter().any(|(builtin, _)| builtin == name) {
+            profiles.push((name.clone(), profile.clone()));
+        }
+    }
+    profiles
+}
+
+/// Проверяет профили из конфигурации
+pub fn validate_profiles(custom: &BTreeMap<String, ScoringProfile>) -> Result<()> {
+    for (name, profile) in custom {
+        profile
+            .validate()
+            .with_context(|| format!("Ошибка в профиле '{}'", name))?;
+    }
+    Ok(())
+}
+
+/// Оценка прогноза по профилю жанра
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileScore {
+    /// Имя профиля (`landscape`, `astro`, ...)
+    pub name: String,
+    /// Название жанра для вывода
+    pub title: String,
+    /// Оценка лучшего часа (0-10)
+    pub score: f64,
+    /// Начало часов с оценкой >= 7 по часам места съемки
+    pub best_hours: Vec<DateTime<Tz>>,
+}
+
+/// Оценивает прогноз по каждому профилю
+///
+/// Условия освещения каждого часа определяются `sun_service` (см.
+/// [`GoldenHourService::lighting_phase_between`]), часы выводятся в его
+/// часовом поясе.
+pub fn score_profiles(
+    forecast: &WeatherForecast,
+    profiles: &[(String, ScoringProfile)],
+    sun_service: &GoldenHourService,
+) -> Vec<ProfileScore> {
+    let timezone = sun_service.timezone();
+    let lighting: Vec<LightingPhase> = forecast
+        .hourly
+        .iter()
+        .map(|weather| {
+            sun_service.lighting_phase_between(
+                weather.timestamp,
+                weather.timestamp + chrono::Duration::hours(1),
+            )
+        })
+        .collect();
+
+    profiles
+        .iter()
+        .map(|(name, profile)| {
+            let scores: Vec<(DateTime<Tz>, f64)> = forecast
+                .hourly
+                .iter()
+                .zip(&lighting)
+                .map(|(weather, &phase)| {
+                    (
+                        weather.timestamp.with_timezone(&timezone),
+                        profile.score_hour(weather, phase),
+                    )
+                })
+                .collect();
+
+            ProfileScore {
+                name: name.clone(),
+                title: if profile.title.is_empty() {
+                    name.clone()
+                } else {
+                    profile.title.clone()
+                },
+                score: scores.iter().map(|(_, score)| *score).fold(0.0, f64::max),
+                best_hours: scores
+                    .iter()
+                    .filter(|(_, score)| *score >= GOOD_HOUR_SCORE)
+                    .map(|(hour, _)| *hour)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherDataSource;
+    use chrono::{TimeZone, Timelike, Utc};
+    use chrono_tz::Europe::Moscow;
+    use pretty_assertions::assert_eq;
+
+    fn weather(wind_speed: f64, cloud_cover: f64, visibility: f64, humidity: f64) -> WeatherData {
+        WeatherData {
+            temperature: 15.0,
+            humidity,
+            wind_speed,
+            cloud_cover,
+            visibility,
+            precipitation_probability: 0.0,
+            description: "тест".to_string(),
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap(),
+            source: WeatherDataSource::Forecast,
+            cloud_layers: None,
+            dew_point: None,
+        }
+    }
+
+    fn profile(name: &str) -> ScoringProfile {
+        builtin_profiles()
+            .into_iter()
+            .find(|(builtin, _)| builtin == name)
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    fn test_score_range_points() {
+        let range = ScoreRange::new(Some(20.0), Some(70.0), 3.0).with_tolerance(20.0);
+        assert_eq!(range.points(50.0), 3.0);
+        assert_eq!(range.points(80.0), 1.5);
+        assert_eq!(range.points(10.0), 1.5);
+        assert_eq!(range.points(95.0), 0.0);
+
+        // Без допуска баллы только внутри диапазона
+        let strict = ScoreRange::new(None, Some(2.0), 5.0);
+        assert_eq!(strict.points(2.0), 5.0);
+        assert_eq!(strict.points(2.1), 0.0);
+    }
+
+    #[test]
+    fn test_profiles_prefer_different_conditions() {
+        let calm_clear = weather(0.5, 0.0, 20.0, 60.0);
+        let windy_cloudy = weather(9.0, 50.0, 20.0, 60.0);
+        let foggy = weather(0.5, 100.0, 0.3, 98.0);
+
+        // Пейзаж любит облака в золотой час, астрофото - ясную ночь
+        let landscape = profile("landscape");
+        assert!(
+            landscape.score_hour(&windy_cloudy, LightingPhase::GoldenHour)
+                > landscape.score_hour(&calm_clear, LightingPhase::GoldenHour)
+        );
+        let astro = profile("astro");
+        assert_eq!(astro.score_hour(&calm_clear, LightingPhase::Night), 10.0);
+        assert!(astro.score_hour(&calm_clear, LightingPhase::Day) < 7.0);
+
+        // Макро страдает от ветра, туман - от хорошей видимости
+        let macro_profile = profile("macro");
+        assert!(
+            macro_profile.score_hour(&windy_cloudy, LightingPhase::Day)
+                < macro_profile.score_hour(&foggy, LightingPhase::Day)
+        );
+        let fog = profile("fog");
+        assert!(fog.score_hour(&foggy, LightingPhase::GoldenHour) >= 9.0);
+        assert!(fog.score_hour(&calm_clear, LightingPhase::GoldenHour) < 5.0);
+    }
+
+    #[test]
+    fn test_merge_and_validate_profiles() {
+        let custom: BTreeMap<String, ScoringProfile> = toml::from_str(
+            r#"
+            [landscape]
+            title = "Мой пейзаж"
+            cloud = { min = 30, max = 60, weight = 3 }
+
+            [underwater]
+            visibility = { min = 10, weight = 1 }
+            lighting = { day = 2 }
+            "#,
+        )
+        .unwrap();
+        validate_profiles(&custom).unwrap();
+
+        let profiles = merge_profiles(&custom);
+        let names: Vec<&str> = profiles.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "landscape",
+                "astro",
+                "macro",
+                "street",
+                "drone",
+                "fog",
+                "underwater"
+            ]
+        );
+        assert_eq!(profiles[0].1.title, "Мой пейзаж");
+        assert_eq!(profiles[6].1.lighting[&LightingPhase::Day], 2.0);
+
+        // Пустой профиль и перепутанные границы - ошибка
+        let invalid: BTreeMap<String, ScoringProfile> = toml::from_str(
+            r#"
+            [empty]
+            title = "Пусто"
+            "#,
+        )
+        .unwrap();
+        assert!(validate_profiles(&invalid).is_err());
+        let invalid: BTreeMap<String, ScoringProfile> =
+            toml::from_str("[bad]\nwind = { min = 5, max = 1, weight = 1 }").unwrap();
+        assert!(validate_profiles(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_score_profiles() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let forecast = WeatherForecast {
+            hourly: (0..24)
+                .map(|hour| WeatherData {
+                    timestamp: start + chrono::Duration::hours(hour),
+                    ..weather(0.5, 0.0, 20.0, 60.0)
+                })
+                .collect(),
+        };
+        let sun_service = GoldenHourService::new(55.7558, 37.6176).with_timezone(Moscow);
+
+        let scores = score_profiles(&forecast, &builtin_profiles(), &sun_service);
+        assert_eq!(scores.len(), 6);
+        let astro = scores.iter().find(|score| score.name == "astro").unwrap();
+        assert_eq!(astro.title, "Астрофото");
+        assert_eq!(astro.score, 10.0);
+        // Ясная январская ночь: вечер и утро подходят, середина дня - нет
+        let hours: Vec<u32> = astro.best_hours.iter().map(|hour| hour.hour()).collect();
+        assert!(hours.contains(&22));
+        assert!(!hours.contains(&13));
+    }
+}
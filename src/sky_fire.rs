@@ -0,0 +1,348 @@
+//! # Sky Fire Module
+//!
+//! Прогноз яркости восхода и заката ("небесного огня") по облачности на
+//! разных ярусах.
+//!
+//! Солнце у горизонта подсвечивает облака снизу, поэтому самые яркие
+//! краски дают средние и верхние облака, закрывающие примерно половину
+//! неба: им есть что подсветить, но свет до них доходит. Низкие облака у
+//! горизонта перекрывают Солнце, и закат гаснет, не начавшись. Оценка
+//! считается по часам прогноза, попадающим в окно события: утром от начала
+//! синего часа до конца золотого, вечером от начала золотого до конца синего.
+//!
+//! ## Основные компоненты
+//!
+//! - [`SkyEvent`] - Восход или закат
+//! - [`SkyFireQuality`] - Ожидаемая яркость красок
+//! - [`SkyFirePrediction`] - Прогноз для одного восхода или заката
+//! - [`predict_sky_fire`] - Прогноз для всех восходов и закатов в пределах прогноза погоды
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::sky_fire::{sky_fire_score, SkyFireQuality};
+//! use my_dashboard::weather::CloudLayers;
+//!
+//! // Половина неба в высоких облаках, у горизонта чисто
+//! let layers = CloudLayers { low: 0.0, mid: 20.0, high: 50.0 };
+//! let (score, _) = sky_fire_score(Some(layers), 60.0, 0.0);
+//!
+//! assert_eq!(SkyFireQuality::from_score(score), SkyFireQuality::Spectacular);
+//! ```
+
+use crate::golden_hour::{Daylight, GoldenHourInfo};
+use crate::weather::{CloudLayers, WeatherData, WeatherForecast};
+use chrono::{DateTime, Duration};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+/// Восход или закат
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkyEvent {
+    Sunrise,
+    Sunset,
+}
+
+impl SkyEvent {
+    /// Название события для отображения
+    pub fn name(self) -> &'static str {
+        match self {
+            SkyEvent::Sunrise => "Восход",
+            SkyEvent::Sunset => "Закат",
+        }
+    }
+}
+
+/// Ожидаемая яркость красок на восходе или закате
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkyFireQuality {
+    /// Серое небо или чистое без подсветки облаков
+    Dull,
+    /// Небольшая подсветка
+    Fair,
+    /// Яркие краски
+    Good,
+    /// Небо "горит"
+    Spectacular,
+}
+
+impl SkyFireQuality {
+    /// Качество по оценке 0-10
+    pub fn from_score(score: f64) -> Self {
+        if score >= 8.0 {
+            SkyFireQuality::Spectacular
+        } else if score >= 6.0 {
+            SkyFireQuality::Good
+        } else if score >= 4.0 {
+            SkyFireQuality::Fair
+        } else {
+            SkyFireQuality::Dull
+        }
+    }
+
+    /// Название качества для отображения
+    pub fn name(self) -> &'static str {
+        match self {
+            SkyFireQuality::Dull => "тусклый",
+            SkyFireQuality::Fair => "умеренный",
+            SkyFireQuality::Good => "яркий",
+            SkyFireQuality::Spectacular => "огненный",
+        }
+    }
+}
+
+/// Прогноз яркости одного восхода или заката
+#[derive(Debug, Clone, Serialize)]
+pub struct SkyFirePrediction {
+    pub event: SkyEvent,
+    /// Время восхода или заката
+    pub time: DateTime<Tz>,
+    /// Оценка яркости (0-10)
+    pub score: f64,
+    pub quality: SkyFireQuality,
+    /// Средняя облачность по ярусам в окне события, если источник ее дает
+    pub cloud_layers: Option<CloudLayers>,
+    /// Объяснение оценки
+    pub reasons: Vec<String>,
+}
+
+/// Прогноз для всех восходов и закатов, попадающих в прогноз погоды
+///
+/// `golden_hours` - золотой и синий час для дней прогноза (см.
+/// [`GoldenHourService::golden_hours_between`](crate::golden_hour::GoldenHourService::golden_hours_between)).
+/// Событие учитывается, если само время восхода или заката попадает в
+/// прогноз; в полярный день и полярную ночь событий нет.
+pub fn predict_sky_fire(
+    forecast: &WeatherForecast,
+    golden_hours: &[GoldenHourInfo],
+) -> Vec<SkyFirePrediction> {
+    let (Some(first), Some(last)) = (forecast.hourly.first(), forecast.hourly.last()) else {
+        return Vec::new();
+    };
+    let (start, end) = (first.timestamp, last.timestamp + Duration::hours(1));
+
+    let mut predictions = Vec::new();
+    for info in golden_hours
+        .iter()
+        .filter(|info| info.daylight == Daylight::Normal)
+    {
+        let events = [
+            (
+                SkyEvent::Sunrise,
+                info.sunrise,
+                info.blue_hour_morning_start,
+                info.golden_hour_morning_end,
+            ),
+            (
+                SkyEvent::Sunset,
+                info.sunset,
+                info.golden_hour_evening_start,
+                info.blue_hour_evening_end,
+            ),
+        ];
+        for (event, time, window_start, window_end) in events {
+            let Some(time) = time.filter(|time| *time >= start && *time < end) else {
+                continue;
+            };
+            let hours: Vec<&WeatherData> = forecast
+                .hourly
+                .iter()
+                .filter(|weather| {
+                    weather.timestamp < window_end
+                        && weather.timestamp + Duration::hours(1) > window_start
+                })
+                .collect();
+            if let Some(prediction) = predict_event(event, time, &hours) {
+                predictions.push(prediction);
+            }
+        }
+    }
+    predictions.sort_by_key(|prediction| prediction.time);
+    predictions
+}
+
+/// Прогноз по часам окна события
+fn predict_event(
+    event: SkyEvent,
+    time: DateTime<Tz>,
+    hours: &[&WeatherData],
+) -> Option<SkyFirePrediction> {
+    if hours.is_empty() {
+        return None;
+    }
+    let count = hours.len() as f64;
+    let average = |value: fn(&WeatherData) -> f64| {
+        hours.iter().map(|weather| value(weather)).sum::<f64>() / count
+    };
+
+    // Ярусы усредняем, только если они известны для всех часов окна
+    let layers: Option<Vec<CloudLayers>> =
+        hours.iter().map(|weather| weather.cloud_layers).collect();
+    let cloud_layers = layers.map(|layers| CloudLayers {
+        low: layers.iter().map(|layer| layer.low).sum::<f64>() / count,
+        mid: layers.iter().map(|layer| layer.mid).sum::<f64>() / count,
+        high: layers.iter().map(|layer| layer.high).sum::<f64>() / count,
+    });
+    let cloud_cover = average(|weather| weather.cloud_cover);
+    let precipitation = hours
+        .iter()
+        .map(|weather| weather.precipitation_probability)
+        .fold(0.0, f64::max);
+
+    let (score, reasons) = sky_fire_score(cloud_layers, cloud_cover, precipitation);
+    Some(SkyFirePrediction {
+        event,
+        time,
+        score,
+        quality: SkyFireQuality::from_score(score),
+        cloud_layers,
+        reasons,
+    })
+}
+
+/// Оценка яркости восхода или заката (0-10) с объяснением
+///
+/// Без данных по ярусам оценка строится по общей облачности: холстом
+/// считаются все облака, а сплошная облачность выше 70% - низкой.
+pub fn sky_fire_score(
+    cloud_layers: Option<CloudLayers>,
+    cloud_cover: f64,
+    precipitation_probability: f64,
+) -> (f64, Vec<String>) {
+    let mut reasons = Vec::new();
+    let (low, canvas) = match cloud_layers {
+        Some(layers) => (layers.low, layers.mid.max(layers.high)),
+        None => {
+            reasons.push("Нет данных по ярусам облачности, оценка по общей".to_string());
+            (
+                (cloud_cover - 70.0).max(0.0) / 30.0 * 100.0,
+                cloud_cover.min(100.0),
+            )
+        }
+    };
+
+    // Лучше всего подсвечиваются облака, закрывающие около половины неба
+    let canvas_factor = 1.0 - (canvas - 50.0).abs() / 50.0;
+    let mut score = 2.0 + 8.0 * canvas_factor.max(0.0);
+    // Низкие облака перекрывают Солнце у горизонта
+    score *= 1.0 - 0.9 * low / 100.0;
+    // Дождь гасит краски
+    score *= 1.0 - 0.5 * precipitation_probability / 100.0;
+
+    if canvas < 10.0 {
+        reasons.push("Чистое небо: мягкие краски без подсветки облаков".to_string());
+    } else if canvas <= 80.0 {
+        reasons.push(format!(
+            "Средние и верхние облака {:.0}% подсветятся Солнцем",
+            canvas
+        ));
+    } else {
+        reasons.push(format!("Сплошные средние и верхние облака {:.0}%", canvas));
+    }
+    if low > 50.0 {
+        reasons.push(format!(
+            "Низкая облачность {:.0}% закроет Солнце у горизонта",
+            low
+        ));
+    }
+    if precipitation_probability >= 40.0 {
+        reasons.push(format!(
+            "Вероятность осадков {:.0}%",
+            precipitation_probability
+        ));
+    }
+
+    (score.clamp(0.0, 10.0), reasons)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::golden_hour::GoldenHourService;
+    use crate::weather::WeatherDataSource;
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Europe::Moscow;
+    use pretty_assertions::assert_eq;
+
+    fn layers(low: f64, mid: f64, high: f64) -> Option<CloudLayers> {
+        Some(CloudLayers { low, mid, high })
+    }
+
+    fn forecast(cloud_layers: Option<CloudLayers>) -> WeatherForecast {
+        let start = Utc.with_ymd_and_hms(2024, 6, 21, 0, 0, 0).unwrap();
+        WeatherForecast {
+            hourly: (0..24)
+                .map(|hour| WeatherData {
+                    temperature: 15.0,
+                    humidity: 60.0,
+                    wind_speed: 2.0,
+                    cloud_cover: 50.0,
+                    visibility: 10.0,
+                    precipitation_probability: 0.0,
+                    description: "тест".to_string(),
+                    timestamp: start + Duration::hours(hour),
+                    source: WeatherDataSource::Forecast,
+                    cloud_layers,
+                    dew_point: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_mid_and_high_clouds_light_up() {
+        let (fire, _) = sky_fire_score(layers(0.0, 50.0, 30.0), 60.0, 0.0);
+        let (clear, _) = sky_fire_score(layers(0.0, 0.0, 0.0), 0.0, 0.0);
+        let (overcast, _) = sky_fire_score(layers(0.0, 100.0, 100.0), 100.0, 0.0);
+
+        assert_eq!(fire, 10.0);
+        assert_eq!(clear, 2.0);
+        assert_eq!(overcast, 2.0);
+    }
+
+    #[test]
+    fn test_low_clouds_block_the_sun() {
+        let (score, reasons) = sky_fire_score(layers(100.0, 50.0, 30.0), 100.0, 0.0);
+
+        assert!((score - 1.0).abs() < 1e-9);
+        assert_eq!(SkyFireQuality::from_score(score), SkyFireQuality::Dull);
+        assert!(reasons
+            .iter()
+            .any(|reason| reason.contains("Низкая облачность")));
+    }
+
+    #[test]
+    fn test_total_cloud_cover_without_layers() {
+        let (score, reasons) = sky_fire_score(None, 50.0, 0.0);
+
+        assert_eq!(score, 10.0);
+        assert!(reasons[0].contains("Нет данных по ярусам"));
+    }
+
+    #[test]
+    fn test_predicts_sunrise_and_sunset_within_forecast() {
+        let service = GoldenHourService::new(55.7558, 37.6176).with_timezone(Moscow);
+        let forecast = forecast(layers(0.0, 50.0, 20.0));
+        let golden_hours = service
+            .golden_hours_between(forecast.hourly[0].timestamp, forecast.hourly[23].timestamp);
+
+        let predictions = predict_sky_fire(&forecast, &golden_hours);
+
+        let events: Vec<SkyEvent> = predictions.iter().map(|p| p.event).collect();
+        assert_eq!(events, vec![SkyEvent::Sunrise, SkyEvent::Sunset]);
+        assert_eq!(predictions[1].quality, SkyFireQuality::Spectacular);
+        assert_eq!(predictions[1].cloud_layers, layers(0.0, 50.0, 20.0));
+    }
+
+    #[test]
+    fn test_no_events_in_polar_day() {
+        let service = GoldenHourService::new(78.2232, 15.6267);
+        let forecast = forecast(None);
+        let golden_hours = service
+            .golden_hours_between(forecast.hourly[0].timestamp, forecast.hourly[23].timestamp);
+
+        assert!(predict_sky_fire(&forecast, &golden_hours).is_empty());
+    }
+}
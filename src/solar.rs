@@ -27,6 +27,8 @@
 //! }
 //! ```
 
+use crate::cache::{CacheSource, ResponseCache};
+use crate::http;
 use crate::{get_current_utc_time, is_demo_mode};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -37,7 +39,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Содержит информацию о скорости, плотности и температуре солнечного ветра,
 /// полученную от NOAA SWEPAM API.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SolarWindData {
     /// Скорость солнечного ветра в км/с
     pub speed: f64,
@@ -55,7 +57,7 @@ pub struct SolarWindData {
 ///
 /// Содержит информацию о геомагнитной активности и Kp индексе,
 /// полученную от NOAA Planetary K-index API.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeomagneticData {
     /// Геомагнитный Kp индекс (0-9)
     pub kp_index: f64,
@@ -71,7 +73,7 @@ pub struct GeomagneticData {
 ///
 /// Содержит информацию о вероятности появления северных сияний,
 /// уровне интенсивности и лучшем времени для наблюдения.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AuroraForecast {
     /// Информация о солнечном ветре
     pub solar_wind: SolarWindData,
@@ -110,6 +112,11 @@ struct KpRecord {
     kp_index: f64,
 }
 
+/// Выполняет запрос к NOAA SWPC и возвращает тело ответа
+async fn fetch_noaa_text(url: &str) -> Result<String> {
+    Ok(http::get_text(url).await?)
+}
+
 /// Получает данные о солнечном ветре от NOAA SWEPAM API
 async fn fetch_solar_wind_data() -> Result<SolarWindData> {
     // Проверяем DEMO режим
@@ -128,17 +135,11 @@ async fn fetch_solar_wind_data() -> Result<SolarWindData> {
 
     debug!("🌞 API ЗАПРОС: NOAA SWEPAM API (солнечный ветер)");
     let url = "https://services.swpc.noaa.gov/json/ace/swepam/ace_swepam_1h.json";
-    let response = reqwest::get(url).await?;
-
-    if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "HTTP {}: {}",
-            response.status(),
-            response.text().await?
-        ));
-    }
-
-    let text = response.text().await?;
+    let text = ResponseCache::from_env()
+        .get_or_fetch(CacheSource::SolarWind, "noaa-swepam-1h", || {
+            fetch_noaa_text(url)
+        })
+        .await?;
 
     // Попробуем парсить JSON с более подробной обработкой ошибок
     let all_records: Vec<SwepamRecord> = match serde_json::from_str::<Vec<SwepamRecord>>(&text) {
@@ -203,17 +204,11 @@ async fn fetch_geomagnetic_data() -> Result<GeomagneticData> {
 
     debug!("🌍 API ЗАПРОС: NOAA Planetary K-index API (геомагнитные данные)");
     let url = "https://services.swpc.noaa.gov/json/planetary_k_index_1m.json";
-    let response = reqwest::get(url).await?;
-
-    if !response.status().is_success() {
-        return Err(anyhow::anyhow!(
-            "HTTP {}: {}",
-            response.status(),
-            response.text().await?
-        ));
-    }
-
-    let text = response.text().await?;
+    let text = ResponseCache::from_env()
+        .get_or_fetch(CacheSource::Geomagnetic, "noaa-kp-1m", || {
+            fetch_noaa_text(url)
+        })
+        .await?;
 
     // Попробуем парсить JSON с более подробной обработкой ошибок
     let all_records: Vec<KpRecord> = match serde_json::from_str::<Vec<KpRecord>>(&text) {
@@ -292,8 +287,9 @@ fn calculate_aurora_activity(solar_wind: &SolarWindData, geomagnetic: &Geomagnet
 }
 
 pub async fn predict_aurora() -> Result<AuroraForecast> {
-    let solar_wind = fetch_solar_wind_data().await?;
-    let geomagnetic = fetch_geomagnetic_data().await?;
+    // Оба запроса к NOAA выполняются параллельно
+    let (solar_wind, geomagnetic) =
+        tokio::try_join!(fetch_solar_wind_data(), fetch_geomagnetic_data())?;
 
     // Используем функцию calculate_aurora_activity для расчета активности
     let activity = calculate_aurora_activity(&solar_wind, &geomagnetic);
@@ -9,6 +9,8 @@
 //! - [`WeatherService`] - Сервис для получения данных о погоде
 //! - [`WeatherForecast`] - Структура прогноза погоды
 //! - [`WeatherAnalysis`] - Результат анализа погодных условий
+//! - [`CloudLayers`] - Облачность по ярусам
+//! - [`HourScore`] - Оценка отдельного часа по составляющим с объяснением
 //! - [`AstrophotographyAnalysis`] - Анализ условий для астрофотографии
 //!
 //! ## Пример использования
@@ -26,19 +28,55 @@
 //! // #[tokio::main]
 //! // async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! //     let forecast = weather_service.get_weather_forecast().await?;
-//! //     let analysis = analyze_weather_for_photography(&forecast);
+//! //     let analysis = analyze_weather_for_photography(&forecast, &[], chrono_tz::Europe::Moscow);
 //! //     println!("Оценка условий: {}/10", analysis.overall_score);
 //! //     Ok(())
 //! // }
 //! ```
 
+use crate::config::DEFAULT_LANGUAGE;
+use crate::golden_hour::{DarknessWindow, GoldenHourInfo};
+use crate::moon::MoonService;
+use crate::weather_provider::{
+    create_provider, provider_kind_from_env, CityCoordinates, WeatherProvider,
+};
 use crate::{get_current_utc_time, is_demo_mode};
-use anyhow::Result;
-use chrono::{DateTime, Timelike, Utc};
-use log::{debug, info, warn};
+use anyhow::{bail, Result};
+use chrono::{DateTime, NaiveDate, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use log::{debug, warn};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Количество часов в почасовом прогнозе на один день
+pub const FORECAST_HOURS: i64 = 24;
+
+/// Максимальный горизонт планирования в днях
+pub const MAX_FORECAST_DAYS: u32 = 7;
+
+/// Происхождение почасовых данных о погоде
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WeatherDataSource {
+    /// Фактическое наблюдение (текущая погода)
+    Observed,
+    /// Прогноз API, интерполированный до часа
+    #[default]
+    Forecast,
+    /// Синтетические данные (резервный генератор или DEMO режим)
+    Synthetic,
+}
+
+/// Облачность по ярусам, %
+///
+/// Нижний ярус (до 2 км) закрывает Солнце у горизонта, средний (2-6 км) и
+/// верхний (выше 6 км) подсвечиваются на закате и восходе.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CloudLayers {
+    pub low: f64,
+    pub mid: f64,
+    pub high: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeatherData {
     pub temperature: f64,
     pub humidity: f64,
@@ -48,6 +86,17 @@ pub struct WeatherData {
     pub precipitation_probability: f64,
     pub description: String,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub source: WeatherDataSource,
+    /// Облачность по ярусам, если источник ее предоставляет
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloud_layers: Option<CloudLayers>,
+    /// Точка росы, °C, если источник ее предоставляет
+    ///
+    /// Без нее прогноз тумана оценивает точку росы по температуре и
+    /// влажности (см. [`crate::fog::dew_point`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dew_point: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -55,46 +104,100 @@ pub struct WeatherForecast {
     pub hourly: Vec<WeatherData>,
 }
 
-// Структуры для парсинга ответа OpenWeatherMap API
-#[derive(Debug, Deserialize)]
-struct CurrentWeatherResponse {
-    main: CurrentWeatherMain,
-    wind: CurrentWeatherWind,
-    clouds: CurrentWeatherClouds,
-    visibility: f64,
-    weather: Vec<OpenWeatherCondition>,
-}
-
-#[derive(Debug, Deserialize)]
-struct CurrentWeatherMain {
-    temp: f64,
-    humidity: f64,
-}
-
-#[derive(Debug, Deserialize)]
-struct CurrentWeatherWind {
-    speed: f64,
-}
-
-#[derive(Debug, Deserialize)]
-struct CurrentWeatherClouds {
-    all: f64,
-}
+impl WeatherForecast {
+    /// Возвращает первые [`FORECAST_HOURS`] часов прогноза
+    ///
+    /// Сводка дашборда описывает ближайшие сутки, даже если прогноз
+    /// загружен на несколько дней вперед.
+    pub fn first_day(&self) -> WeatherForecast {
+        WeatherForecast {
+            hourly: self
+                .hourly
+                .iter()
+                .take(FORECAST_HOURS as usize)
+                .cloned()
+                .collect(),
+        }
+    }
 
-#[derive(Debug, Deserialize)]
-struct OpenWeatherCondition {
-    description: String,
+    /// Возвращает прогноз, начиная с часа, в который попадает `time`
+    ///
+    /// Ошибка, если прогноз не покрывает этот момент (он в прошлом
+    /// или дальше горизонта прогноза); время в ошибке выводится в поясе `time`.
+    pub fn starting_at<Z: TimeZone>(self, time: DateTime<Z>) -> Result<WeatherForecast>
+    where
+        Z::Offset: std::fmt::Display,
+    {
+        let requested = time.format("%d.%m.%Y %H:%M").to_string();
+        let time = time.with_timezone(&Utc);
+        let hour_start = time
+            .with_minute(0)
+            .and_then(|t| t.with_second(0))
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(time);
+        let covered = self
+            .hourly
+            .first()
+            .is_some_and(|first| first.timestamp <= hour_start);
+        let hourly: Vec<WeatherData> = self
+            .hourly
+            .into_iter()
+            .filter(|w| w.timestamp >= hour_start)
+            .collect();
+
+        if !covered || hourly.is_empty() {
+            bail!("прогноз не покрывает {}", requested);
+        }
+        Ok(WeatherForecast { hourly })
+    }
 }
 
 pub struct WeatherService {
-    api_key: String,
+    provider: Box<dyn WeatherProvider>,
     city: String,
+    coordinates: Option<CityCoordinates>,
     demo_mode: bool,
+    forecast_days: u32,
+}
+
+/// Определяет горизонт прогноза из переменной окружения `FORECAST_DAYS`
+///
+/// Значение ограничивается диапазоном 1..=[`MAX_FORECAST_DAYS`]; при отсутствии
+/// или ошибке разбора используется 1 день.
+pub fn forecast_days_from_env() -> u32 {
+    match std::env::var("FORECAST_DAYS") {
+        Ok(value) => match value.trim().parse::<u32>() {
+            Ok(days) => days.clamp(1, MAX_FORECAST_DAYS),
+            Err(_) => {
+                warn!(
+                    "Некорректное значение FORECAST_DAYS='{}', используем 1",
+                    value
+                );
+                1
+            }
+        },
+        Err(_) => 1,
+    }
 }
 
 impl WeatherService {
+    /// Создает сервис погоды с провайдером из переменной `WEATHER_PROVIDER`
+    /// и горизонтом из переменной `FORECAST_DAYS`
     pub fn new(api_key: String, city: String) -> Self {
-        debug!("Создание WeatherService для города: {}", city);
+        Self::with_provider(
+            create_provider(provider_kind_from_env(), api_key, DEFAULT_LANGUAGE),
+            city,
+        )
+        .with_forecast_days(forecast_days_from_env())
+    }
+
+    /// Создает сервис погоды с явно заданным провайдером
+    pub fn with_provider(provider: Box<dyn WeatherProvider>, city: String) -> Self {
+        debug!(
+            "Создание WeatherService для города: {} (провайдер {})",
+            city,
+            provider.name()
+        );
 
         // Проверяем DEMO режим
         let demo_mode = is_demo_mode();
@@ -104,155 +207,108 @@ impl WeatherService {
         }
 
         Self {
-            api_key,
+            provider,
             city,
+            coordinates: None,
             demo_mode,
+            forecast_days: 1,
         }
     }
 
-    pub async fn get_weather_forecast(&self) -> Result<WeatherForecast> {
-        debug!("Запрос прогноза погоды для города: {}", self.city);
+    /// Задает координаты точки съемки
+    ///
+    /// Погода запрашивается прямо для этих координат, название города
+    /// используется только для отображения.
+    pub fn with_coordinates(mut self, latitude: f64, longitude: f64) -> Self {
+        self.coordinates = Some(CityCoordinates {
+            lat: latitude,
+            lon: longitude,
+        });
+        self
+    }
 
-        // Если включен DEMO режим или используется demo_key, возвращаем моковые данные
-        if self.demo_mode || self.api_key == "demo_key" {
-            warn!("Используются демонстрационные данные погоды");
-            return self.get_mock_forecast();
+    /// Задает горизонт прогноза в днях
+    ///
+    /// Значение ограничивается [`MAX_FORECAST_DAYS`] и возможностями провайдера.
+    pub fn with_forecast_days(mut self, days: u32) -> Self {
+        let max_days = MAX_FORECAST_DAYS.min(self.provider.max_forecast_days());
+        if days > max_days {
+            warn!(
+                "Провайдер {} поддерживает прогноз максимум на {} дн., запрошено {}",
+                self.provider.name(),
+                max_days,
+                days
+            );
         }
+        self.forecast_days = days.clamp(1, max_days);
+        self
+    }
 
-        // Получаем координаты города
-        let coords = self.get_city_coordinates().await?;
-        debug!(
-            "Координаты города {}: lat={}, lon={}",
-            self.city, coords.lat, coords.lon
-        );
+    /// Горизонт прогноза в днях
+    pub fn forecast_days(&self) -> u32 {
+        self.forecast_days
+    }
 
-        // Используем бесплатный Current Weather API вместо OneCall
-        let url = format!(
-            "https://api.openweathermap.org/data/2.5/weather?lat={}&lon={}&units=metric&appid={}",
-            coords.lat, coords.lon, self.api_key
-        );
+    fn forecast_hours(&self) -> i64 {
+        FORECAST_HOURS * self.forecast_days as i64
+    }
 
-        debug!("🌤️ API ЗАПРОС: OpenWeather API для города {}", self.city);
-        let response = reqwest::get(&url).await?;
+    /// Получает почасовой прогноз погоды на `forecast_days` дней вперед
+    ///
+    /// Первая запись - фактическое наблюдение, остальные - почасовой прогноз
+    /// выбранного провайдера.
+    pub async fn get_weather_forecast(&self) -> Result<WeatherForecast> {
+        debug!("Запрос прогноза погоды для города: {}", self.city);
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_message = match status.as_u16() {
-                401 => {
-                    "Неверный API ключ. Получите бесплатный ключ на https://openweathermap.org/api"
-                        .to_string()
-                }
-                429 => "Превышен лимит запросов. Попробуйте позже.".to_string(),
-                404 => {
-                    format!("Город '{}' не найден", self.city)
-                }
-                _ => {
-                    format!("HTTP ошибка {} при получении данных погоды", status)
-                }
-            };
-            warn!("Ошибка API: {}", error_message);
-            return Err(anyhow::anyhow!(error_message));
+        // Если включен DEMO режим или у провайдера нет ключа, возвращаем моковые данные
+        if self.demo_mode || !self.provider.has_credentials() {
+            warn!("Используются демонстрационные данные погоды");
+            return self.get_mock_forecast();
         }
 
-        let weather_response: CurrentWeatherResponse = response.json().await?;
-        info!(
-            "Получены данные погоды: {}°C, облачность {}%",
-            weather_response.main.temp, weather_response.clouds.all
-        );
-
-        // Создаем прогноз на основе текущих данных БЕЗ случайных вариаций
-        let mut forecast = WeatherForecast { hourly: Vec::new() };
-
-        // Генерируем прогноз на 24 часа с реалистичными суточными циклами
-        let current_time = chrono::Utc::now();
-        let base_temp = weather_response.main.temp;
-
-        for hour in 0..24 {
-            // Создаем реалистичные суточные вариации температуры БЕЗ случайности
-            let hour_of_day = (current_time.hour() + hour as u32) % 24;
-            let temp_variation = match hour_of_day {
-                6..=8 => -2.0,   // Утро прохладнее
-                9..=11 => -1.0,  // Начало дня
-                12..=16 => 0.0,  // День - базовая температура
-                17..=19 => -1.0, // Вечер
-                20..=22 => -2.0, // Поздний вечер
-                _ => -3.0,       // Ночь холоднее
-            };
+        let coords = self.resolve_coordinates().await?;
 
-            let temperature = base_temp + temp_variation;
-
-            // Суточные вариации других параметров БЕЗ случайности
-            let humidity_variation = match hour_of_day {
-                6..=8 => -5.0,   // Утро - меньше влажности
-                12..=16 => 5.0,  // День - больше влажности
-                20..=22 => -3.0, // Вечер
-                _ => 0.0,
-            };
-
-            let wind_variation = match hour_of_day {
-                12..=16 => 1.0, // День - ветер сильнее
-                _ => 0.0,
-            };
+        self.provider
+            .fetch_forecast(&coords, self.forecast_hours())
+            .await
+    }
 
-            let cloud_variation = match hour_of_day {
-                6..=8 => -10.0, // Утро - меньше облаков
-                12..=16 => 5.0, // День - больше облаков
-                _ => 0.0,
-            };
+    /// Определяет название места для отображения
+    ///
+    /// Если заданы координаты, название уточняется обратным геокодированием.
+    /// При ошибке или в DEMO режиме возвращается исходное название города.
+    pub async fn resolve_location_name(&self) -> String {
+        let coords = match self.coordinates {
+            Some(coords) if !self.demo_mode && self.provider.has_credentials() => coords,
+            _ => return self.city.clone(),
+        };
 
-            let weather_data = WeatherData {
-                temperature: temperature.clamp(-20.0, 50.0), // Ограничиваем разумными пределами
-                humidity: (weather_response.main.humidity + humidity_variation).clamp(0.0, 100.0),
-                wind_speed: (weather_response.wind.speed + wind_variation).max(0.0),
-                cloud_cover: (weather_response.clouds.all + cloud_variation).clamp(0.0, 100.0),
-                visibility: weather_response.visibility / 1000.0, // конвертируем в км
-                precipitation_probability: if weather_response.clouds.all > 70.0 {
-                    20.0
-                } else {
-                    5.0
-                },
-                description: weather_response
-                    .weather
-                    .first()
-                    .map(|w| w.description.clone())
-                    .unwrap_or_else(|| "Неизвестно".to_string()),
-                timestamp: current_time + chrono::Duration::hours(hour),
-            };
-            forecast.hourly.push(weather_data);
+        match self.provider.reverse_geocode(&coords).await {
+            Ok(Some(name)) => name,
+            Ok(None) => self.city.clone(),
+            Err(e) => {
+                warn!("Не удалось определить название места по координатам: {}", e);
+                self.city.clone()
+            }
         }
-
-        debug!("Сгенерирован прогноз на 24 часа с суточными циклами");
-        Ok(forecast)
     }
 
-    async fn get_city_coordinates(&self) -> Result<CityCoordinates> {
-        debug!(
-            "🗺️ API ЗАПРОС: OpenWeather Geocoding API для города {}",
-            self.city
-        );
-        let url = format!(
-            "http://api.openweathermap.org/geo/1.0/direct?q={}&limit=1&appid={}",
-            self.city, self.api_key
-        );
-
-        let response = reqwest::get(&url).await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            return Err(anyhow::anyhow!(
-                "HTTP ошибка {} при получении координат города '{}'",
-                status,
-                self.city
-            ));
+    async fn resolve_coordinates(&self) -> Result<CityCoordinates> {
+        if let Some(coords) = self.coordinates {
+            debug!(
+                "Используются заданные координаты: lat={}, lon={}",
+                coords.lat, coords.lon
+            );
+            return Ok(coords);
         }
 
-        let coords: Vec<CityCoordinates> = response.json().await?;
-
-        if let Some(coord) = coords.first() {
-            Ok(coord.clone())
-        } else {
-            Err(anyhow::anyhow!("Город '{}' не найден", self.city))
-        }
+        // Координаты не заданы - ищем город по названию
+        let coords = self.provider.geocode(&self.city).await?;
+        debug!(
+            "Координаты города {}: lat={}, lon={}",
+            self.city, coords.lat, coords.lon
+        );
+        Ok(coords)
     }
 
     fn get_mock_forecast(&self) -> Result<WeatherForecast> {
@@ -262,7 +318,10 @@ impl WeatherService {
         // Используем фиксированное время для стабильности тестов
         let base_time = get_current_utc_time();
 
-        for hour in 0..24 {
+        for step in 0..self.forecast_hours() {
+            // Дни горизонта повторяют суточный цикл, облачность меняется от дня к дню
+            let hour = step % FORECAST_HOURS;
+            let day_cloud_offset = ((step / FORECAST_HOURS) * 15 % 45) as f64;
             let weather_data = WeatherData {
                 temperature: 15.0 + (hour as f64 * 0.5) - 6.0, // Температура от 9 до 21 градуса
                 humidity: 60.0 + (hour as f64 * 2.0) % 40.0,
@@ -271,7 +330,7 @@ impl WeatherService {
                     20.0
                 } else {
                     60.0
-                },
+                } + day_cloud_offset,
                 visibility: 10.0 - (hour as f64 * 0.1) % 5.0,
                 precipitation_probability: if hour > 12 && hour < 18 { 30.0 } else { 5.0 },
                 description: match hour {
@@ -282,7 +341,23 @@ impl WeatherService {
                     18..=20 => "Закат".to_string(),
                     _ => "Ночь".to_string(),
                 },
-                timestamp: base_time + chrono::Duration::hours(hour),
+                timestamp: base_time + chrono::Duration::hours(step),
+                source: WeatherDataSource::Synthetic,
+                // Днем облака в основном средние, ночью - верхние
+                cloud_layers: Some(if (6..=18).contains(&hour) {
+                    CloudLayers {
+                        low: 10.0,
+                        mid: 50.0 + day_cloud_offset,
+                        high: 20.0,
+                    }
+                } else {
+                    CloudLayers {
+                        low: 0.0,
+                        mid: 5.0,
+                        high: 20.0 + day_cloud_offset,
+                    }
+                }),
+                dew_point: None,
             };
             forecast.hourly.push(weather_data);
         }
@@ -291,88 +366,335 @@ impl WeatherService {
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
-struct CityCoordinates {
-    lat: f64,
-    lon: f64,
+/// Строит почасовой прогноз из текущего наблюдения и точек прогноза API
+///
+/// Первая запись - само наблюдение, следующие `hours - 1` записей идут с шагом
+/// в один час и линейно интерполируются между соседними точками. Точки прогноза
+/// должны быть отсортированы по времени; после последней точки значения не
+/// экстраполируются, а повторяются.
+pub(crate) fn build_hourly_forecast(
+    observed: WeatherData,
+    points: &[WeatherData],
+    hours: i64,
+) -> WeatherForecast {
+    let start = observed.timestamp;
+
+    // Наблюдение служит первой опорной точкой для интерполяции
+    let mut anchors = vec![observed.clone()];
+    anchors.extend(points.iter().filter(|p| p.timestamp > start).cloned());
+
+    let mut forecast = WeatherForecast {
+        hourly: vec![observed],
+    };
+
+    for hour in 1..hours {
+        let timestamp = start + chrono::Duration::hours(hour);
+        let next_idx = anchors
+            .iter()
+            .position(|p| p.timestamp >= timestamp)
+            .unwrap_or(anchors.len() - 1);
+
+        let weather_data = if next_idx == 0 || anchors[next_idx].timestamp < timestamp {
+            WeatherData {
+                timestamp,
+                source: WeatherDataSource::Forecast,
+                ..anchors[next_idx].clone()
+            }
+        } else {
+            interpolate_weather(&anchors[next_idx - 1], &anchors[next_idx], timestamp)
+        };
+
+        forecast.hourly.push(weather_data);
+    }
+
+    forecast
 }
 
-pub fn analyze_weather_for_photography(forecast: &WeatherForecast) -> WeatherAnalysis {
-    let mut analysis = WeatherAnalysis {
-        overall_score: 0.0,
-        recommendations: Vec::new(),
-        best_hours: Vec::new(),
-        concerns: Vec::new(),
+/// Линейно интерполирует погодные данные между двумя точками на момент `timestamp`
+fn interpolate_weather(
+    before: &WeatherData,
+    after: &WeatherData,
+    timestamp: DateTime<Utc>,
+) -> WeatherData {
+    let span = (after.timestamp - before.timestamp).num_seconds() as f64;
+    let t = if span > 0.0 {
+        ((timestamp - before.timestamp).num_seconds() as f64 / span).clamp(0.0, 1.0)
+    } else {
+        1.0
     };
+    let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+    WeatherData {
+        temperature: lerp(before.temperature, after.temperature),
+        humidity: lerp(before.humidity, after.humidity),
+        wind_speed: lerp(before.wind_speed, after.wind_speed),
+        cloud_cover: lerp(before.cloud_cover, after.cloud_cover),
+        visibility: lerp(before.visibility, after.visibility),
+        precipitation_probability: lerp(
+            before.precipitation_probability,
+            after.precipitation_probability,
+        ),
+        // Описание берем у ближайшей по времени точки
+        description: if t < 0.5 {
+            before.description.clone()
+        } else {
+            after.description.clone()
+        },
+        timestamp,
+        source: WeatherDataSource::Forecast,
+        cloud_layers: match (before.cloud_layers, after.cloud_layers) {
+            (Some(before), Some(after)) => Some(CloudLayers {
+                low: lerp(before.low, after.low),
+                mid: lerp(before.mid, after.mid),
+                high: lerp(before.high, after.high),
+            }),
+            (before, after) => {
+                if t < 0.5 {
+                    before
+                } else {
+                    after
+                }
+            }
+        },
+        dew_point: match (before.dew_point, after.dew_point) {
+            (Some(before), Some(after)) => Some(lerp(before, after)),
+            (before, after) => {
+                if t < 0.5 {
+                    before
+                } else {
+                    after
+                }
+            }
+        },
+    }
+}
 
-    // Анализируем каждый час
-    for (hour, weather) in forecast.hourly.iter().enumerate() {
-        let mut hour_score = 0.0;
-        let mut hour_recommendations = Vec::new();
-        let mut hour_concerns = Vec::new();
+/// Резервный генератор: синтезирует прогноз на `hours` часов из одного наблюдения
+///
+/// Используется только когда почасовой прогноз API недоступен. Добавляет
+/// детерминированные суточные вариации к текущим значениям.
+pub(crate) fn synthesize_forecast_from_current(
+    observed: &WeatherData,
+    hours: i64,
+) -> WeatherForecast {
+    let mut forecast = WeatherForecast { hourly: Vec::new() };
+    let current_time = observed.timestamp;
+
+    // Первая запись - само наблюдение без вариаций
+    forecast.hourly.push(observed.clone());
+
+    for hour in 1..hours {
+        // Создаем реалистичные суточные вариации температуры БЕЗ случайности
+        let hour_of_day = ((current_time.hour() as i64 + hour) % 24) as u32;
+        let temp_variation = match hour_of_day {
+            6..=8 => -2.0,   // Утро прохладнее
+            9..=11 => -1.0,  // Начало дня
+            12..=16 => 0.0,  // День - базовая температура
+            17..=19 => -1.0, // Вечер
+            20..=22 => -2.0, // Поздний вечер
+            _ => -3.0,       // Ночь холоднее
+        };
 
-        // Оценка температуры
-        if weather.temperature >= 10.0 && weather.temperature <= 25.0 {
-            hour_score += 2.0;
-        } else {
-            hour_concerns.push(format!("Неудобная температура: {}°C", weather.temperature));
-        }
+        // Суточные вариации других параметров БЕЗ случайности
+        let humidity_variation = match hour_of_day {
+            6..=8 => -5.0,   // Утро - меньше влажности
+            12..=16 => 5.0,  // День - больше влажности
+            20..=22 => -3.0, // Вечер
+            _ => 0.0,
+        };
 
-        // Оценка ветра
-        if weather.wind_speed < 10.0 {
-            hour_score += 2.0;
-        } else {
-            hour_concerns.push(format!("Сильный ветер: {} м/с", weather.wind_speed));
-        }
+        let wind_variation = match hour_of_day {
+            12..=16 => 1.0, // День - ветер сильнее
+            _ => 0.0,
+        };
 
-        // Оценка облачности
-        if weather.cloud_cover < 30.0 {
-            hour_score += 3.0;
-            hour_recommendations.push("Отличная видимость".to_string());
-        } else if weather.cloud_cover < 70.0 {
-            hour_score += 1.5;
-            hour_recommendations.push("Хорошие условия для съемки".to_string());
-        } else {
-            hour_concerns.push(format!("Высокая облачность: {}%", weather.cloud_cover));
-        }
+        let cloud_variation = match hour_of_day {
+            6..=8 => -10.0, // Утро - меньше облаков
+            12..=16 => 5.0, // День - больше облаков
+            _ => 0.0,
+        };
 
-        // Оценка видимости
-        if weather.visibility > 8.0 {
-            hour_score += 2.0;
-        } else {
-            hour_concerns.push(format!("Плохая видимость: {} км", weather.visibility));
-        }
+        let weather_data = WeatherData {
+            temperature: (observed.temperature + temp_variation).clamp(-20.0, 50.0), // Ограничиваем разумными пределами
+            humidity: (observed.humidity + humidity_variation).clamp(0.0, 100.0),
+            wind_speed: (observed.wind_speed + wind_variation).max(0.0),
+            cloud_cover: (observed.cloud_cover + cloud_variation).clamp(0.0, 100.0),
+            visibility: observed.visibility,
+            precipitation_probability: observed.precipitation_probability,
+            description: observed.description.clone(),
+            timestamp: current_time + chrono::Duration::hours(hour),
+            source: WeatherDataSource::Synthetic,
+            cloud_layers: observed.cloud_layers,
+            // Температура и влажность меняются, поэтому точку росы наблюдения не переносим
+            dew_point: None,
+        };
+        forecast.hourly.push(weather_data);
+    }
 
-        // Оценка осадков
-        if weather.precipitation_probability < 20.0 {
-            hour_score += 1.0;
-        } else {
-            hour_concerns.push(format!(
-                "Вероятность осадков: {}%",
-                weather.precipitation_probability
-            ));
-        }
+    debug!(
+        "Сгенерирован синтетический прогноз на {} часов с суточными циклами",
+        hours
+    );
+    forecast
+}
 
-        // Специальные условия для фотографии
-        if (6..=8).contains(&hour) {
-            hour_score += 2.0; // Золотой час утром
-            hour_recommendations.push("Золотой час - идеальное время для съемки".to_string());
-        } else if (18..=20).contains(&hour) {
-            hour_score += 2.0; // Золотой час вечером
-            hour_recommendations.push("Золотой час - идеальное время для съемки".to_string());
-        }
+/// Баллы оценки часа по отдельным условиям
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ScoreComponents {
+    /// Комфортная температура (10-25°C): 0 или 2
+    pub temperature: f64,
+    /// Ветер слабее 10 м/с: 0 или 2
+    pub wind: f64,
+    /// Облачность: 3 при < 30%, 1.5 при < 70%, иначе 0
+    pub cloud: f64,
+    /// Видимость больше 8 км: 0 или 2
+    pub visibility: f64,
+    /// Вероятность осадков меньше 20%: 0 или 1
+    pub precipitation: f64,
+    /// Бонус золотого часа: 0 или 2
+    pub lighting: f64,
+}
 
-        if hour_score >= 7.0 {
-            analysis.best_hours.push(hour);
-        }
+impl ScoreComponents {
+    /// Сумма баллов без ограничения сверху
+    pub fn total(&self) -> f64 {
+        self.temperature
+            + self.wind
+            + self.cloud
+            + self.visibility
+            + self.precipitation
+            + self.lighting
+    }
+}
+
+/// Оценка одного часа прогноза с объяснением
+#[derive(Debug, Clone, Serialize)]
+pub struct HourScore {
+    /// Начало часа
+    pub timestamp: DateTime<Utc>,
+    /// Итоговая оценка (0-10)
+    pub score: f64,
+    /// Баллы по отдельным условиям
+    pub components: ScoreComponents,
+    /// Чем час хорош
+    pub recommendations: Vec<String>,
+    /// Чем час плох
+    pub concerns: Vec<String>,
+}
 
-        analysis.overall_score += hour_score;
+/// Оценивает один час прогноза по составляющим
+///
+/// Бонус золотого часа дается, если час пересекается с золотым часом одного
+/// из дней `golden_hours`.
+pub fn score_hour(weather: &WeatherData, golden_hours: &[GoldenHourInfo]) -> HourScore {
+    let mut components = ScoreComponents::default();
+    let mut recommendations = Vec::new();
+    let mut concerns = Vec::new();
+
+    // Оценка температуры
+    if weather.temperature >= 10.0 && weather.temperature <= 25.0 {
+        components.temperature = 2.0;
+    } else {
+        concerns.push(format!(
+            "Неудобная температура: {:.1}°C",
+            weather.temperature
+        ));
+    }
+
+    // Оценка ветра
+    if weather.wind_speed < 10.0 {
+        components.wind = 2.0;
+    } else {
+        concerns.push(format!("Сильный ветер: {:.1} м/с", weather.wind_speed));
+    }
+
+    // Оценка облачности
+    if weather.cloud_cover < 30.0 {
+        components.cloud = 3.0;
+        recommendations.push("Отличная видимость".to_string());
+    } else if weather.cloud_cover < 70.0 {
+        components.cloud = 1.5;
+        recommendations.push("Хорошие условия для съемки".to_string());
+    } else {
+        concerns.push(format!("Высокая облачность: {:.0}%", weather.cloud_cover));
+    }
+
+    // Оценка видимости
+    if weather.visibility > 8.0 {
+        components.visibility = 2.0;
+    } else {
+        concerns.push(format!("Плохая видимость: {:.1} км", weather.visibility));
+    }
+
+    // Оценка осадков
+    if weather.precipitation_probability < 20.0 {
+        components.precipitation = 1.0;
+    } else {
+        concerns.push(format!(
+            "Вероятность осадков: {:.0}%",
+            weather.precipitation_probability
+        ));
+    }
+
+    // Специальные условия для фотографии
+    if is_golden_hour(weather, golden_hours) {
+        components.lighting = 2.0;
+        recommendations.push("Золотой час - идеальное время для съемки".to_string());
+    }
+
+    HourScore {
+        timestamp: weather.timestamp,
+        // Бонус золотого часа может поднять сумму выше 10
+        score: components.total().min(10.0),
+        components,
+        recommendations,
+        concerns,
+    }
+}
+
+/// Оценивает условия для съемки по часам прогноза
+///
+/// Бонус получают часы, пересекающиеся с золотым часом одного из дней
+/// `golden_hours` (см. [`GoldenHourService::golden_hours_between`]). Лучшие
+/// часы считаются по часам места съемки в поясе `timezone`.
+///
+/// [`GoldenHourService::golden_hours_between`]: crate::golden_hour::GoldenHourService::golden_hours_between
+pub fn analyze_weather_for_photography(
+    forecast: &WeatherForecast,
+    golden_hours: &[GoldenHourInfo],
+    timezone: Tz,
+) -> WeatherAnalysis {
+    let mut analysis = WeatherAnalysis {
+        overall_score: 0.0,
+        recommendations: Vec::new(),
+        best_hours: Vec::new(),
+        hours: Vec::new(),
+        concerns: Vec::new(),
+    };
+
+    // Анализируем каждый час
+    for weather in &forecast.hourly {
+        let hour_score = score_hour(weather, golden_hours);
+        let total = hour_score.components.total();
+
+        if total >= 7.0 {
+            analysis
+                .best_hours
+                .push(weather.timestamp.with_timezone(&timezone));
+        }
+        analysis.overall_score += total;
 
         // Добавляем concerns в общий список, если они есть
-        analysis.concerns.extend(hour_concerns);
+        analysis
+            .concerns
+            .extend(hour_score.concerns.iter().cloned());
+        analysis.hours.push(hour_score);
     }
 
-    analysis.overall_score /= 24.0;
+    if !forecast.hourly.is_empty() {
+        // Бонус золотого часа может поднять оценку часа выше 10
+        analysis.overall_score = (analysis.overall_score / forecast.hourly.len() as f64).min(10.0);
+    }
 
     // Общие рекомендации
     if analysis.overall_score >= 7.0 {
@@ -392,7 +714,17 @@ pub fn analyze_weather_for_photography(forecast: &WeatherForecast) -> WeatherAna
     analysis
 }
 
-pub fn analyze_astrophotography_conditions(forecast: &WeatherForecast) -> AstrophotographyAnalysis {
+/// Анализирует условия для астрофотографии
+///
+/// Лучшими считаются часы без облаков и осадков, попадающие в окна полной
+/// темноты `darkness` (см. [`GoldenHourService::darkness_windows`]).
+///
+/// [`GoldenHourService::darkness_windows`]: crate::golden_hour::GoldenHourService::darkness_windows
+pub fn analyze_astrophotography_conditions(
+    forecast: &WeatherForecast,
+    darkness: &[DarknessWindow],
+    timezone: Tz,
+) -> AstrophotographyAnalysis {
     let mut analysis = AstrophotographyAnalysis {
         is_suitable: true,
         cloud_cover_issues: Vec::new(),
@@ -402,7 +734,7 @@ pub fn analyze_astrophotography_conditions(forecast: &WeatherForecast) -> Astrop
     };
 
     // Анализируем условия для астрофотографии
-    for (hour, weather) in forecast.hourly.iter().enumerate() {
+    for weather in &forecast.hourly {
         let mut hour_suitable = true;
         let mut hour_concerns = Vec::new();
 
@@ -438,11 +770,15 @@ pub fn analyze_astrophotography_conditions(forecast: &WeatherForecast) -> Astrop
             ));
         }
 
-        // Ночные часы (22:00 - 4:00) лучше подходят для астрофотографии
-        let is_night_hour = hour >= 22 || hour <= 4;
+        // Для астрофотографии нужна полная темнота: Солнце ниже 18° под горизонтом
+        let is_dark_hour = darkness
+            .iter()
+            .any(|window| window.contains(weather.timestamp));
 
-        if hour_suitable && is_night_hour {
-            analysis.best_hours.push(hour);
+        if hour_suitable && is_dark_hour {
+            analysis
+                .best_hours
+                .push(weather.timestamp.with_timezone(&timezone));
         }
 
         if !hour_suitable {
@@ -471,6 +807,12 @@ pub fn analyze_astrophotography_conditions(forecast: &WeatherForecast) -> Astrop
             .push("Рекомендуется перенести съемку на другой день".to_string());
     }
 
+    if darkness.is_empty() {
+        analysis
+            .concerns
+            .push("Нет полной темноты: Солнце не опускается ниже 18° под горизонтом".to_string());
+    }
+
     // Проверяем общую облачность
     let avg_cloud_cover =
         forecast.hourly.iter().map(|w| w.cloud_cover).sum::<f64>() / forecast.hourly.len() as f64;
@@ -484,11 +826,130 @@ pub fn analyze_astrophotography_conditions(forecast: &WeatherForecast) -> Astrop
     analysis
 }
 
+/// Анализирует условия для астрофотографии с учетом Луны
+///
+/// Часы, когда яркая Луна стоит над горизонтом, исключаются из лучших:
+/// она засвечивает Млечный Путь и слабые объекты даже при ясном небе.
+pub fn analyze_astrophotography_with_moon(
+    forecast: &WeatherForecast,
+    darkness: &[DarknessWindow],
+    moon: &MoonService,
+    timezone: Tz,
+) -> AstrophotographyAnalysis {
+    let mut analysis = analyze_astrophotography_conditions(forecast, darkness, timezone);
+
+    // Часы сравниваются по моменту времени, а не по часу суток: прогноз
+    // может захватывать одни и те же часы разных ночей
+    let (moonlit, dark): (Vec<DateTime<Tz>>, Vec<DateTime<Tz>>) = analysis
+        .best_hours
+        .iter()
+        .partition(|hour| moon.brightens_sky(hour.with_timezone(&Utc)));
+    let Some(first_moonlit) = moonlit.first() else {
+        return analysis;
+    };
+
+    analysis.best_hours = dark;
+    let position = moon.position(first_moonlit.with_timezone(&Utc));
+    analysis.concerns.push(format!(
+        "{} ({:.0}%) над горизонтом {} ч из ночных часов - засвечивает небо",
+        position.phase.name(),
+        position.illumination * 100.0,
+        moonlit.len()
+    ));
+    analysis
+        .recommendations
+        .push(if analysis.best_hours.is_empty() {
+            "Яркая Луна всю ночь: вместо Млечного Пути снимайте пейзажи, освещенные Луной"
+                .to_string()
+        } else {
+            "Снимайте Млечный Путь, пока Луна под горизонтом".to_string()
+        });
+
+    analysis
+}
+
+/// Анализ погодных условий за один календарный день горизонта
+#[derive(Debug, Clone)]
+pub struct DailyWeatherAnalysis {
+    /// Дата по часам места съемки
+    pub date: NaiveDate,
+    /// Средняя оценка условий за день (0-10)
+    pub score: f64,
+    /// Начало часов дня с оценкой >= 7 по часам места съемки
+    pub best_hours: Vec<DateTime<Tz>>,
+    /// Минимальная температура за день
+    pub min_temperature: f64,
+    /// Максимальная температура за день
+    pub max_temperature: f64,
+    /// Средняя облачность за день
+    pub avg_cloud_cover: f64,
+    /// Количество часов прогноза, попавших в этот день
+    pub hours_count: usize,
+}
+
+/// Разбивает прогноз по календарным дням и оценивает каждый день отдельно
+///
+/// Сутки отсчитываются по часам места съемки в поясе `timezone`. Дни
+/// возвращаются в хронологическом порядке. Первый и последний день горизонта
+/// могут быть неполными. Золотой час `golden_hours` учитывается так же, как
+/// в [`analyze_weather_for_photography`].
+pub fn analyze_daily_weather(
+    forecast: &WeatherForecast,
+    golden_hours: &[GoldenHourInfo],
+    timezone: Tz,
+) -> Vec<DailyWeatherAnalysis> {
+    let mut days: Vec<(NaiveDate, Vec<WeatherData>)> = Vec::new();
+
+    for weather in &forecast.hourly {
+        let date = weather.timestamp.with_timezone(&timezone).date_naive();
+        match days.last_mut() {
+            Some((last_date, hours)) if *last_date == date => hours.push(weather.clone()),
+            _ => days.push((date, vec![weather.clone()])),
+        }
+    }
+
+    days.into_iter()
+        .map(|(date, hourly)| {
+            let day_forecast = WeatherForecast { hourly };
+            let analysis = analyze_weather_for_photography(&day_forecast, golden_hours, timezone);
+            let hours = &day_forecast.hourly;
+
+            DailyWeatherAnalysis {
+                date,
+                score: analysis.overall_score,
+                best_hours: analysis.best_hours,
+                min_temperature: hours
+                    .iter()
+                    .map(|w| w.temperature)
+                    .fold(f64::INFINITY, f64::min),
+                max_temperature: hours
+                    .iter()
+                    .map(|w| w.temperature)
+                    .fold(f64::NEG_INFINITY, f64::max),
+                avg_cloud_cover: hours.iter().map(|w| w.cloud_cover).sum::<f64>()
+                    / hours.len() as f64,
+                hours_count: hours.len(),
+            }
+        })
+        .collect()
+}
+
+/// Пересекается ли час прогноза с золотым часом одного из дней
+fn is_golden_hour(weather: &WeatherData, golden_hours: &[GoldenHourInfo]) -> bool {
+    let end = weather.timestamp + chrono::Duration::hours(1);
+    golden_hours
+        .iter()
+        .any(|info| info.overlaps_golden_hour(weather.timestamp, end))
+}
+
 #[derive(Debug)]
 pub struct WeatherAnalysis {
     pub overall_score: f64,
     pub recommendations: Vec<String>,
-    pub best_hours: Vec<usize>,
+    /// Начало лучших часов по часам места съемки
+    pub best_hours: Vec<DateTime<Tz>>,
+    /// Оценка каждого часа прогноза в порядке `WeatherForecast::hourly`
+    pub hours: Vec<HourScore>,
     pub concerns: Vec<String>,
 }
 
@@ -497,14 +958,17 @@ pub struct AstrophotographyAnalysis {
     pub is_suitable: bool,
     pub cloud_cover_issues: Vec<String>,
     pub recommendations: Vec<String>,
-    pub best_hours: Vec<usize>,
+    /// Начало лучших часов по часам места съемки
+    pub best_hours: Vec<DateTime<Tz>>,
     pub concerns: Vec<String>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use crate::golden_hour::GoldenHourService;
+    use chrono::{TimeZone, Utc};
+    use chrono_tz::Europe::Moscow;
     use pretty_assertions::assert_eq;
 
     // Вспомогательные функции для создания тестовых данных
@@ -518,6 +982,9 @@ mod tests {
             precipitation_probability: 5.0,
             description: "ясно".to_string(),
             timestamp: Utc::now(),
+            source: WeatherDataSource::Forecast,
+            cloud_layers: None,
+            dew_point: None,
         }
     }
 
@@ -546,6 +1013,9 @@ mod tests {
                     _ => "ночь".to_string(),
                 },
                 timestamp: Utc::now() + chrono::Duration::hours(hour),
+                source: WeatherDataSource::Forecast,
+                cloud_layers: None,
+                dew_point: None,
             };
             forecast.hourly.push(weather_data);
         }
@@ -553,6 +1023,14 @@ mod tests {
         forecast
     }
 
+    /// Окна темноты в Москве на время прогноза
+    fn darkness_for(forecast: &WeatherForecast) -> Vec<DarknessWindow> {
+        let (Some(first), Some(last)) = (forecast.hourly.first(), forecast.hourly.last()) else {
+            return Vec::new();
+        };
+        GoldenHourService::new(55.7558, 37.6176).darkness_windows(first.timestamp, last.timestamp)
+    }
+
     fn create_bad_weather_forecast() -> WeatherForecast {
         let mut forecast = WeatherForecast { hourly: Vec::new() };
 
@@ -567,6 +1045,9 @@ mod tests {
                 precipitation_probability: 80.0, // Высокая вероятность осадков
                 description: "сильный дождь".to_string(),
                 timestamp: Utc::now() + chrono::Duration::hours(hour),
+                source: WeatherDataSource::Forecast,
+                cloud_layers: None,
+                dew_point: None,
             };
             forecast.hourly.push(weather_data);
         }
@@ -577,7 +1058,7 @@ mod tests {
     #[test]
     fn test_weather_analysis_calculation() {
         let forecast = create_test_forecast();
-        let analysis = analyze_weather_for_photography(&forecast);
+        let analysis = analyze_weather_for_photography(&forecast, &[], Moscow);
 
         // Проверяем, что оценка в разумных пределах
         assert!(analysis.overall_score >= 0.0);
@@ -593,7 +1074,7 @@ mod tests {
     #[test]
     fn test_weather_analysis_bad_conditions() {
         let forecast = create_bad_weather_forecast();
-        let analysis = analyze_weather_for_photography(&forecast);
+        let analysis = analyze_weather_for_photography(&forecast, &[], Moscow);
 
         // При плохих условиях оценка должна быть низкой
         assert!(analysis.overall_score < 5.0);
@@ -605,17 +1086,138 @@ mod tests {
     #[test]
     fn test_astrophotography_analysis() {
         let forecast = create_test_forecast();
-        let analysis = analyze_astrophotography_conditions(&forecast);
+        let analysis =
+            analyze_astrophotography_conditions(&forecast, &darkness_for(&forecast), Moscow);
 
         // Проверяем структуру анализа
         assert!(!analysis.recommendations.is_empty());
         // Не проверяем на >= 0, это всегда true
     }
 
+    #[test]
+    fn test_astrophotography_uses_darkness_windows() {
+        // Ясные сутки с полудня 15.01.2024 (UTC)
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        let forecast = WeatherForecast {
+            hourly: (0..24)
+                .map(|hour| WeatherData {
+                    timestamp: start + chrono::Duration::hours(hour),
+                    cloud_cover: 0.0,
+                    visibility: 20.0,
+                    precipitation_probability: 0.0,
+                    ..create_test_weather_data()
+                })
+                .collect(),
+        };
+        let darkness = darkness_for(&forecast);
+        let analysis = analyze_astrophotography_conditions(&forecast, &darkness, Moscow);
+
+        // Лучшие часы - ровно те, что попадают в окно полной темноты
+        let expected: Vec<DateTime<Tz>> = forecast
+            .hourly
+            .iter()
+            .filter(|weather| darkness.iter().any(|w| w.contains(weather.timestamp)))
+            .map(|weather| weather.timestamp.with_timezone(&Moscow))
+            .collect();
+        assert_eq!(analysis.best_hours, expected);
+        // Зимой в Москве темно больше 12 часов
+        assert!(analysis.best_hours.len() >= 12);
+        // Дневные часы (15:00 по Москве) не подходят
+        assert!(analysis.best_hours.iter().all(|hour| hour.hour() != 15));
+
+        // Без окон темноты (белые ночи) лучших часов нет
+        let analysis = analyze_astrophotography_conditions(&forecast, &[], Moscow);
+        assert!(analysis.best_hours.is_empty());
+        assert!(analysis
+            .concerns
+            .iter()
+            .any(|c| c.contains("Нет полной темноты")));
+    }
+
+    #[test]
+    fn test_astrophotography_with_moon() {
+        // Ясная ночь 25-26.01.2024 (полнолуние), часы с 18:00 UTC
+        let start = Utc.with_ymd_and_hms(2024, 1, 25, 18, 0, 0).unwrap();
+        let clear_night = WeatherForecast {
+            hourly: (0..12)
+                .map(|hour| WeatherData {
+                    timestamp: start + chrono::Duration::hours(hour),
+                    cloud_cover: 0.0,
+                    visibility: 20.0,
+                    precipitation_probability: 0.0,
+                    wind_speed: 2.0,
+                    ..create_test_weather_data()
+                })
+                .collect(),
+        };
+
+        let darkness = darkness_for(&clear_night);
+        let weather_only = analyze_astrophotography_conditions(&clear_night, &darkness, Moscow);
+        assert!(!weather_only.best_hours.is_empty());
+
+        // Полная Луна всю ночь над Москвой - лучших часов не остается
+        let moon = MoonService::new(55.7558, 37.6176);
+        let analysis = analyze_astrophotography_with_moon(&clear_night, &darkness, &moon, Moscow);
+        assert!(analysis.is_suitable);
+        assert!(analysis.best_hours.is_empty());
+        assert!(analysis.concerns.iter().any(|c| c.contains("Полнолуние")));
+        assert!(analysis
+            .recommendations
+            .iter()
+            .any(|r| r.contains("освещенные Луной")));
+
+        // В новолуние Луна не мешает
+        let new_moon_start = Utc.with_ymd_and_hms(2024, 1, 11, 18, 0, 0).unwrap();
+        let dark_night = WeatherForecast {
+            hourly: clear_night
+                .hourly
+                .iter()
+                .enumerate()
+                .map(|(hour, weather)| WeatherData {
+                    timestamp: new_moon_start + chrono::Duration::hours(hour as i64),
+                    ..weather.clone()
+                })
+                .collect(),
+        };
+        let darkness = darkness_for(&dark_night);
+        let analysis = analyze_astrophotography_with_moon(&dark_night, &darkness, &moon, Moscow);
+        assert_eq!(
+            analysis.best_hours,
+            analyze_astrophotography_conditions(&dark_night, &darkness, Moscow).best_hours
+        );
+    }
+
+    #[test]
+    fn test_moonlit_hours_matched_by_timestamp() {
+        // 22:00 по Москве в полнолуние и в новолуние: час суток один и тот же
+        let full_moon = Utc.with_ymd_and_hms(2024, 1, 25, 19, 0, 0).unwrap();
+        let new_moon = Utc.with_ymd_and_hms(2024, 1, 11, 19, 0, 0).unwrap();
+        let forecast = WeatherForecast {
+            hourly: [new_moon, full_moon]
+                .into_iter()
+                .map(|timestamp| WeatherData {
+                    timestamp,
+                    cloud_cover: 0.0,
+                    visibility: 20.0,
+                    precipitation_probability: 0.0,
+                    ..create_test_weather_data()
+                })
+                .collect(),
+        };
+        let moon = MoonService::new(55.7558, 37.6176);
+        let analysis =
+            analyze_astrophotography_with_moon(&forecast, &darkness_for(&forecast), &moon, Moscow);
+
+        // Засвечен только час в полнолуние, ночь новолуния остается
+        assert_eq!(analysis.best_hours, vec![new_moon.with_timezone(&Moscow)]);
+        assert!(analysis.concerns.iter().any(|c| c.contains("1 ч")));
+    }
+
     #[test]
     fn test_astrophotography_analysis_bad_conditions() {
         let forecast = create_bad_weather_forecast();
-        let analysis = analyze_astrophotography_conditions(&forecast);
+        let analysis =
+            analyze_astrophotography_conditions(&forecast, &darkness_for(&forecast), Moscow);
 
         // При плохих условиях астрофотография должна быть непригодна
         assert!(!analysis.is_suitable);
@@ -671,7 +1273,7 @@ mod tests {
     fn test_weather_analysis_edge_cases() {
         // Тест с пустым прогнозом
         let empty_forecast = WeatherForecast { hourly: Vec::new() };
-        let analysis = analyze_weather_for_photography(&empty_forecast);
+        let analysis = analyze_weather_for_photography(&empty_forecast, &[], Moscow);
 
         // При пустом прогнозе оценка должна быть 0
         assert_eq!(analysis.overall_score, 0.0);
@@ -680,23 +1282,48 @@ mod tests {
 
     #[test]
     fn test_golden_hour_detection() {
-        let forecast = create_test_forecast();
-        let analysis = analyze_weather_for_photography(&forecast);
-
-        // Проверяем, что золотые часы (6-8 и 18-20) имеют высокие оценки
-        let golden_hours: Vec<usize> = vec![6, 7, 8, 18, 19, 20];
-
-        // Проверяем, что хотя бы некоторые золотые часы попали в лучшие часы
-        let has_golden_hours = golden_hours
+        // Прогноз начинается не в полночь: индекс часа не совпадает с часом суток
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let hourly: Vec<WeatherData> = (0..24)
+            .map(|hour| {
+                create_point(
+                    start + chrono::Duration::hours(hour),
+                    5.0,
+                    50.0,
+                    WeatherDataSource::Forecast,
+                )
+            })
+            .collect();
+        let forecast = WeatherForecast { hourly };
+        let service = GoldenHourService::new(55.7558, 37.6176).with_timezone(Moscow);
+        let golden_hours = service.golden_hours_between(start, start + chrono::Duration::hours(23));
+        let analysis = analyze_weather_for_photography(&forecast, &golden_hours, Moscow);
+
+        // Без бонуса оценка часа 6.5, поэтому в лучшие часы попадают
+        // только часы золотого часа
+        let expected: Vec<DateTime<Tz>> = forecast
+            .hourly
+            .iter()
+            .filter(|weather| is_golden_hour(weather, &golden_hours))
+            .map(|weather| weather.timestamp.with_timezone(&Moscow))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(analysis.best_hours, expected);
+
+        // Зимой в Москве золотой час утром около 8-10 и вечером около 15-17
+        assert!(expected
             .iter()
-            .any(|&hour| analysis.best_hours.contains(&hour));
-        assert!(has_golden_hours || analysis.best_hours.is_empty());
+            .all(|hour| (8..=10).contains(&hour.hour()) || (15..=17).contains(&hour.hour())));
+
+        // Без окон золотого часа бонуса нет
+        let analysis = analyze_weather_for_photography(&forecast, &[], Moscow);
+        assert!(analysis.best_hours.is_empty());
     }
 
     #[test]
     fn test_weather_analysis_components() {
         let forecast = create_test_forecast();
-        let analysis = analyze_weather_for_photography(&forecast);
+        let analysis = analyze_weather_for_photography(&forecast, &[], Moscow);
 
         // Проверяем все компоненты анализа
         assert!(analysis.overall_score >= 0.0);
@@ -705,23 +1332,64 @@ mod tests {
         // Проверяем, что есть хотя бы одна рекомендация или проблема
         assert!(!analysis.recommendations.is_empty() || !analysis.concerns.is_empty());
 
-        // Проверяем, что лучшие часы в разумных пределах
-        for &hour in &analysis.best_hours {
-            assert!((0..=23).contains(&hour));
+        // Лучшие часы выводятся по часам места съемки
+        for hour in &analysis.best_hours {
+            assert_eq!(hour.timezone(), Moscow);
+        }
+
+        // Разбор есть для каждого часа и согласован с общими списками
+        assert_eq!(analysis.hours.len(), forecast.hourly.len());
+        for (weather, hour) in forecast.hourly.iter().zip(&analysis.hours) {
+            assert_eq!(hour.timestamp, weather.timestamp);
+            assert_eq!(hour.score, hour.components.total().min(10.0));
         }
+        let concerns: Vec<String> = analysis
+            .hours
+            .iter()
+            .flat_map(|hour| hour.concerns.clone())
+            .collect();
+        assert_eq!(concerns, analysis.concerns);
+    }
+
+    #[test]
+    fn test_score_hour_explains_score() {
+        let mut weather = create_test_weather_data();
+        weather.temperature = 5.0;
+        weather.wind_speed = 12.34;
+        weather.cloud_cover = 50.0;
+
+        let hour = score_hour(&weather, &[]);
+        assert_eq!(
+            hour.components,
+            ScoreComponents {
+                temperature: 0.0,
+                wind: 0.0,
+                cloud: 1.5,
+                visibility: 2.0,
+                precipitation: 1.0,
+                lighting: 0.0,
+            }
+        );
+        assert_eq!(hour.score, 4.5);
+        assert_eq!(
+            hour.concerns,
+            vec!["Неудобная температура: 5.0°C", "Сильный ветер: 12.3 м/с"]
+        );
+        assert_eq!(hour.recommendations, vec!["Хорошие условия для съемки"]);
     }
 
     #[test]
     fn test_astrophotography_analysis_components() {
         let forecast = create_test_forecast();
-        let analysis = analyze_astrophotography_conditions(&forecast);
+        let analysis =
+            analyze_astrophotography_conditions(&forecast, &darkness_for(&forecast), Moscow);
 
         // Проверяем, что есть рекомендации
         assert!(!analysis.recommendations.is_empty());
 
         // Проверяем, что лучшие часы в разумных пределах
-        for &hour in &analysis.best_hours {
-            assert!((0..=23).contains(&hour));
+        for hour in &analysis.best_hours {
+            assert_eq!(hour.timezone(), Moscow);
         }
     }
 
@@ -741,11 +1409,14 @@ mod tests {
                 precipitation_probability: if hour % 2 == 0 { 0.0 } else { 100.0 }, // Экстремальные осадки
                 description: "экстремальные условия".to_string(),
                 timestamp: Utc::now() + chrono::Duration::hours(hour),
+                source: WeatherDataSource::Forecast,
+                cloud_layers: None,
+                dew_point: None,
             };
             extreme_forecast.hourly.push(weather_data);
         }
 
-        let analysis = analyze_weather_for_photography(&extreme_forecast);
+        let analysis = analyze_weather_for_photography(&extreme_forecast, &[], Moscow);
 
         // При экстремальных условиях оценка должна быть низкой
         assert!(analysis.overall_score < 5.0);
@@ -770,11 +1441,18 @@ mod tests {
                 precipitation_probability: 90.0, // Высокая вероятность осадков
                 description: "полная облачность".to_string(),
                 timestamp: Utc::now() + chrono::Duration::hours(hour),
+                source: WeatherDataSource::Forecast,
+                cloud_layers: None,
+                dew_point: None,
             };
             extreme_forecast.hourly.push(weather_data);
         }
 
-        let analysis = analyze_astrophotography_conditions(&extreme_forecast);
+        let analysis = analyze_astrophotography_conditions(
+            &extreme_forecast,
+            &darkness_for(&extreme_forecast),
+            Moscow,
+        );
 
         // При полной облачности астрофотография должна быть непригодна
         assert!(!analysis.is_suitable);
@@ -799,11 +1477,14 @@ mod tests {
                 precipitation_probability: 0.0, // Без осадков
                 description: "идеальные условия".to_string(),
                 timestamp: Utc::now() + chrono::Duration::hours(hour),
+                source: WeatherDataSource::Forecast,
+                cloud_layers: None,
+                dew_point: None,
             };
             perfect_forecast.hourly.push(weather_data);
         }
 
-        let analysis = analyze_weather_for_photography(&perfect_forecast);
+        let analysis = analyze_weather_for_photography(&perfect_forecast, &[], Moscow);
 
         // При идеальных условиях оценка должна быть высокой
         assert!(analysis.overall_score >= 8.0);
@@ -828,11 +1509,18 @@ mod tests {
                 precipitation_probability: 0.0, // Без осадков
                 description: "идеальная ночь".to_string(),
                 timestamp: Utc::now() + chrono::Duration::hours(hour),
+                source: WeatherDataSource::Forecast,
+                cloud_layers: None,
+                dew_point: None,
             };
             perfect_forecast.hourly.push(weather_data);
         }
 
-        let analysis = analyze_astrophotography_conditions(&perfect_forecast);
+        let analysis = analyze_astrophotography_conditions(
+            &perfect_forecast,
+            &darkness_for(&perfect_forecast),
+            Moscow,
+        );
 
         // При идеальных условиях астрофотография должна быть пригодна
         assert!(analysis.is_suitable);
@@ -855,10 +1543,13 @@ mod tests {
             precipitation_probability: 5.0,
             description: "ясно".to_string(),
             timestamp: Utc::now(),
+            source: WeatherDataSource::Forecast,
+            cloud_layers: None,
+            dew_point: None,
         };
         single_hour_forecast.hourly.push(weather_data);
 
-        let analysis = analyze_weather_for_photography(&single_hour_forecast);
+        let analysis = analyze_weather_for_photography(&single_hour_forecast, &[], Moscow);
 
         // Проверяем, что анализ работает с одним часом
         assert!(analysis.overall_score >= 0.0);
@@ -886,15 +1577,339 @@ mod tests {
                 precipitation_probability: if hour > 10 && hour < 14 { 40.0 } else { 10.0 },
                 description: "переменная погода".to_string(),
                 timestamp: Utc::now() + chrono::Duration::hours(hour),
+                source: WeatherDataSource::Forecast,
+                cloud_layers: None,
+                dew_point: None,
             };
             mixed_forecast.hourly.push(weather_data);
         }
 
-        let analysis = analyze_weather_for_photography(&mixed_forecast);
+        let analysis = analyze_weather_for_photography(&mixed_forecast, &[], Moscow);
 
         // Проверяем, что анализ работает со смешанными условиями
         assert!(analysis.overall_score >= 0.0);
         assert!(analysis.overall_score <= 10.0);
         assert!(!analysis.best_hours.is_empty());
     }
+
+    fn create_point(
+        timestamp: DateTime<Utc>,
+        temperature: f64,
+        cloud_cover: f64,
+        source: WeatherDataSource,
+    ) -> WeatherData {
+        WeatherData {
+            temperature,
+            humidity: 50.0,
+            wind_speed: 3.0,
+            cloud_cover,
+            visibility: 10.0,
+            precipitation_probability: 0.0,
+            description: format!("{}°C", temperature),
+            timestamp,
+            source,
+            cloud_layers: None,
+            dew_point: None,
+        }
+    }
+
+    #[test]
+    fn test_build_hourly_forecast_interpolates_between_points() {
+        let start = Utc::now();
+        let observed = create_point(start, 10.0, 0.0, WeatherDataSource::Observed);
+        let points = vec![
+            create_point(
+                start + chrono::Duration::hours(3),
+                16.0,
+                90.0,
+                WeatherDataSource::Forecast,
+            ),
+            create_point(
+                start + chrono::Duration::hours(6),
+                10.0,
+                30.0,
+                WeatherDataSource::Forecast,
+            ),
+        ];
+
+        let forecast = build_hourly_forecast(observed, &points, FORECAST_HOURS);
+
+        assert_eq!(forecast.hourly.len(), FORECAST_HOURS as usize);
+        assert_eq!(forecast.hourly[0].source, WeatherDataSource::Observed);
+        assert_eq!(forecast.hourly[0].temperature, 10.0);
+
+        // Между наблюдением (10°C) и первой точкой (16°C) рост на 2°C в час
+        assert!((forecast.hourly[1].temperature - 12.0).abs() < 1e-6);
+        assert!((forecast.hourly[2].temperature - 14.0).abs() < 1e-6);
+        assert!((forecast.hourly[3].temperature - 16.0).abs() < 1e-6);
+        assert!((forecast.hourly[4].cloud_cover - 70.0).abs() < 1e-6);
+
+        // После последней точки значения повторяются
+        assert!((forecast.hourly[10].temperature - 10.0).abs() < 1e-6);
+
+        for (hour, weather) in forecast.hourly.iter().enumerate().skip(1) {
+            assert_eq!(weather.source, WeatherDataSource::Forecast);
+            assert_eq!(
+                weather.timestamp,
+                start + chrono::Duration::hours(hour as i64)
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_hourly_forecast_ignores_past_points() {
+        let start = Utc::now();
+        let observed = create_point(start, 10.0, 0.0, WeatherDataSource::Observed);
+        let points = vec![
+            create_point(
+                start - chrono::Duration::hours(1),
+                30.0,
+                100.0,
+                WeatherDataSource::Forecast,
+            ),
+            create_point(
+                start + chrono::Duration::hours(2),
+                12.0,
+                0.0,
+                WeatherDataSource::Forecast,
+            ),
+        ];
+
+        let forecast = build_hourly_forecast(observed, &points, 3);
+
+        assert_eq!(forecast.hourly.len(), 3);
+        assert!((forecast.hourly[1].temperature - 11.0).abs() < 1e-6);
+        assert!((forecast.hourly[2].temperature - 12.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_synthesize_forecast_from_current() {
+        let observed = create_point(Utc::now(), 15.0, 50.0, WeatherDataSource::Observed);
+        let forecast = synthesize_forecast_from_current(&observed, FORECAST_HOURS);
+
+        assert_eq!(forecast.hourly.len(), FORECAST_HOURS as usize);
+        assert_eq!(forecast.hourly[0].source, WeatherDataSource::Observed);
+        assert_eq!(forecast.hourly[0].temperature, 15.0);
+        assert!(forecast.hourly[1..]
+            .iter()
+            .all(|w| w.source == WeatherDataSource::Synthetic));
+    }
+
+    struct StaticProvider {
+        coords: CityCoordinates,
+    }
+
+    #[async_trait::async_trait]
+    impl WeatherProvider for StaticProvider {
+        fn name(&self) -> &'static str {
+            "Static"
+        }
+
+        async fn geocode(&self, _city: &str) -> Result<CityCoordinates> {
+            Ok(self.coords)
+        }
+
+        async fn fetch_forecast(
+            &self,
+            coords: &CityCoordinates,
+            hours: i64,
+        ) -> Result<WeatherForecast> {
+            assert_eq!(*coords, self.coords);
+            let observed = create_point(Utc::now(), 12.0, 10.0, WeatherDataSource::Observed);
+            Ok(synthesize_forecast_from_current(&observed, hours))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_weather_service_dispatches_to_provider() {
+        let provider = StaticProvider {
+            coords: CityCoordinates {
+                lat: 59.9311,
+                lon: 30.3609,
+            },
+        };
+        let service = WeatherService::with_provider(Box::new(provider), "SPb".to_string());
+        let forecast = service.get_weather_forecast().await.unwrap();
+
+        assert_eq!(forecast.hourly.len(), FORECAST_HOURS as usize);
+        assert_eq!(forecast.hourly[0].temperature, 12.0);
+    }
+
+    #[tokio::test]
+    async fn test_weather_service_multi_day_horizon() {
+        let provider = StaticProvider {
+            coords: CityCoordinates {
+                lat: 59.9311,
+                lon: 30.3609,
+            },
+        };
+        let service = WeatherService::with_provider(Box::new(provider), "SPb".to_string())
+            .with_forecast_days(3);
+        assert_eq!(service.forecast_days(), 3);
+
+        let forecast = service.get_weather_forecast().await.unwrap();
+        assert_eq!(forecast.hourly.len(), 3 * FORECAST_HOURS as usize);
+        assert_eq!(forecast.first_day().hourly.len(), FORECAST_HOURS as usize);
+
+        // Горизонт ограничивается максимумом
+        let provider = StaticProvider {
+            coords: CityCoordinates { lat: 0.0, lon: 0.0 },
+        };
+        let service = WeatherService::with_provider(Box::new(provider), "Test".to_string())
+            .with_forecast_days(30);
+        assert_eq!(service.forecast_days(), MAX_FORECAST_DAYS);
+    }
+
+    #[test]
+    fn test_weather_analysis_multi_day_score_is_average() {
+        let start = Utc::now();
+        let hourly = (0..72)
+            .map(|hour| {
+                create_point(
+                    start + chrono::Duration::hours(hour),
+                    20.0,
+                    10.0,
+                    WeatherDataSource::Forecast,
+                )
+            })
+            .collect();
+        let analysis = analyze_weather_for_photography(&WeatherForecast { hourly }, &[], Moscow);
+
+        // Оценка усредняется по всем часам, а не делится на 24
+        assert!(analysis.overall_score <= 10.0);
+        assert!(analysis.overall_score >= 7.0);
+    }
+
+    #[test]
+    fn test_forecast_starting_at() {
+        let start = Utc.with_ymd_and_hms(2024, 6, 15, 0, 0, 0).unwrap();
+        let forecast = || WeatherForecast {
+            hourly: (0..48)
+                .map(|hour| {
+                    create_point(
+                        start + chrono::Duration::hours(hour),
+                        20.0,
+                        30.0,
+                        WeatherDataSource::Forecast,
+                    )
+                })
+                .collect(),
+        };
+
+        // Минуты отбрасываются: прогноз начинается с часа, содержащего момент
+        let from_noon = forecast()
+            .starting_at(Utc.with_ymd_and_hms(2024, 6, 15, 12, 40, 0).unwrap())
+            .unwrap();
+        assert_eq!(from_noon.hourly.len(), 36);
+        assert_eq!(
+            from_noon.hourly[0].timestamp,
+            Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap()
+        );
+
+        // Моменты вне горизонта прогноза - ошибка
+        assert!(forecast()
+            .starting_at(Utc.with_ymd_and_hms(2024, 6, 14, 23, 0, 0).unwrap())
+            .is_err());
+        assert!(forecast()
+            .starting_at(Utc.with_ymd_and_hms(2024, 6, 17, 0, 0, 0).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_analyze_daily_weather_groups_by_local_date() {
+        let start = Moscow
+            .with_ymd_and_hms(2024, 6, 15, 0, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let hourly = (0..48)
+            .map(|hour| {
+                let cloud_cover = if hour < 24 { 90.0 } else { 10.0 };
+                create_point(
+                    start + chrono::Duration::hours(hour),
+                    20.0,
+                    cloud_cover,
+                    WeatherDataSource::Forecast,
+                )
+            })
+            .collect();
+
+        let days = analyze_daily_weather(&WeatherForecast { hourly }, &[], Moscow);
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2024, 6, 15).unwrap());
+        assert_eq!(days[1].date, NaiveDate::from_ymd_opt(2024, 6, 16).unwrap());
+        assert_eq!(days[0].hours_count, 24);
+        assert_eq!(days[0].avg_cloud_cover, 90.0);
+        assert_eq!(days[1].avg_cloud_cover, 10.0);
+        assert!(days[1].score > days[0].score);
+        // Лучшие часы хранят дату: все они относятся к своему дню
+        assert!(!days[1].best_hours.is_empty());
+        assert!(days[1]
+            .best_hours
+            .iter()
+            .all(|hour| hour.date_naive() == days[1].date));
+    }
+
+    /// Провайдер без прямого геокодирования: проверяет, что заданные координаты
+    /// используются без обращения к поиску по названию
+    struct ReverseOnlyProvider {
+        expected: CityCoordinates,
+    }
+
+    #[async_trait::async_trait]
+    impl WeatherProvider for ReverseOnlyProvider {
+        fn name(&self) -> &'static str {
+            "ReverseOnly"
+        }
+
+        async fn geocode(&self, city: &str) -> Result<CityCoordinates> {
+            Err(anyhow::anyhow!("Геокодирование '{}' не ожидалось", city))
+        }
+
+        async fn reverse_geocode(&self, coords: &CityCoordinates) -> Result<Option<String>> {
+            assert_eq!(*coords, self.expected);
+            Ok(Some("Санкт-Петербург".to_string()))
+        }
+
+        async fn fetch_forecast(
+            &self,
+            coords: &CityCoordinates,
+            hours: i64,
+        ) -> Result<WeatherForecast> {
+            assert_eq!(*coords, self.expected);
+            let observed = create_point(Utc::now(), 5.0, 40.0, WeatherDataSource::Observed);
+            Ok(synthesize_forecast_from_current(&observed, hours))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_weather_service_uses_given_coordinates() {
+        let expected = CityCoordinates {
+            lat: 59.9386,
+            lon: 30.3141,
+        };
+        let service = WeatherService::with_provider(
+            Box::new(ReverseOnlyProvider { expected }),
+            "SPb".to_string(),
+        )
+        .with_coordinates(expected.lat, expected.lon);
+
+        let forecast = service.get_weather_forecast().await.unwrap();
+        assert_eq!(forecast.hourly[0].temperature, 5.0);
+        assert_eq!(service.resolve_location_name().await, "Санкт-Петербург");
+    }
+
+    #[tokio::test]
+    async fn test_weather_service_without_coordinates_uses_city_name() {
+        let service = WeatherService::with_provider(
+            Box::new(ReverseOnlyProvider {
+                expected: CityCoordinates { lat: 0.0, lon: 0.0 },
+            }),
+            "SPb".to_string(),
+        );
+
+        // Без координат сервис ищет город по названию
+        assert!(service.get_weather_forecast().await.is_err());
+        assert_eq!(service.resolve_location_name().await, "SPb");
+    }
 }
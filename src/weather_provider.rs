@@ -0,0 +1,913 @@
+//! # Weather Provider Module
+//!
+//! Модуль с источниками погодных данных для [`WeatherService`](crate::weather::WeatherService).
+//! Каждый источник реализует трейт [`WeatherProvider`], поэтому сервис погоды
+//! не зависит от конкретного API.
+//!
+//! ## Основные компоненты
+//!
+//! - [`WeatherProvider`] - Трейт источника погодных данных
+//! - [`WeatherProviderKind`] - Выбор источника через конфигурацию
+//! - [`OpenWeatherMapProvider`] - OpenWeatherMap (нужен API ключ)
+//! - [`OpenMeteoProvider`] - Open-Meteo (без API ключа)
+//!
+//! ## Пример использования
+//!
+//! ```rust
+//! use my_dashboard::weather_provider::{create_provider, WeatherProviderKind};
+//!
+//! // Выбираем провайдер по названию из конфигурации
+//! let kind: WeatherProviderKind = "open-meteo".parse().unwrap();
+//! let provider = create_provider(kind, "demo_key".to_string(), "ru");
+//!
+//! assert_eq!(provider.name(), "Open-Meteo");
+//! assert!(provider.has_credentials());
+//! ```
+
+use crate::cache::{CacheSource, ResponseCache};
+use crate::config::DEFAULT_LANGUAGE;
+use crate::http;
+use crate::weather::{
+    build_hourly_forecast, synthesize_forecast_from_current, CloudLayers, WeatherData,
+    WeatherDataSource, WeatherForecast, FORECAST_HOURS, MAX_FORECAST_DAYS,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Координаты точки, для которой запрашивается погода
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub struct CityCoordinates {
+    /// Широта в градусах
+    pub lat: f64,
+    /// Долгота в градусах
+    pub lon: f64,
+}
+
+/// Источник погодных данных
+///
+/// Реализации отвечают за геокодирование и получение почасового прогноза
+/// в общем формате [`WeatherForecast`].
+#[async_trait]
+pub trait WeatherProvider: Send + Sync {
+    /// Название провайдера для логов и вывода
+    fn name(&self) -> &'static str;
+
+    /// Есть ли у провайдера все необходимое для реальных запросов (например, API ключ)
+    fn has_credentials(&self) -> bool {
+        true
+    }
+
+    /// Максимальный горизонт прогноза, который поддерживает провайдер, в днях
+    fn max_forecast_days(&self) -> u32 {
+        MAX_FORECAST_DAYS
+    }
+
+    /// Определяет координаты города по названию
+    async fn geocode(&self, city: &str) -> Result<CityCoordinates>;
+
+    /// Определяет название места по координатам (только для отображения)
+    ///
+    /// Возвращает `None`, если провайдер не поддерживает обратное геокодирование.
+    async fn reverse_geocode(&self, _coords: &CityCoordinates) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Получает почасовой прогноз на `hours` часов для указанных координат
+    async fn fetch_forecast(&self, coords: &CityCoordinates, hours: i64)
+        -> Result<WeatherForecast>;
+}
+
+/// Доступные источники погодных данных
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeatherProviderKind {
+    /// OpenWeatherMap (требует `OPENWEATHER_API_KEY`)
+    #[default]
+    OpenWeatherMap,
+    /// Open-Meteo (без ключа)
+    OpenMeteo,
+}
+
+impl FromStr for WeatherProviderKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "openweathermap" | "openweather" | "owm" => Ok(Self::OpenWeatherMap),
+            "open-meteo" | "openmeteo" | "open_meteo" => Ok(Self::OpenMeteo),
+            other => Err(anyhow::anyhow!(
+                "Неизвестный провайдер погоды '{}'. Доступны: openweathermap, open-meteo",
+                other
+            )),
+        }
+    }
+}
+
+/// Определяет провайдер погоды из переменной окружения `WEATHER_PROVIDER`
+///
+/// При отсутствии или некорректном значении используется OpenWeatherMap.
+pub fn provider_kind_from_env() -> WeatherProviderKind {
+    match std::env::var("WEATHER_PROVIDER") {
+        Ok(value) => value.parse().unwrap_or_else(|e| {
+            warn!("{}, используем OpenWeatherMap", e);
+            WeatherProviderKind::default()
+        }),
+        Err(_) => WeatherProviderKind::default(),
+    }
+}
+
+/// Создает провайдер указанного типа
+///
+/// `api_key` используется только провайдерами, которым нужен ключ;
+/// `language` - язык названий мест при геокодировании.
+pub fn create_provider(
+    kind: WeatherProviderKind,
+    api_key: String,
+    language: &str,
+) -> Box<dyn WeatherProvider> {
+    match kind {
+        WeatherProviderKind::OpenWeatherMap => {
+            Box::new(OpenWeatherMapProvider::new(api_key).with_language(language))
+        }
+        WeatherProviderKind::OpenMeteo => {
+            Box::new(OpenMeteoProvider::new().with_language(language))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// OpenWeatherMap
+// ---------------------------------------------------------------------------
+
+// Структуры для парсинга ответа OpenWeatherMap API
+#[derive(Debug, Deserialize)]
+struct CurrentWeatherResponse {
+    main: CurrentWeatherMain,
+    wind: CurrentWeatherWind,
+    clouds: CurrentWeatherClouds,
+    visibility: f64,
+    weather: Vec<OpenWeatherCondition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentWeatherMain {
+    temp: f64,
+    humidity: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentWeatherWind {
+    speed: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentWeatherClouds {
+    all: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenWeatherCondition {
+    description: String,
+}
+
+// Структуры для парсинга ответа OpenWeatherMap 5 day / 3 hour Forecast API
+#[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    list: Vec<ForecastItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForecastItem {
+    dt: i64,
+    main: CurrentWeatherMain,
+    wind: CurrentWeatherWind,
+    clouds: CurrentWeatherClouds,
+    // В прогнозе видимость может отсутствовать
+    #[serde(default = "default_visibility_meters")]
+    visibility: f64,
+    // Вероятность осадков в долях (0-1)
+    #[serde(default)]
+    pop: f64,
+    weather: Vec<OpenWeatherCondition>,
+}
+
+fn default_visibility_meters() -> f64 {
+    10_000.0
+}
+
+impl CurrentWeatherResponse {
+    fn to_weather_data(&self, timestamp: DateTime<Utc>) -> WeatherData {
+        WeatherData {
+            temperature: self.main.temp,
+            humidity: self.main.humidity,
+            wind_speed: self.wind.speed,
+            cloud_cover: self.clouds.all,
+            visibility: self.visibility / 1000.0, // конвертируем в км
+            // Current Weather API не отдает вероятность осадков, оцениваем по облачности
+            precipitation_probability: if self.clouds.all > 70.0 { 20.0 } else { 5.0 },
+            description: condition_description(&self.weather),
+            timestamp,
+            source: WeatherDataSource::Observed,
+            cloud_layers: None,
+            dew_point: None,
+        }
+    }
+}
+
+impl ForecastItem {
+    fn to_weather_data(&self) -> Option<WeatherData> {
+        Some(WeatherData {
+            temperature: self.main.temp,
+            humidity: self.main.humidity,
+            wind_speed: self.wind.speed,
+            cloud_cover: self.clouds.all,
+            visibility: self.visibility / 1000.0,
+            precipitation_probability: (self.pop * 100.0).clamp(0.0, 100.0),
+            description: condition_description(&self.weather),
+            timestamp: DateTime::from_timestamp(self.dt, 0)?,
+            source: WeatherDataSource::Forecast,
+            cloud_layers: None,
+            dew_point: None,
+        })
+    }
+}
+
+fn condition_description(conditions: &[OpenWeatherCondition]) -> String {
+    conditions
+        .first()
+        .map(|w| w.description.clone())
+        .unwrap_or_else(|| "Неизвестно".to_string())
+}
+
+fn parse_owm_current(body: &str, timestamp: DateTime<Utc>) -> Result<WeatherData> {
+    let response: CurrentWeatherResponse = serde_json::from_str(body)?;
+    info!(
+        "Получены данные погоды: {}°C, облачность {}%",
+        response.main.temp, response.clouds.all
+    );
+    Ok(response.to_weather_data(timestamp))
+}
+
+fn parse_owm_forecast_points(body: &str) -> Result<Vec<WeatherData>> {
+    let response: ForecastResponse = serde_json::from_str(body)?;
+    let points: Vec<WeatherData> = response
+        .list
+        .iter()
+        .filter_map(ForecastItem::to_weather_data)
+        .collect();
+
+    if points.is_empty() {
+        return Err(anyhow::anyhow!("Прогноз погоды пуст"));
+    }
+
+    Ok(points)
+}
+
+// Структура для парсинга ответа OpenWeatherMap Reverse Geocoding API
+#[derive(Debug, Deserialize)]
+struct ReverseGeocodingItem {
+    name: String,
+    #[serde(default)]
+    local_names: std::collections::HashMap<String, String>,
+}
+
+fn parse_owm_reverse_geocoding(body: &str, language: &str) -> Result<Option<String>> {
+    let places: Vec<ReverseGeocodingItem> = serde_json::from_str(body)?;
+    Ok(places.into_iter().next().map(|mut place| {
+        // Предпочитаем название на языке из конфигурации, если оно есть
+        place.local_names.remove(language).unwrap_or(place.name)
+    }))
+}
+
+fn parse_owm_geocoding(body: &str, city: &str) -> Result<CityCoordinates> {
+    let coords: Vec<CityCoordinates> = serde_json::from_str(body)?;
+    coords
+        .first()
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("Город '{}' не найден", city))
+}
+
+/// Провайдер OpenWeatherMap: Current Weather + 5 day / 3 hour Forecast API
+pub struct OpenWeatherMapProvider {
+    api_key: String,
+    language: String,
+    cache: ResponseCache,
+}
+
+impl OpenWeatherMapProvider {
+    pub fn new(api_key: String) -> Self {
+        Self {
+            api_key,
+            language: DEFAULT_LANGUAGE.to_string(),
+            cache: ResponseCache::from_env(),
+        }
+    }
+
+    /// Задает язык названий мест при обратном геокодировании
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = language.to_string();
+        self
+    }
+
+    /// Выполняет запрос через кэш; `key` не должен содержать API ключ
+    async fn get_text(&self, source: CacheSource, key: &str, url: &str) -> Result<String> {
+        self.cache
+            .get_or_fetch(source, key, || async {
+                http::get_text(url).await.map_err(|e| match e.status() {
+                    Some(status) => {
+                        let error_message = describe_owm_http_error(status);
+                        warn!("Ошибка API: {}", error_message);
+                        anyhow::anyhow!(error_message)
+                    }
+                    None => e.into(),
+                })
+            })
+            .await
+    }
+}
+
+fn describe_owm_http_error(status: reqwest::StatusCode) -> String {
+    match status.as_u16() {
+        401 => "Неверный API ключ. Получите бесплатный ключ на https://openweathermap.org/api"
+            .to_string(),
+        429 => "Превышен лимит запросов. Попробуйте позже.".to_string(),
+        404 => "Данные для указанной локации не найдены".to_string(),
+        _ => format!("HTTP ошибка {} при получении данных погоды", status),
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenWeatherMapProvider {
+    fn name(&self) -> &'static str {
+        "OpenWeatherMap"
+    }
+
+    fn has_credentials(&self) -> bool {
+        !self.api_key.is_empty() && self.api_key != "demo_key"
+    }
+
+    fn max_forecast_days(&self) -> u32 {
+        // Бесплатный Forecast API отдает данные на 5 дней вперед
+        5
+    }
+
+    async fn geocode(&self, city: &str) -> Result<CityCoordinates> {
+        debug!(
+            "🗺️ API ЗАПРОС: OpenWeather Geocoding API для города {}",
+            city
+        );
+        // Название города кодируется в запросе: пробелы, `&` и кириллица
+        let url = reqwest::Url::parse_with_params(
+            "https://api.openweathermap.org/geo/1.0/direct",
+            &[
+                ("q", city),
+                ("limit", "1"),
+                ("appid", self.api_key.as_str()),
+            ],
+        )?;
+        let key = format!("owm-direct-{}", city.to_lowercase());
+
+        let body = self
+            .get_text(CacheSource::Geocoding, &key, url.as_str())
+            .await
+            .map_err(|e| anyhow::anyhow!("Ошибка получения координат города '{}': {}", city, e))?;
+
+        parse_owm_geocoding(&body, city)
+    }
+
+    async fn reverse_geocode(&self, coords: &CityCoordinates) -> Result<Option<String>> {
+        debug!(
+            "🗺️ API ЗАПРОС: OpenWeather Reverse Geocoding API для lat={}, lon={}",
+            coords.lat, coords.lon
+        );
+        let url = format!(
+            "https://api.openweathermap.org/geo/1.0/reverse?lat={}&lon={}&limit=1&appid={}",
+            coords.lat, coords.lon, self.api_key
+        );
+
+        let key = format!("owm-reverse-{}-{}", coords.lat, coords.lon);
+
+        parse_owm_reverse_geocoding(
+            &self.get_text(CacheSource::Geocoding, &key, &url).await?,
+            &self.language,
+        )
+    }
+
+    async fn fetch_forecast(
+        &self,
+        coords: &CityCoordinates,
+        hours: i64,
+    ) -> Result<WeatherForecast> {
+        let current_time = Utc::now();
+
+        debug!(
+            "🌤️ API ЗАПРОС: OpenWeather API для lat={}, lon={}",
+            coords.lat, coords.lon
+        );
+        let current_url = format!(
+            "https://api.openweathermap.org/data/2.5/weather?lat={}&lon={}&units=metric&appid={}",
+            coords.lat, coords.lon, self.api_key
+        );
+        let current_key = format!("owm-current-{}-{}", coords.lat, coords.lon);
+
+        debug!(
+            "📅 API ЗАПРОС: OpenWeather Forecast API для lat={}, lon={}",
+            coords.lat, coords.lon
+        );
+        let forecast_url = format!(
+            "https://api.openweathermap.org/data/2.5/forecast?lat={}&lon={}&units=metric&appid={}",
+            coords.lat, coords.lon, self.api_key
+        );
+        let forecast_key = format!("owm-forecast-{}-{}", coords.lat, coords.lon);
+
+        // Текущая погода и прогноз запрашиваются параллельно
+        let (current_body, forecast_body) = tokio::join!(
+            self.get_text(CacheSource::Weather, &current_key, &current_url),
+            self.get_text(CacheSource::Weather, &forecast_key, &forecast_url),
+        );
+        let observed = parse_owm_current(&current_body?, current_time)?;
+        let points = forecast_body.and_then(|body| parse_owm_forecast_points(&body));
+
+        match points {
+            Ok(points) => {
+                let forecast = build_hourly_forecast(observed, &points, hours);
+                debug!(
+                    "Построен почасовой прогноз на {} часов из {} точек API",
+                    forecast.hourly.len(),
+                    points.len()
+                );
+                Ok(forecast)
+            }
+            Err(e) => {
+                warn!(
+                    "Не удалось получить прогноз ({}), используется синтетический прогноз",
+                    e
+                );
+                Ok(synthesize_forecast_from_current(&observed, hours))
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Open-Meteo
+// ---------------------------------------------------------------------------
+
+const OPEN_METEO_VARIABLES: &str =
+    "temperature_2m,relative_humidity_2m,dew_point_2m,wind_speed_10m,cloud_cover,visibility,weather_code";
+
+// Структуры для парсинга ответа Open-Meteo Forecast API (timeformat=unixtime)
+#[derive(Debug, Deserialize)]
+struct OpenMeteoResponse {
+    current: OpenMeteoCurrent,
+    hourly: OpenMeteoHourly,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoCurrent {
+    time: i64,
+    temperature_2m: f64,
+    relative_humidity_2m: f64,
+    dew_point_2m: Option<f64>,
+    wind_speed_10m: f64,
+    cloud_cover: f64,
+    visibility: Option<f64>,
+    weather_code: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoHourly {
+    time: Vec<i64>,
+    temperature_2m: Vec<Option<f64>>,
+    relative_humidity_2m: Vec<Option<f64>>,
+    #[serde(default)]
+    dew_point_2m: Vec<Option<f64>>,
+    wind_speed_10m: Vec<Option<f64>>,
+    cloud_cover: Vec<Option<f64>>,
+    visibility: Vec<Option<f64>>,
+    precipitation_probability: Vec<Option<f64>>,
+    weather_code: Vec<Option<u32>>,
+    #[serde(default)]
+    cloud_cover_low: Vec<Option<f64>>,
+    #[serde(default)]
+    cloud_cover_mid: Vec<Option<f64>>,
+    #[serde(default)]
+    cloud_cover_high: Vec<Option<f64>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoGeocodingResponse {
+    #[serde(default)]
+    results: Vec<OpenMeteoGeocodingResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoGeocodingResult {
+    latitude: f64,
+    longitude: f64,
+}
+
+/// Описание погоды по коду WMO, который возвращает Open-Meteo
+fn wmo_description(code: Option<u32>) -> String {
+    match code {
+        Some(0) => "ясно",
+        Some(1) => "преимущественно ясно",
+        Some(2) => "переменная облачность",
+        Some(3) => "пасмурно",
+        Some(45) | Some(48) => "туман",
+        Some(51) | Some(53) | Some(55) => "морось",
+        Some(56) | Some(57) => "ледяная морось",
+        Some(61) | Some(63) | Some(65) => "дождь",
+        Some(66) | Some(67) => "ледяной дождь",
+        Some(71) | Some(73) | Some(75) => "снег",
+        Some(77) => "снежные зерна",
+        Some(80) | Some(81) | Some(82) => "ливень",
+        Some(85) | Some(86) => "снегопад",
+        Some(95) => "гроза",
+        Some(96) | Some(99) => "гроза с градом",
+        _ => "Неизвестно",
+    }
+    .to_string()
+}
+
+fn parse_open_meteo_forecast(body: &str, hours: i64) -> Result<WeatherForecast> {
+    let response: OpenMeteoResponse = serde_json::from_str(body)?;
+    let hourly = &response.hourly;
+    let value = |values: &[Option<f64>], idx: usize| values.get(idx).copied().flatten();
+    // Облачность по ярусам есть, только если известны все три яруса
+    let cloud_layers = |idx: usize| {
+        Some(CloudLayers {
+            low: value(&hourly.cloud_cover_low, idx)?,
+            mid: value(&hourly.cloud_cover_mid, idx)?,
+            high: value(&hourly.cloud_cover_high, idx)?,
+        })
+    };
+
+    let points: Vec<WeatherData> = hourly
+        .time
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, &time)| {
+            Some(WeatherData {
+                // Час без основных значений пропускаем: нули выглядели бы
+                // как ясное безветренное небо
+                temperature: value(&hourly.temperature_2m, idx)?,
+                humidity: value(&hourly.relative_humidity_2m, idx)?,
+                wind_speed: value(&hourly.wind_speed_10m, idx)?,
+                cloud_cover: value(&hourly.cloud_cover, idx)?,
+                visibility: value(&hourly.visibility, idx).unwrap_or(10_000.0) / 1000.0,
+                precipitation_probability: value(&hourly.precipitation_probability, idx)
+                    .unwrap_or(0.0),
+                description: wmo_description(hourly.weather_code.get(idx).copied().flatten()),
+                timestamp: DateTime::from_timestamp(time, 0)?,
+                source: WeatherDataSource::Forecast,
+                cloud_layers: cloud_layers(idx),
+                dew_point: value(&hourly.dew_point_2m, idx),
+            })
+        })
+        .collect();
+
+    if points.is_empty() {
+        return Err(anyhow::anyhow!("Прогноз погоды пуст"));
+    }
+
+    let current = &response.current;
+    let timestamp = DateTime::from_timestamp(current.time, 0)
+        .ok_or_else(|| anyhow::anyhow!("Некорректное время текущей погоды"))?;
+
+    // Для текущей погоды вероятность осадков и облачность по ярусам берем
+    // из ближайшего часа прогноза
+    let nearest = points
+        .iter()
+        .min_by_key(|p| (p.timestamp - timestamp).num_seconds().abs());
+    let precipitation_probability = nearest.map_or(0.0, |p| p.precipitation_probability);
+
+    let observed = WeatherData {
+        temperature: current.temperature_2m,
+        humidity: current.relative_humidity_2m,
+        wind_speed: current.wind_speed_10m,
+        cloud_cover: current.cloud_cover,
+        visibility: current.visibility.unwrap_or(10_000.0) / 1000.0,
+        precipitation_probability,
+        description: wmo_description(current.weather_code),
+        timestamp,
+        source: WeatherDataSource::Observed,
+        cloud_layers: nearest.and_then(|p| p.cloud_layers),
+        dew_point: current.dew_point_2m,
+    };
+
+    info!(
+        "Получены данные погоды Open-Meteo: {}°C, облачность {}%",
+        observed.temperature, observed.cloud_cover
+    );
+
+    Ok(build_hourly_forecast(observed, &points, hours))
+}
+
+fn parse_open_meteo_geocoding(body: &str, city: &str) -> Result<CityCoordinates> {
+    let response: OpenMeteoGeocodingResponse = serde_json::from_str(body)?;
+    response
+        .results
+        .first()
+        .map(|r| CityCoordinates {
+            lat: r.latitude,
+            lon: r.longitude,
+        })
+        .ok_or_else(|| anyhow::anyhow!("Город '{}' не найден", city))
+}
+
+/// Провайдер Open-Meteo: бесплатный API без ключа
+pub struct OpenMeteoProvider {
+    language: String,
+    cache: ResponseCache,
+}
+
+impl OpenMeteoProvider {
+    pub fn new() -> Self {
+        Self {
+            language: DEFAULT_LANGUAGE.to_string(),
+            cache: ResponseCache::from_env(),
+        }
+    }
+
+    /// Задает язык названий мест при геокодировании
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = language.to_string();
+        self
+    }
+
+    async fn get_text(&self, source: CacheSource, key: &str, url: &str) -> Result<String> {
+        self.cache
+            .get_or_fetch(source, key, || async {
+                http::get_text(url).await.map_err(|e| match e.status() {
+                    Some(status) => {
+                        warn!("Ошибка Open-Meteo API: HTTP {}", status);
+                        anyhow::anyhow!("HTTP ошибка {} при получении данных Open-Meteo", status)
+                    }
+                    None => e.into(),
+                })
+            })
+            .await
+    }
+}
+
+impl Default for OpenMeteoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenMeteoProvider {
+    fn name(&self) -> &'static str {
+        "Open-Meteo"
+    }
+
+    async fn geocode(&self, city: &str) -> Result<CityCoordinates> {
+        debug!(
+            "🗺️ API ЗАПРОС: Open-Meteo Geocoding API для города {}",
+            city
+        );
+        let url = reqwest::Url::parse_with_params(
+            "https://geocoding-api.open-meteo.com/v1/search",
+            &[
+                ("name", city),
+                ("count", "1"),
+                ("language", self.language.as_str()),
+                ("format", "json"),
+            ],
+        )?;
+
+        let key = format!(
+            "open-meteo-search-{}-{}",
+            self.language,
+            city.to_lowercase()
+        );
+
+        parse_open_meteo_geocoding(
+            &self
+                .get_text(CacheSource::Geocoding, &key, url.as_str())
+                .await?,
+            city,
+        )
+    }
+
+    async fn fetch_forecast(
+        &self,
+        coords: &CityCoordinates,
+        hours: i64,
+    ) -> Result<WeatherForecast> {
+        debug!(
+            "🌤️ API ЗАПРОС: Open-Meteo Forecast API для lat={}, lon={}",
+            coords.lat, coords.lon
+        );
+        // Прогноз начинается с полуночи по UTC, поэтому берем на один день больше
+        let forecast_days = (hours + FORECAST_HOURS - 1) / FORECAST_HOURS + 1;
+        let url = format!(
+            "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&current={}&hourly={},precipitation_probability,cloud_cover_low,cloud_cover_mid,cloud_cover_high&wind_speed_unit=ms&timeformat=unixtime&forecast_days={}",
+            coords.lat, coords.lon, OPEN_METEO_VARIABLES, OPEN_METEO_VARIABLES, forecast_days
+        );
+
+        let key = format!(
+            "open-meteo-forecast-{}-{}-{}d",
+            coords.lat, coords.lon, forecast_days
+        );
+
+        parse_open_meteo_forecast(
+            &self.get_text(CacheSource::Weather, &key, &url).await?,
+            hours,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const OWM_CURRENT: &str = include_str!("../tests/fixtures/openweathermap_current.json");
+    const OWM_FORECAST: &str = include_str!("../tests/fixtures/openweathermap_forecast.json");
+    const OWM_GEOCODING: &str = include_str!("../tests/fixtures/openweathermap_geocoding.json");
+    const OWM_REVERSE_GEOCODING: &str =
+        include_str!("../tests/fixtures/openweathermap_reverse_geocoding.json");
+    const OPEN_METEO_FORECAST: &str = include_str!("../tests/fixtures/open_meteo_forecast.json");
+    const OPEN_METEO_GEOCODING: &str = include_str!("../tests/fixtures/open_meteo_geocoding.json");
+
+    #[test]
+    fn test_provider_kind_parsing() {
+        assert_eq!(
+            "openweathermap".parse::<WeatherProviderKind>().unwrap(),
+            WeatherProviderKind::OpenWeatherMap
+        );
+        assert_eq!(
+            "OWM".parse::<WeatherProviderKind>().unwrap(),
+            WeatherProviderKind::OpenWeatherMap
+        );
+        assert_eq!(
+            "Open-Meteo".parse::<WeatherProviderKind>().unwrap(),
+            WeatherProviderKind::OpenMeteo
+        );
+        assert!("yandex".parse::<WeatherProviderKind>().is_err());
+    }
+
+    #[test]
+    fn test_create_provider() {
+        let owm = create_provider(
+            WeatherProviderKind::OpenWeatherMap,
+            "demo_key".to_string(),
+            "ru",
+        );
+        assert_eq!(owm.name(), "OpenWeatherMap");
+        assert!(!owm.has_credentials());
+
+        let owm = create_provider(
+            WeatherProviderKind::OpenWeatherMap,
+            "real_key".to_string(),
+            "ru",
+        );
+        assert!(owm.has_credentials());
+
+        let open_meteo = create_provider(WeatherProviderKind::OpenMeteo, String::new(), "ru");
+        assert_eq!(open_meteo.name(), "Open-Meteo");
+        assert!(open_meteo.has_credentials());
+    }
+
+    #[test]
+    fn test_owm_fixtures_build_hourly_forecast() {
+        let first_point_time = DateTime::from_timestamp(1_705_287_600, 0).unwrap();
+        let observed =
+            parse_owm_current(OWM_CURRENT, first_point_time - chrono::Duration::hours(1)).unwrap();
+        assert_eq!(observed.source, WeatherDataSource::Observed);
+        assert_eq!(observed.temperature, -7.4);
+        assert_eq!(observed.visibility, 10.0);
+        assert_eq!(observed.description, "пасмурно");
+
+        let points = parse_owm_forecast_points(OWM_FORECAST).unwrap();
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[0].timestamp, first_point_time);
+        assert!((points[1].precipitation_probability - 40.0).abs() < 1e-6);
+        // Видимость отсутствует во второй точке - используется значение по умолчанию
+        assert_eq!(points[1].visibility, 10.0);
+
+        let forecast = build_hourly_forecast(observed, &points, FORECAST_HOURS);
+        assert_eq!(forecast.hourly.len(), FORECAST_HOURS as usize);
+        assert_eq!(forecast.hourly[1].timestamp, first_point_time);
+        assert_eq!(forecast.hourly[1].temperature, points[0].temperature);
+        assert!(forecast.hourly[1..]
+            .iter()
+            .all(|w| w.source == WeatherDataSource::Forecast));
+    }
+
+    #[test]
+    fn test_owm_geocoding_fixture() {
+        let coords = parse_owm_geocoding(OWM_GEOCODING, "Moscow").unwrap();
+        assert_eq!(
+            coords,
+            CityCoordinates {
+                lat: 55.7504461,
+                lon: 37.6174943
+            }
+        );
+        assert!(parse_owm_geocoding("[]", "Nowhere").is_err());
+    }
+
+    #[test]
+    fn test_owm_reverse_geocoding_fixture() {
+        assert_eq!(
+            parse_owm_reverse_geocoding(OWM_REVERSE_GEOCODING, "ru").unwrap(),
+            Some("Москва".to_string())
+        );
+        assert_eq!(
+            parse_owm_reverse_geocoding(OWM_REVERSE_GEOCODING, "de").unwrap(),
+            Some("Moskau".to_string())
+        );
+        // Без локальных названий используется основное
+        assert_eq!(
+            parse_owm_reverse_geocoding(
+                r#"[{"name": "Reykjavik", "lat": 64.1, "lon": -21.9}]"#,
+                "ru"
+            )
+            .unwrap(),
+            Some("Reykjavik".to_string())
+        );
+        assert_eq!(parse_owm_reverse_geocoding("[]", "ru").unwrap(), None);
+    }
+
+    #[test]
+    fn test_open_meteo_fixture_builds_hourly_forecast() {
+        let forecast = parse_open_meteo_forecast(OPEN_METEO_FORECAST, FORECAST_HOURS).unwrap();
+
+        assert_eq!(forecast.hourly.len(), FORECAST_HOURS as usize);
+
+        let observed = &forecast.hourly[0];
+        assert_eq!(observed.source, WeatherDataSource::Observed);
+        assert_eq!(observed.temperature, -6.8);
+        assert_eq!(observed.wind_speed, 3.1);
+        assert_eq!(observed.visibility, 24.14);
+        assert_eq!(observed.description, "пасмурно");
+        // Вероятность осадков берется из ближайшего часа
+        assert_eq!(observed.precipitation_probability, 10.0);
+        assert_eq!(observed.dew_point, Some(-8.6));
+        assert_eq!(
+            observed.cloud_layers,
+            Some(CloudLayers {
+                low: 100.0,
+                mid: 60.0,
+                high: 20.0
+            })
+        );
+
+        // Первый час прогноза интерполирован между 12:00 (-6.0) и 13:00 (-5.0)
+        let next = &forecast.hourly[1];
+        assert_eq!(next.source, WeatherDataSource::Forecast);
+        assert!((next.temperature - (-5.5)).abs() < 1e-6);
+        assert_eq!(next.description, "снег");
+        assert!((next.dew_point.unwrap() - (-7.75)).abs() < 1e-6);
+        assert_eq!(
+            next.cloud_layers,
+            Some(CloudLayers {
+                low: 85.0,
+                mid: 70.0,
+                high: 25.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_open_meteo_skips_hours_with_missing_values() {
+        let body = OPEN_METEO_FORECAST.replace(
+            r#""cloud_cover": [100, 100, 96, 80, 64]"#,
+            r#""cloud_cover": [100, null, 96, 80, 64]"#,
+        );
+        let forecast = parse_open_meteo_forecast(&body, FORECAST_HOURS).unwrap();
+
+        // Час 12:00 без облачности пропущен: 12:30 интерполируется между
+        // наблюдением в 11:30 (100%) и 13:00 (96%)
+        assert!((forecast.hourly[1].cloud_cover - (100.0 - 4.0 * 2.0 / 3.0)).abs() < 1e-6);
+        assert!(forecast.hourly.iter().all(|hour| hour.cloud_cover > 0.0));
+    }
+
+    #[test]
+    fn test_open_meteo_geocoding_fixture() {
+        let coords = parse_open_meteo_geocoding(OPEN_METEO_GEOCODING, "Москва").unwrap();
+        assert_eq!(
+            coords,
+            CityCoordinates {
+                lat: 55.75222,
+                lon: 37.61556
+            }
+        );
+        assert!(parse_open_meteo_geocoding(r#"{"generationtime_ms": 0.5}"#, "Nowhere").is_err());
+    }
+
+    #[test]
+    fn test_wmo_description() {
+        assert_eq!(wmo_description(Some(0)), "ясно");
+        assert_eq!(wmo_description(Some(45)), "туман");
+        assert_eq!(wmo_description(Some(95)), "гроза");
+        assert_eq!(wmo_description(None), "Неизвестно");
+    }
+}
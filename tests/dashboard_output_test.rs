@@ -0,0 +1,88 @@
+use my_dashboard::config::Config;
+use my_dashboard::golden_hour::Daylight;
+use my_dashboard::location::Location;
+use my_dashboard::{enable_demo_mode, generate_dashboard_output, DashboardOutput, Section};
+use pretty_assertions::assert_eq;
+use std::sync::Once;
+
+// DEMO режим и директория кэша задаются на весь процесс, поэтому тесты
+// вынесены в отдельный файл: сеть и ~/.cache/my_dashboard не используются
+fn setup() {
+    static SETUP: Once = Once::new();
+    SETUP.call_once(|| {
+        let cache_dir =
+            std::env::temp_dir().join(format!("my_dashboard_output_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        std::env::set_var("XDG_CACHE_HOME", &cache_dir);
+        enable_demo_mode();
+    });
+}
+
+async fn dashboard(location: Location) -> anyhow::Result<DashboardOutput> {
+    setup();
+    generate_dashboard_output(&Config::default(), &location).await
+}
+
+fn moscow() -> Location {
+    Location::new("Moscow", 55.7558, 37.6176)
+}
+
+#[tokio::test]
+async fn test_generate_dashboard_output_demo() {
+    let output = dashboard(moscow()).await.unwrap();
+
+    assert_eq!(output.location_name, "Moscow");
+    assert!(!output.summary.overall_recommendation.is_empty());
+    assert!(output.summary.warnings.is_empty());
+
+    let weather = output.weather_output.as_option().unwrap();
+    assert!(weather.current.is_some());
+    assert_eq!(weather.hourly.len(), 24);
+
+    // DEMO режим отдает фиксированные данные NOAA
+    let Section::Available(aurora) = &output.solar_output else {
+        panic!("В DEMO режиме данные о сияниях должны быть доступны");
+    };
+    assert_eq!(aurora.solar_wind.speed, 719.3);
+    assert_eq!(
+        output.summary.aurora_probability,
+        Some(aurora.visibility_probability)
+    );
+
+    assert_eq!(output.golden_hour_output.info.daylight, Daylight::Normal);
+    assert!(!output.tips_output.general_recommendations.is_empty());
+    assert!(output.astrophotography_output.as_option().is_some());
+}
+
+#[tokio::test]
+async fn test_dashboard_output_json() {
+    let output = dashboard(moscow()).await.unwrap();
+    let json = serde_json::to_value(&output).unwrap();
+
+    // Исходные числа доступны без разбора текста
+    assert_eq!(json["location"]["latitude"], 55.7558);
+    assert!(json["summary"]["weather_score"].is_number());
+    assert_eq!(json["weather"]["status"], "available");
+    assert!(json["weather"]["data"]["overall_score"].is_number());
+    assert!(json["weather"]["data"]["hourly"].is_array());
+    assert!(json["golden_hour"]["sunrise"].is_string());
+    assert!(json["golden_hour"]["is_golden_hour"].is_boolean());
+    assert!(json["tips"]["general_recommendations"].is_array());
+    assert_eq!(json["aurora"]["status"], "available");
+    assert_eq!(json["aurora"]["data"]["solar_wind"]["speed"], 719.3);
+}
+
+#[tokio::test]
+async fn test_generate_dashboard_output_edge_coords() {
+    // Северный полюс в январе: полярная ночь, но дашборд строится
+    let output = dashboard(Location::new("Северный полюс", 90.0, 180.0))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        output.golden_hour_output.info.daylight,
+        Daylight::PolarNight
+    );
+    assert_eq!(output.golden_hour_output.info.sunrise, None);
+    assert_eq!(output.weather_output.as_option().unwrap().hourly.len(), 24);
+}
@@ -0,0 +1,18 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::Europe::Moscow;
+use my_dashboard::{get_current_time, golden_hour::GoldenHourService, set_time_override};
+
+// Время задается один раз на процесс, поэтому тест вынесен в отдельный файл
+#[test]
+fn test_golden_hour_flag_follows_time_override() {
+    // 21:00 по Москве 21 июня - вечерний золотой час
+    let time = Utc.with_ymd_and_hms(2024, 6, 21, 18, 0, 0).unwrap();
+    set_time_override(time);
+    assert_eq!(get_current_time(), time);
+
+    let service = GoldenHourService::new(55.7558, 37.6176).with_timezone(Moscow);
+    let info = service.calculate_golden_hours(time);
+
+    assert!(info.is_golden_hour_at(time));
+    assert!(service.is_golden_hour());
+}